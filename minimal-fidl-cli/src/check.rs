@@ -0,0 +1,278 @@
+use crate::watch;
+use clap::ValueEnum;
+use minimal_fidl_collect::{FidlFileRs, FidlProject, FileError, LineIndex};
+use rayon::prelude::*;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::exit;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Each error with the source it points at.
+    Text,
+    /// A single JSON document, i.e for CI annotations.
+    Json,
+}
+
+/// Parses, collects and validates every `.fidl` file under `paths`.
+/// Exits with 1 if anything is wrong, unless watching in which case it runs until interrupted.
+pub fn minimal_fidl_check(paths: &[PathBuf], format: OutputFormat, watch: bool) {
+    let mut check = Check::new(paths);
+    let error_count = check.report(format);
+    if watch {
+        watch::watch(paths, |changed| {
+            check.update(changed);
+            check.report(format);
+        })
+    }
+    if error_count == 0 {
+        exit(0)
+    } else {
+        exit(1)
+    }
+}
+
+/// What is known about the checked files, kept so only changed files are parsed again.
+struct Check {
+    /// Inputs that could not be read.
+    read_errors: Vec<(PathBuf, FileError)>,
+    /// Each file's model with the declarations that were skipped, or why it could not be collected at all.
+    files: BTreeMap<PathBuf, Result<(FidlFileRs, Vec<FileError>), FileError>>,
+    /// Every file that loads, so references can be resolved across imports even when some do not.
+    project: FidlProject,
+    /// The files the project could not load along with why.
+    project_errors: Vec<(PathBuf, FileError)>,
+}
+impl Check {
+    fn new(paths: &[PathBuf]) -> Self {
+        let mut read_errors: Vec<(PathBuf, FileError)> = Vec::new();
+        let mut files: Vec<PathBuf> = Vec::new();
+        for path in paths {
+            if let Err(err) = std::fs::metadata(path) {
+                read_errors.push((path.clone(), FileError::CouldNotReadFile(err)));
+                continue;
+            }
+            match FidlProject::paths(path) {
+                Ok(paths) => files.extend(paths),
+                Err(err) => read_errors.push((path.clone(), FileError::CouldNotReadFile(err))),
+            }
+        }
+        let mut check = Self {
+            read_errors,
+            files: BTreeMap::new(),
+            project: FidlProject::default(),
+            project_errors: Vec::new(),
+        };
+        check.collect(files);
+        check.resolve(&[]);
+        check
+    }
+
+    /// Collects the `changed` files again, or forgets them if they were deleted.
+    fn update(&mut self, changed: &[PathBuf]) {
+        let (existing, deleted): (Vec<PathBuf>, Vec<PathBuf>) =
+            changed.iter().cloned().partition(|path| path.is_file());
+        for path in &deleted {
+            self.files.remove(path);
+        }
+        self.collect(existing);
+        self.resolve(changed);
+    }
+
+    /// Collects `paths` on the rayon thread pool.
+    fn collect(&mut self, paths: Vec<PathBuf>) {
+        let collected: Vec<_> = paths
+            .into_par_iter()
+            .map(|path| {
+                let collected = FidlProject::generate_file_with_recovery(&path);
+                (path, collected)
+            })
+            .collect();
+        self.files.extend(collected);
+    }
+
+    /// Whether the file at `path` already has errors of its own from being collected.
+    /// The project can reach a file through another relative path than the one it was collected from.
+    fn collect_failed(&self, path: &Path) -> bool {
+        let path = canonical(path);
+        let collected = self
+            .files
+            .iter()
+            .find(|(p, _)| canonical(p) == path)
+            .map(|(_, collected)| collected);
+        match collected {
+            Some(Ok((_, parse_errors))) => !parse_errors.is_empty(),
+            Some(Err(_)) => true,
+            None => false,
+        }
+    }
+
+    /// Brings the project up to date with the `changed` files, loading it from scratch when that is not possible.
+    fn resolve(&mut self, changed: &[PathBuf]) {
+        if !changed.is_empty() && self.project_errors.is_empty() {
+            let mut result = Ok(());
+            for path in changed {
+                if path.is_file() {
+                    result = result.and(self.project.reload(path));
+                } else {
+                    self.project.remove(path);
+                }
+            }
+            // A failed reload can leave the project half updated, so it is loaded from scratch.
+            if result.is_ok() {
+                return;
+            }
+        }
+        let (project, errors) = FidlProject::from_paths_with_recovery(self.files.keys().cloned());
+        self.project = project;
+        self.project_errors = errors;
+    }
+
+    /// Prints every error and warning, returning how many errors there are.
+    fn report(&self, format: OutputFormat) -> usize {
+        let resolve_errors: Vec<(PathBuf, FileError)> = self
+            .project
+            .check()
+            .into_iter()
+            .map(|err| (path_of(&err), err))
+            .collect();
+        // The resolver finds most undefined types too, they are only reported once.
+        let unreported = |path: &Path, span| {
+            !resolve_errors
+                .iter()
+                .any(|(p, err)| p == path && err.span() == Some(span))
+        };
+        let mut undefined: Vec<(&Path, FileError)> = Vec::new();
+        for (path, collected) in &self.files {
+            if let Ok((file, _)) = collected {
+                undefined.extend(
+                    file.undefined_types()
+                        .into_iter()
+                        .filter(|err| err.span().is_some_and(|span| unreported(path, span)))
+                        .map(|err| (path.as_path(), err)),
+                );
+            }
+        }
+        // Types of imported files that are not checked are not warned about.
+        let unused: Vec<(&Path, FileError)> = self
+            .project
+            .unused_types()
+            .into_iter()
+            .filter(|(path, _)| self.files.contains_key(*path))
+            .collect();
+
+        let mut errors: Vec<(&Path, &FileError)> = Vec::new();
+        errors.extend(
+            self.read_errors
+                .iter()
+                .map(|(path, err)| (path.as_path(), err)),
+        );
+        for (path, collected) in &self.files {
+            match collected {
+                Ok((_, parse_errors)) => {
+                    errors.extend(parse_errors.iter().map(|err| (path.as_path(), err)))
+                }
+                Err(err) => errors.push((path, err)),
+            }
+        }
+        // A file that failed to collect fails to load into the project the same way, it is only reported once.
+        // Its missing imports are only found by the project.
+        errors.extend(
+            self.project_errors
+                .iter()
+                .filter(|(path, err)| {
+                    matches!(err, FileError::ImportNotFound(..)) || !self.collect_failed(path)
+                })
+                .map(|(path, err)| (path.as_path(), err)),
+        );
+        errors.extend(
+            resolve_errors
+                .iter()
+                .map(|(path, err)| (path.as_path(), err)),
+        );
+        errors.extend(undefined.iter().map(|(path, err)| (*path, err)));
+        let error_count = errors.len();
+        errors.extend(unused.iter().map(|(path, err)| (*path, err)));
+        let warning_count = errors.len() - error_count;
+
+        let mut sources: HashMap<&Path, String> = HashMap::new();
+        for (path, _) in &errors {
+            if !sources.contains_key(path) {
+                let source = std::fs::read_to_string(path).unwrap_or_default();
+                sources.insert(path, source);
+            }
+        }
+        match format {
+            OutputFormat::Text => {
+                for (path, err) in &errors {
+                    println!("{}\n", err.render(Some(path), &sources[path]));
+                }
+                let warnings = match warning_count {
+                    0 => String::new(),
+                    count => format!(" and {} warnings", count),
+                };
+                println!(
+                    "Checked {} files, found {} errors{}",
+                    self.files.len(),
+                    error_count,
+                    warnings
+                );
+            }
+            OutputFormat::Json => {
+                let diagnostics: Vec<Value> = errors
+                    .iter()
+                    .map(|(path, err)| diagnostic(path, err, &sources[path]))
+                    .collect();
+                let output = json!({
+                    "files": self.files.len(),
+                    "errors": error_count,
+                    "warnings": warning_count,
+                    "diagnostics": diagnostics,
+                });
+                println!("{}", serde_json::to_string_pretty(&output).unwrap());
+            }
+        }
+        error_count
+    }
+}
+
+/// `path` with `..` and symbolic links resolved, or as it is if it does not exist.
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// The file an error from the whole project is in, i.e an imported file that does not parse.
+fn path_of(err: &FileError) -> PathBuf {
+    match err {
+        FileError::CouldNotParseFile(path, _)
+        | FileError::ImportNotFound(path, _)
+        | FileError::UnresolvedTypeRef(path, ..)
+        | FileError::AmbiguousTypeRef(path, ..) => path.clone(),
+        _ => PathBuf::new(),
+    }
+}
+
+/// Lines and columns are 1 based and null for errors without a location, the end is exclusive.
+fn diagnostic(path: &Path, err: &FileError, source: &str) -> Value {
+    let index = LineIndex::new(source);
+    let (start, end) = match err.span() {
+        Some(span) => {
+            let span = span.trim(source);
+            (
+                Some(index.line_column(span.start)),
+                Some(index.line_column(span.end)),
+            )
+        }
+        None => (None, None),
+    };
+    json!({
+        "file": path.display().to_string(),
+        "severity": if err.is_warning() { "warning" } else { "error" },
+        "message": err.to_string(),
+        "line": start.map(|(line, _)| line),
+        "column": start.map(|(_, column)| column),
+        "end_line": end.map(|(line, _)| line),
+        "end_column": end.map(|(_, column)| column),
+    })
+}
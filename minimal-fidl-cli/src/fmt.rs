@@ -1,68 +1,138 @@
+use crate::watch;
 use minimal_fidl_formatter::Formatter;
-use minimal_fidl_parser::{
-    BasicContext, Context, Key, Rules, Source, _var_name, grammar, RULES_SIZE,
-};
+use minimal_fidl_parser::{parse_with_context, BasicContext, Context, RULES_SIZE};
+use rayon::prelude::*;
+use similar::TextDiff;
 use std::cell::RefCell;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::exit;
 use std::time::Instant;
 
-pub fn minimal_fidl_fmt(paths: &[PathBuf], dry_run: bool) {
-    let paths = walk_dirs(&paths[0]).expect("Some error occurred");
-    // Context should be reuseable if cleared after each parse but that's unstable so we pretend we can do this but actualy make a new
-    // Context in the format file.
-    //Dummy context since it should be reusable but not sure if it is right now
-    let ctx: RefCell<BasicContext> = RefCell::new(BasicContext::new(0_usize, RULES_SIZE as usize));
+thread_local! {
+    /// Each thread of the pool reuses one context for every file it formats.
+    static CONTEXT: RefCell<BasicContext> = RefCell::new(BasicContext::new(0, RULES_SIZE as usize));
+}
+
+/// Exit code of `--check` when a file is not formatted, 1 is kept for files that could not be formatted.
+const UNFORMATTED_EXIT_CODE: i32 = 2;
+
+pub fn minimal_fidl_fmt(paths: &[PathBuf], dry_run: bool, check: bool, watch: bool) {
+    let mut files: Vec<PathBuf> = Vec::new();
+    for path in paths {
+        files.extend(walk_dirs(path).expect("Some error occurred"));
+    }
+    let exit_code = format_files(&files, dry_run, check);
+    if watch {
+        watch::watch(paths, |changed| {
+            // Deleted files have nothing to format.
+            let changed: Vec<PathBuf> = changed.iter().filter(|p| p.is_file()).cloned().collect();
+            format_files(&changed, dry_run, check);
+        })
+    }
+    exit(exit_code)
+}
+
+/// Formats each of `paths` on the rayon thread pool, returning the exit code.
+/// Results are printed in the order of `paths` once every file is formatted.
+fn format_files(paths: &[PathBuf], dry_run: bool, check: bool) -> i32 {
     let start = Instant::now();
+    let formatted: Vec<_> = paths
+        .par_iter()
+        .map(|path| {
+            let instant = Instant::now();
+            let formatted_string = format_file(path);
+            (instant.elapsed(), formatted_string)
+        })
+        .collect();
     let mut success_count = 0;
-    let mut err_count = 0;
-    for path in paths {
-        let instant = Instant::now();
-        let formatted_string: Result<String, ()> = format_file(&ctx, &path);
-        let instant_after = Instant::now();
-        let duration = instant_after - instant;
-        println!("Time to format {:#?}", duration);
+    let mut unformatted_count = 0;
+    let mut failed: Vec<&PathBuf> = Vec::new();
+    for (path, (duration, formatted_string)) in paths.iter().zip(formatted) {
         match formatted_string {
-            Ok(formatted_string) => {
-                if dry_run {
+            Ok((original, formatted_string)) => {
+                // Checking only prints the files that would change.
+                if !check {
+                    println!("\nParsing file: {:?}", path);
+                    println!("Wooo formatted file: {:?}", path);
+                    println!("Time to format {:#?}", duration);
+                }
+                if check {
+                    if original != formatted_string {
+                        print!("{}", unified_diff(path, &original, &formatted_string));
+                        unformatted_count += 1;
+                    } else {
+                        success_count += 1;
+                    }
+                } else if dry_run {
                     println!("Dry run: {:?}", path);
                     println!("Formatted text: \n{}", formatted_string);
                     success_count += 1;
+                } else if original == formatted_string {
+                    // Not rewriting formatted files keeps their modification time, which is what watching looks at.
+                    println!("Already formatted: {:?}", path);
+                    success_count += 1;
                 } else {
-                    let write_result = std::fs::write(&path, formatted_string);
+                    let write_result = std::fs::write(path, formatted_string);
                     match write_result {
                         Ok(()) => {
                             println!("Successfully wrote out formatted file: {:?}", path);
                             success_count += 1;
                         }
                         Err(_e) => {
-                            err_count += 1;
+                            failed.push(path);
                             println!("Error writing file: {:?}", path);
                         }
                     }
                 }
             }
-            Err(_e) => {
-                err_count += 1;
+            Err(message) => {
+                println!("{}", message);
+                failed.push(path);
                 println!("Error formatting file: {:?}", path);
             }
         }
     }
     let end = Instant::now();
+    // Checking formats nothing, the files that would be reformatted are counted below instead.
+    let summary = match check {
+        true => "Already formatted",
+        false => "Successfully formatted",
+    };
+    println!("{}: {}/{}", summary, success_count, paths.len());
+    if !failed.is_empty() {
+        println!("Failed to format:");
+        for path in &failed {
+            println!("    {:?}", path);
+        }
+    }
+    if check {
+        println!("Files that would be reformatted: {}", unformatted_count);
+    }
     println!(
-        "Successfully formatted: {}/{}",
-        success_count,
-        err_count + success_count
+        "Total time elapsed {:#?} on {} threads",
+        end - start,
+        rayon::current_num_threads()
     );
-    println!("Total time elapsed {:#?}", end - start);
-    if success_count == (err_count + success_count) {
-        exit(0)
+    if !failed.is_empty() {
+        1
+    } else if unformatted_count > 0 {
+        UNFORMATTED_EXIT_CODE
     } else {
-        exit(1)
+        0
     }
 }
 
+/// The changes formatting would make to `path` with 3 lines of context, like `diff -u`.
+fn unified_diff(path: &Path, original: &str, formatted: &str) -> String {
+    let name = path.display().to_string();
+    TextDiff::from_lines(original, formatted)
+        .unified_diff()
+        .context_radius(3)
+        .header(&name, &name)
+        .to_string()
+}
+
 fn is_fidl_file(path: &Path) -> bool {
     let extension = path.extension();
     match extension {
@@ -71,9 +141,12 @@ fn is_fidl_file(path: &Path) -> bool {
     }
 }
 
-fn walk_dirs(path: &PathBuf) -> Result<Vec<PathBuf>, std::io::Error> {
+/// Every `.fidl` file under `path` sorted, or `path` itself if it is a `.fidl` file.
+pub(crate) fn walk_dirs(path: &PathBuf) -> Result<Vec<PathBuf>, std::io::Error> {
     let mut ret_vec: Vec<PathBuf> = Vec::new();
-    if path.is_dir() {
+    if path.is_file() {
+        ret_vec.push(path.clone());
+    } else if path.is_dir() {
         for path in std::fs::read_dir(path)? {
             let path = path?;
             let path = path.path();
@@ -85,48 +158,30 @@ fn walk_dirs(path: &PathBuf) -> Result<Vec<PathBuf>, std::io::Error> {
             }
         }
     }
-    let ret_vec: Vec<PathBuf> = ret_vec
+    let mut ret_vec: Vec<PathBuf> = ret_vec
         .iter()
         .filter(|path| is_fidl_file(path))
         .map(|path| path.to_path_buf())
         .collect();
+    // `read_dir` order depends on the file system, sorted paths print in the same order every run.
+    ret_vec.sort();
     Ok(ret_vec)
 }
 
-fn format_file(_ctx: &RefCell<BasicContext>, path: &PathBuf) -> Result<String, ()> {
-    let input = std::fs::read_to_string(path).expect("Expected file to exist");
-    let string = input.to_string();
-    let src_len = string.len();
-    let source = Source::new(&string);
-    let position: u32 = 0;
-    let result: (bool, u32);
-    let ctx = RefCell::new(BasicContext::new(src_len, RULES_SIZE as usize));
-    {
-        let executor = _var_name(Rules::Grammar, &ctx, grammar);
-        result = executor(Key(0), &source, position);
-    }
-    if !result.0 || result.1 != src_len as u32 {
+/// The text of the file at `path` and its formatted text, or what went wrong.
+/// Watching can change or delete the file while it is formatted, which is an error like any other.
+fn format_file(path: &PathBuf) -> Result<(String, String), String> {
+    let input = std::fs::read_to_string(path)
+        .map_err(|err| format!("Error failed to read: {:?}\n{}\n", path, err))?;
+    let (result, position, publisher, failure) =
+        CONTEXT.with(|ctx| parse_with_context(&input, ctx));
+    if !result || position != input.len() as u32 {
         // Error failed to parse
-        println!(
-            "Successfully parsed up to char: {:?} out of total chars: {src_len}",
-            result.1
-        );
-        println!("Error failed to parse: {:?}\n", path);
-        return Err(());
-    }
-    let publisher = ctx.into_inner().get_publisher().clear_false();
-    // let formatted_text = publisher.print(Key(0), Some(true));
-    println!("\nParsing file: {:?}", path);
-    let fmt = Formatter::new(&string, &publisher);
-    let formatted_text = fmt.format();
-    match formatted_text {
-        Err(_formatter_err) => {
-            println!("Could not format");
-            Err(())
-        }
-        Ok(formatted_text) => {
-            println!("Wooo formatted file: {:?}", path);
-            Ok(formatted_text)
-        }
+        return Err(format!("Error failed to parse: {:?}\n{}\n", path, failure));
     }
+    let fmt = Formatter::new(&input, &publisher);
+    let formatted = fmt
+        .format()
+        .map_err(|_formatter_err| "Could not format".to_string())?;
+    Ok((input, formatted))
 }
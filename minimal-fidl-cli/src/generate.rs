@@ -0,0 +1,191 @@
+use crate::watch;
+use clap::ValueEnum;
+use minimal_fidl_collect::FidlProject;
+use minimal_fidl_generator::{CodeGenerator, PythonCodeGen, RustCodeGen, Selection};
+use std::path::{Path, PathBuf};
+use std::process::exit;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Language {
+    Python,
+    Rust,
+}
+
+/// Generates code for the selected parts of every `.fidl` file under `inputs` into `out`.
+/// Exits with 1 if a file can not be loaded or a selected name does not exist.
+/// When watching, the code of changed files is generated again until interrupted.
+pub fn minimal_fidl_generate(
+    inputs: &[PathBuf],
+    lang: Language,
+    out: &Path,
+    selection: &Selection,
+    clean: bool,
+    watch: bool,
+) {
+    match lang {
+        Language::Python => generate::<PythonCodeGen>(inputs, out, selection, clean, watch),
+        Language::Rust => generate::<RustCodeGen>(inputs, out, selection, clean, watch),
+    }
+}
+
+fn generate<G: CodeGenerator>(
+    inputs: &[PathBuf],
+    out: &Path,
+    selection: &Selection,
+    clean: bool,
+    watch: bool,
+) {
+    let mut codegen = G::new();
+    let mut interfaces: Vec<String> = Vec::new();
+    let mut type_collections: Vec<String> = Vec::new();
+    let inputs: Vec<PathBuf> = inputs.iter().map(|input| relative(input)).collect();
+    let mut projects: Vec<(PathBuf, FidlProject)> = Vec::new();
+    for input in &inputs {
+        let project = match FidlProject::new(input) {
+            Ok(project) => project,
+            Err(err) => {
+                println!("Could not load {:?}: {}", input, err);
+                exit(1)
+            }
+        };
+        for (_, file) in project.files() {
+            interfaces.extend(file.interfaces.iter().map(|i| i.name.clone()));
+            type_collections.extend(file.type_collections.iter().map(|t| t.name.clone()));
+        }
+        if let Err(err) = codegen.generate_selection(input, &project, selection) {
+            println!("Could not generate code for {:?}: {:?}", input, err);
+            exit(1)
+        }
+        projects.push((input.clone(), project));
+    }
+
+    let mut missing = false;
+    for name in &selection.interfaces {
+        if !interfaces.contains(name) {
+            println!("No interface named {}", name);
+            missing = true;
+        }
+    }
+    for name in &selection.type_collections {
+        if !type_collections.contains(name) {
+            println!("No type collection named {}", name);
+            missing = true;
+        }
+    }
+    if missing {
+        exit(1)
+    }
+
+    if clean && out.exists() {
+        if let Some(input) = inputs.iter().find(|input| contains(out, input)) {
+            println!(
+                "Refusing to clean {:?}, it contains the input {:?}",
+                out, input
+            );
+            exit(1)
+        }
+        if let Err(err) = std::fs::remove_dir_all(out) {
+            println!("Could not clean {:?}: {}", out, err);
+            exit(1)
+        }
+    }
+    if let Err(err) = codegen.emit_project(out.to_path_buf()) {
+        println!("Could not write code into {:?}: {:?}", out, err);
+        exit(1)
+    }
+    println!("Generated code into {:?}", out);
+    if watch {
+        watch::watch(&inputs, |changed| {
+            regenerate::<G>(&mut projects, changed, out, selection)
+        })
+    }
+    exit(0)
+}
+
+/// Parses the changed files again and generates code for them and the files importing them.
+/// Errors are printed and the files are picked up again on their next change.
+fn regenerate<G: CodeGenerator>(
+    projects: &mut [(PathBuf, FidlProject)],
+    changed: &[PathBuf],
+    out: &Path,
+    selection: &Selection,
+) {
+    for (input, project) in projects.iter_mut() {
+        let changed: Vec<&PathBuf> = changed
+            .iter()
+            .filter(|path| path.starts_with(&*input))
+            .collect();
+        if changed.is_empty() {
+            continue;
+        }
+        // A deleted file is forgotten once removed, so the files importing it are looked up first.
+        let mut affected: Vec<PathBuf> = changed
+            .iter()
+            .flat_map(|path| project.reverse_dependencies(path))
+            .map(Path::to_path_buf)
+            .collect();
+        let mut codegen = G::new();
+        for path in &changed {
+            if !path.is_file() {
+                project.remove(path);
+                match codegen.remove_generated(out, path) {
+                    Ok(()) => println!("Removed the code generated for {:?}", path),
+                    Err(err) => println!(
+                        "Could not remove the code generated for {:?}: {:?}",
+                        path, err
+                    ),
+                }
+            } else if let Err(err) = project.reload(path) {
+                println!("Could not load {:?}: {}", path, err);
+            }
+        }
+        // Code generated for an importing file can depend on what it imports.
+        for path in &changed {
+            // Deleted files and files that do not parse have nothing to generate.
+            if project.file(path).is_some() {
+                affected.push(path.to_path_buf());
+            }
+            affected.extend(
+                project
+                    .reverse_dependencies(path)
+                    .into_iter()
+                    .map(Path::to_path_buf),
+            );
+        }
+        affected.sort();
+        affected.dedup();
+        // Importers of a deleted file are gone too when they were deleted with it.
+        let affected: Vec<&Path> = affected
+            .iter()
+            .map(PathBuf::as_path)
+            .filter(|path| project.file(path).is_some())
+            .collect();
+        if affected.is_empty() {
+            continue;
+        }
+        let result = codegen
+            .generate_files(input, project, &affected, selection)
+            .and_then(|()| codegen.emit_project(out.to_path_buf()));
+        match result {
+            Ok(()) => println!("Generated code for {:?} into {:?}", affected, out),
+            Err(err) => println!("Could not generate code for {:?}: {:?}", input, err),
+        }
+    }
+}
+
+/// Generated paths follow the input paths, so inputs under the working directory are made relative to it.
+fn relative(path: &Path) -> PathBuf {
+    let cwd = std::env::current_dir().unwrap_or_default();
+    match path.strip_prefix(&cwd) {
+        Ok(path) => path.to_path_buf(),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// Whether `path` is `dir` or inside of it.
+fn contains(dir: &Path, path: &Path) -> bool {
+    match (dir.canonicalize(), path.canonicalize()) {
+        (Ok(dir), Ok(path)) => path.starts_with(dir),
+        _ => false,
+    }
+}
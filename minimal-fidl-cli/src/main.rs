@@ -1,6 +1,12 @@
 use clap::{Parser, Subcommand};
+use check::OutputFormat;
+use generate::Language;
+use minimal_fidl_generator::Selection;
 use std::path::PathBuf;
+mod check;
 mod fmt;
+mod generate;
+mod watch;
 /// A fictional versioning CLI
 #[derive(Debug, Parser)] // requires `derive` feature
 #[command(name = "git")]
@@ -8,6 +14,9 @@ mod fmt;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    /// Number of threads to parse and format files on, defaults to the number of CPUs
+    #[arg(short = 'j', long, global = true)]
+    jobs: Option<usize>,
 }
 
 #[derive(Debug, Subcommand)]
@@ -18,13 +27,89 @@ enum Commands {
         paths: Vec<PathBuf>,
         #[arg(short = 'd', long = "dry-run")]
         dry_run: bool,
+        /// Print a diff of the files that are not formatted instead of writing them, exiting with 2 if there are any
+        #[arg(long, conflicts_with = "dry_run")]
+        check: bool,
+        /// Keep running and format files again when they change
+        #[arg(short = 'w', long)]
+        watch: bool,
+    },
+    /// Parses, collects and validates files, exiting with 1 if any have errors
+    #[command(arg_required_else_help = true)]
+    Check {
+        /// Files or directories to check
+        paths: Vec<PathBuf>,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+        /// Keep running and check files again when they change
+        #[arg(short = 'w', long)]
+        watch: bool,
+    },
+    /// Generates code for every interface and type collection, or only the selected ones
+    #[command(arg_required_else_help = true)]
+    Generate {
+        /// Files or directories to generate code for
+        inputs: Vec<PathBuf>,
+        #[arg(long, value_enum)]
+        lang: Language,
+        /// Directory to write the generated code to
+        #[arg(long)]
+        out: PathBuf,
+        /// Only generate this interface, can be repeated
+        #[arg(long = "interface", value_name = "NAME")]
+        interfaces: Vec<String>,
+        /// Only generate this type collection, can be repeated
+        #[arg(long = "type-collection", value_name = "NAME")]
+        type_collections: Vec<String>,
+        /// Remove the output directory before writing to it
+        #[arg(long)]
+        clean: bool,
+        /// Keep running and generate code again for files that change and the files importing them
+        #[arg(short = 'w', long)]
+        watch: bool,
     },
 }
 
 fn main() {
     let args = Cli::parse();
-    println!("{:?}", args);
+    if let Some(jobs) = args.jobs {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build_global()
+            .expect("The thread pool is only built once");
+    }
     match &args.command {
-        Commands::Fmt { paths, dry_run } => fmt::minimal_fidl_fmt(paths, *dry_run),
+        Commands::Fmt {
+            paths,
+            dry_run,
+            check,
+            watch,
+        } => {
+            if !check {
+                println!("{:?}", args);
+            }
+            fmt::minimal_fidl_fmt(paths, *dry_run, *check, *watch)
+        }
+        // Nothing else may be printed so the JSON output can be parsed.
+        Commands::Check {
+            paths,
+            format,
+            watch,
+        } => check::minimal_fidl_check(paths, *format, *watch),
+        Commands::Generate {
+            inputs,
+            lang,
+            out,
+            interfaces,
+            type_collections,
+            clean,
+            watch,
+        } => {
+            let selection = Selection {
+                interfaces: interfaces.clone(),
+                type_collections: type_collections.clone(),
+            };
+            generate::minimal_fidl_generate(inputs, *lang, out, &selection, *clean, *watch)
+        }
     }
 }
@@ -0,0 +1,48 @@
+use crate::fmt::walk_dirs;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// How often the inputs are walked to look for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Calls `on_change` with the `.fidl` files under `paths` that were created, modified or deleted,
+/// sorted, until the process is interrupted.
+pub fn watch(paths: &[PathBuf], mut on_change: impl FnMut(&[PathBuf])) -> ! {
+    eprintln!("Watching {:?} for changes", paths);
+    let mut previous = snapshot(paths);
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+        let current = snapshot(paths);
+        let mut changed: Vec<PathBuf> = current
+            .iter()
+            .filter(|(path, modified)| previous.get(*path) != Some(modified))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.extend(
+            previous
+                .keys()
+                .filter(|path| !current.contains_key(*path))
+                .cloned(),
+        );
+        if !changed.is_empty() {
+            changed.sort();
+            on_change(&changed);
+        }
+        previous = current;
+    }
+}
+
+/// When each `.fidl` file under `paths` was last modified.
+fn snapshot(paths: &[PathBuf]) -> HashMap<PathBuf, SystemTime> {
+    let mut modified: HashMap<PathBuf, SystemTime> = HashMap::new();
+    for path in paths {
+        // Files can be deleted while walking, they are picked up on the next snapshot.
+        for path in walk_dirs(path).unwrap_or_default() {
+            if let Ok(time) = std::fs::metadata(&path).and_then(|m| m.modified()) {
+                modified.insert(path, time);
+            }
+        }
+    }
+    modified
+}
@@ -0,0 +1,181 @@
+use serde_json::Value;
+use std::process::{Command, Output};
+
+fn check(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_minimal-fidl-cli"))
+        .arg("check")
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_check_1() {
+    let output = check(&["tests/test_fidls/check/valid"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Checked 2 files, found 0 errors\n"
+    );
+}
+
+#[test]
+fn test_check_2() {
+    // Errors from parsing a file and from resolving another are both reported.
+    let output = check(&["tests/test_fidls/check/invalid"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("invalid/broken.fidl:4:12"), "{}", stdout);
+    assert!(stdout.contains("invalid/unresolved.fidl:8:5"), "{}", stdout);
+    // The location is only printed once, under the message.
+    assert!(
+        stdout.contains("error: The type 'Missing' could not be resolved.\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.ends_with("Checked 2 files, found 2 errors\n"),
+        "{}",
+        stdout
+    );
+
+    let output = check(&["tests/test_fidls/check/missing"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_check_3() {
+    let output = check(&["--format", "json", "tests/test_fidls/check/invalid"]);
+    assert_eq!(output.status.code(), Some(1));
+    let json: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["files"], 2);
+    assert_eq!(json["errors"], 2);
+    let diagnostics = json["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics.len(), 2);
+    let unresolved = &diagnostics[1];
+    let mut keys: Vec<&str> = unresolved
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(|key| key.as_str())
+        .collect();
+    keys.sort();
+    assert_eq!(
+        keys,
+        [
+            "column",
+            "end_column",
+            "end_line",
+            "file",
+            "line",
+            "message",
+            "severity"
+        ]
+    );
+    assert_eq!(
+        unresolved["file"],
+        "tests/test_fidls/check/invalid/unresolved.fidl"
+    );
+    assert_eq!(unresolved["severity"], "error");
+    assert_eq!(
+        (
+            &unresolved["line"],
+            &unresolved["column"],
+            &unresolved["end_line"],
+            &unresolved["end_column"]
+        ),
+        (
+            &Value::from(8),
+            &Value::from(5),
+            &Value::from(8),
+            &Value::from(30)
+        )
+    );
+    assert!(unresolved["message"]
+        .as_str()
+        .unwrap()
+        .ends_with("The type 'Missing' could not be resolved."));
+
+    // A file that could not be read has no location.
+    let output = check(&["--format", "json", "tests/test_fidls/check/missing"]);
+    assert_eq!(output.status.code(), Some(1));
+    let json: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["files"], 0);
+    assert_eq!(json["diagnostics"][0]["line"], Value::Null);
+    assert_eq!(json["diagnostics"][0]["end_column"], Value::Null);
+}
+
+#[test]
+fn test_check_4() {
+    // The output does not depend on the number of threads files are collected on.
+    let run =
+        |jobs: &str| check(&["--jobs", jobs, "--format", "json", "tests/test_fidls/check"]).stdout;
+    let output = run("1");
+    assert_eq!(run("4"), output);
+    let json: Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(json["files"], 4);
+    let files: Vec<&str> = json["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|diagnostic| diagnostic["file"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        files,
+        [
+            "tests/test_fidls/check/invalid/broken.fidl",
+            "tests/test_fidls/check/invalid/unresolved.fidl"
+        ]
+    );
+}
+
+#[test]
+fn test_check_5() {
+    // A broken file that is also imported through another relative path is reported once, and the
+    // declarations of it that do parse can still be referenced.
+    let output = check(&["tests/test_fidls/check_imports"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.matches("error:").count(), 1, "{}", stdout);
+    assert!(
+        stdout.contains("check_imports/sub/broken.fidl:10:12"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.ends_with("Checked 2 files, found 1 errors\n"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn test_check_6() {
+    // A type nothing uses is only a warning, the check still passes.
+    let output = check(&["tests/test_fidls/check_warnings"]);
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.starts_with("warning: The type 'Unused' is declared but never used.\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("check_warnings/unused.fidl:9:5"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.ends_with("Checked 1 files, found 0 errors and 1 warnings\n"),
+        "{}",
+        stdout
+    );
+
+    let output = check(&["--format", "json", "tests/test_fidls/check_warnings"]);
+    let json: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        (&json["errors"], &json["warnings"]),
+        (&Value::from(0), &Value::from(1))
+    );
+    assert_eq!(json["diagnostics"][0]["severity"], "warning");
+}
@@ -0,0 +1,105 @@
+use std::process::{Command, Output};
+
+fn fmt_check(path: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_minimal-fidl-cli"))
+        .args(["fmt", "--check", path])
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_fmt_1() {
+    let output = fmt_check("tests/test_fidls/fmt/formatted.fidl");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap().lines().next(),
+        Some("Already formatted: 1/1")
+    );
+}
+
+#[test]
+fn test_fmt_2() {
+    // Only the diff and the summary are printed, the file is left as it is.
+    let path = "tests/test_fidls/fmt/unformatted.fidl";
+    let before = std::fs::read_to_string(path).unwrap();
+    let output = fmt_check(path);
+    assert_eq!(output.status.code(), Some(2));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let diff = "--- tests/test_fidls/fmt/unformatted.fidl\n+++ tests/test_fidls/fmt/unformatted.fidl\n@@ -1,7 +1,9 @@\n package org.example\n typeCollection Types {\n-struct Point {\n-UInt8 x\n-    UInt8   y\n-}\n-}\n+\n+    struct Point {\n+        UInt8 x\n+        UInt8 y\n+    }\n+\n+}\n\\ No newline at end of file\n";
+    assert!(stdout.starts_with(diff), "{}", stdout);
+    let summary: Vec<&str> = stdout[diff.len()..].lines().collect();
+    assert_eq!(
+        summary[..2],
+        [
+            "Already formatted: 0/1",
+            "Files that would be reformatted: 1"
+        ]
+    );
+    assert!(summary[2].starts_with("Total time elapsed"));
+    assert_eq!(summary.len(), 3);
+    assert_eq!(std::fs::read_to_string(path).unwrap(), before);
+}
+
+#[test]
+fn test_fmt_3() {
+    // A file that can not be formatted exits with 1 even when others are not formatted.
+    let output = fmt_check("tests/test_fidls/fmt/broken.fidl");
+    assert_eq!(output.status.code(), Some(1));
+    let output = fmt_check("tests/test_fidls/fmt");
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("Already formatted: 1/3\nFailed to format:\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("Files that would be reformatted: 1"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn test_fmt_4() {
+    // Files are reported in path order whatever the number of threads they are formatted on.
+    let run = |jobs: &str| {
+        let output = Command::new(env!("CARGO_BIN_EXE_minimal-fidl-cli"))
+            .args(["fmt", "--check", "--jobs", jobs, "tests/test_fidls"])
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(1));
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let (report, timing) = stdout.trim_end().rsplit_once('\n').unwrap();
+        assert!(
+            timing.ends_with(&format!("on {} threads", jobs)),
+            "{}",
+            timing
+        );
+        report.to_string()
+    };
+    let report = run("1");
+    assert_eq!(run("4"), report);
+    let files: Vec<&str> = report
+        .lines()
+        .filter_map(|line| line.strip_prefix("--- "))
+        .collect();
+    let mut sorted = files.clone();
+    sorted.sort();
+    assert_eq!(files, sorted);
+    assert_eq!(files.len(), 6);
+}
+
+#[test]
+fn test_fmt_5() {
+    // A file that can not be read is reported like one that can not be parsed instead of panicking.
+    let dir = std::env::temp_dir().join("minimal_fidl_fmt_5");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("binary.fidl"), [0xff, 0xfe, 0x00]).unwrap();
+    let output = fmt_check(dir.to_str().unwrap());
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("Error failed to read: "), "{}", stdout);
+    assert!(stdout.contains("Failed to format:\n"), "{}", stdout);
+}
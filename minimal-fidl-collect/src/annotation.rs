@@ -1,6 +1,6 @@
 use minimal_fidl_parser::{BasicPublisher, Node, Rules};
 
-use crate::{annotation, FileError};
+use crate::{annotation, diagnostic::Span, EnumValue, FileError};
 
 #[derive(Debug, Clone)]
 pub struct Annotation {
@@ -74,3 +74,33 @@ pub fn annotation_constructor(
     }
     Ok(annotations)
 }
+
+/// Reads the fixed length of the array `name` from its `@length: N` annotation.
+pub fn array_length(
+    annotations: &[Annotation],
+    name: &str,
+    span: Span,
+) -> Result<Option<u64>, FileError> {
+    let mut length: Option<u64> = None;
+    for annotation in annotations {
+        if annotation.name.trim() != "length" {
+            continue;
+        }
+        let invalid =
+            |reason: String| FileError::InvalidArrayLength(name.to_string(), reason, span);
+        if length.is_some() {
+            return Err(invalid("it is given more than once".to_string()));
+        }
+        let contents = annotation.contents.trim();
+        let value =
+            EnumValue::convert_string_representation_of_number_to_value(contents.to_string())
+                .map_err(|_| invalid(format!("'{}' is not a 64-bit unsigned integer", contents)))?;
+        if value == 0 {
+            return Err(invalid(
+                "an array must hold at least one element".to_string(),
+            ));
+        }
+        length = Some(value);
+    }
+    Ok(length)
+}
@@ -0,0 +1,82 @@
+use crate::diagnostic::Span;
+use crate::{
+    annotation::{annotation_constructor, array_length, Annotation},
+    fidl_file::FileError,
+    type_ref::{IntegerRange, TypeRef},
+};
+use minimal_fidl_parser::{BasicPublisher, Node, Rules};
+
+/// An explicitly named array, `array Name of Type`.
+#[derive(Debug, Clone)]
+pub struct ArrayType {
+    start_position: u32,
+    end_position: u32,
+    pub annotations: Vec<Annotation>,
+    pub name: String,
+    pub type_n: String,
+    pub is_array: bool,
+    pub range: Option<IntegerRange>,
+    /// Set by a `@length: N` annotation, otherwise the array is dynamically sized.
+    pub length: Option<u64>,
+}
+impl ArrayType {
+    pub fn new(source: &str, publisher: &BasicPublisher, node: &Node) -> Result<Self, FileError> {
+        debug_assert_eq!(node.rule, Rules::array_type);
+        let mut name: Result<String, FileError> = Err(FileError::InternalLogicError(
+            "Uninitialized value: name in ArrayType::new".to_string(),
+        ));
+        let mut type_ref: Result<TypeRef, FileError> = Err(FileError::InternalLogicError(
+            "Uninitialized value: type_ref in ArrayType::new".to_string(),
+        ));
+        let mut annotations: Vec<Annotation> = Vec::new();
+
+        for child in node.get_children() {
+            let child = publisher.get_node(*child);
+            match child.rule {
+                Rules::comment | Rules::multiline_comment => {}
+                Rules::annotation_block => {
+                    annotations = annotation_constructor(source, publisher, child)?;
+                }
+                Rules::type_dec => {
+                    name = Ok(child.get_string(source));
+                }
+                Rules::type_ref => {
+                    type_ref = Ok(TypeRef::new(source, publisher, child)?);
+                }
+                rule => {
+                    return Err(FileError::UnexpectedNode(rule, "ArrayType::new".to_string()));
+                }
+            }
+        }
+        let type_ref = type_ref?;
+        let name = name?;
+        let length = array_length(&annotations, &name, Span::of(node))?;
+        Ok(Self {
+            name,
+            type_n: type_ref.name,
+            is_array: type_ref.is_array,
+            range: type_ref.range,
+            length,
+            annotations,
+            start_position: node.start_position,
+            end_position: node.end_position,
+        })
+    }
+
+    pub fn span(&self) -> Span {
+        Span::new(self.start_position, self.end_position)
+    }
+
+    pub fn push_if_not_exists_else_err(self, arrays: &mut Vec<ArrayType>) -> Result<(), FileError> {
+        for a in &mut *arrays {
+            if a.name == self.name {
+                return Err(FileError::ArrayTypeAlreadyExists(
+                    Box::new(a.clone()),
+                    Box::new(self.clone()),
+                ));
+            }
+        }
+        arrays.push(self);
+        Ok(())
+    }
+}
@@ -3,7 +3,8 @@ use std::{
     str::FromStr,
 };
 
-use crate::{annotation::{annotation_constructor, Annotation}, fidl_file::FileError, VariableDeclaration};
+use crate::diagnostic::Span;
+use crate::{annotation::{annotation_constructor, Annotation}, fidl_file::FileError, type_ref::{IntegerRange, TypeRef}, VariableDeclaration};
 use minimal_fidl_parser::{BasicPublisher, Key, Node, Rules};
 #[derive(Debug, Clone)]
 pub struct Attribute {
@@ -12,6 +13,11 @@ pub struct Attribute {
     pub annotations: Vec<Annotation>,
     pub name: String,
     pub type_n: String,
+    /// Clients can only get the value, generated code has no set method.
+    pub readonly: bool,
+    /// Clients can not be notified of changes, generated code has no change callback.
+    pub no_subscriptions: bool,
+    pub range: Option<IntegerRange>,
 }
 impl Attribute {
     pub fn new(source: &str, publisher: &BasicPublisher, node: &Node) -> Result<Self, FileError> {
@@ -23,6 +29,9 @@ impl Attribute {
             "Uninitialized value: name in Attribute::new".to_string(),
         ));
         let mut annotations: Vec<Annotation> = Vec::new();
+        let mut readonly = false;
+        let mut no_subscriptions = false;
+        let mut range: Option<IntegerRange> = None;
         for child in node.get_children() {
             let child = publisher.get_node(*child);
             match child.rule {
@@ -31,9 +40,17 @@ impl Attribute {
                     annotations = annotation_constructor(source, publisher, child)?;
                 }
                 Rules::type_ref => {
-                    type_n = Ok(child.get_string(source));
+                    let res = TypeRef::new(source, publisher, child)?;
+                    range = res.range;
+                    // Attributes keep the array suffix as part of their type name.
+                    match res.is_array {
+                        true => type_n = Ok(format!("{}[]", res.name)),
+                        false => type_n = Ok(res.name),
+                    }
                 }
                 Rules::variable_name => name = Ok(child.get_string(source)),
+                Rules::readonly => readonly = true,
+                Rules::no_subscriptions => no_subscriptions = true,
 
                 rule => {
                     return Err(FileError::UnexpectedNode(
@@ -46,11 +63,18 @@ impl Attribute {
         Ok(Self {
             name: name?,
             type_n: type_n?,
+            readonly,
+            no_subscriptions,
+            range,
             annotations,
             start_position: node.start_position,
             end_position: node.end_position,
         })
     }
+    pub fn span(&self) -> Span {
+        Span::new(self.start_position, self.end_position)
+    }
+
     pub fn push_if_not_exists_else_err(
         self,
         attributes: &mut Vec<Attribute>,
@@ -58,8 +82,8 @@ impl Attribute {
         for attr in &mut *attributes {
             if attr.name == self.name {
                 return Err(FileError::AttributeAlreadyExists(
-                    attr.clone(),
-                    self.clone(),
+                    Box::new(attr.clone()),
+                    Box::new(self.clone()),
                 ));
             }
         }
@@ -0,0 +1,78 @@
+use crate::diagnostic::Span;
+use crate::{annotation::{annotation_constructor, Annotation}, fidl_file::FileError, method::Method, VariableDeclaration};
+use minimal_fidl_parser::{BasicPublisher, Node, Rules};
+/// An event sent by the provider, subscribers register a callback that receives the output
+/// parameters as a single typed payload.
+#[derive(Debug, Clone)]
+pub struct Broadcast {
+    start_position: u32,
+    end_position: u32,
+    pub annotations: Vec<Annotation>,
+    pub name: String,
+    pub selective: bool,
+    pub output_parameters: Vec<VariableDeclaration>,
+}
+impl Broadcast {
+    pub fn new(source: &str, publisher: &BasicPublisher, node: &Node) -> Result<Self, FileError> {
+        debug_assert_eq!(node.rule, Rules::broadcast);
+        let mut name: Result<String, FileError> = Err(FileError::InternalLogicError(
+            "Uninitialized value: name in Broadcast::new".to_string(),
+        ));
+        let mut selective = false;
+        let mut output_parameters: Vec<VariableDeclaration> = Vec::new();
+        let mut annotations: Vec<Annotation> = Vec::new();
+
+        for child in node.get_children() {
+            let child = publisher.get_node(*child);
+            match child.rule {
+                Rules::comment
+                | Rules::multiline_comment
+                | Rules::open_bracket
+                | Rules::close_bracket => {}
+                Rules::annotation_block => {
+                    annotations = annotation_constructor(source, publisher, child)?;
+                }
+                Rules::variable_name => {
+                    name = Ok(child.get_string(source));
+                }
+                Rules::selective => {
+                    selective = true;
+                }
+                Rules::output_params => {
+                    // A broadcast only has out arguments so it shares the parameter handling of Method.
+                    Method::params(source, publisher, child, &mut output_parameters)?;
+                }
+                rule => {
+                    return Err(FileError::UnexpectedNode(rule, "Broadcast::new".to_string()));
+                }
+            }
+        }
+        Ok(Self {
+            name: name?,
+            start_position: node.start_position,
+            annotations,
+            end_position: node.end_position,
+            selective,
+            output_parameters,
+        })
+    }
+    pub fn span(&self) -> Span {
+        Span::new(self.start_position, self.end_position)
+    }
+
+    pub fn push_if_not_exists_else_err(
+        self,
+        broadcasts: &mut Vec<Broadcast>,
+    ) -> Result<(), FileError> {
+        for s in &mut *broadcasts {
+            if s.name == self.name {
+                return Err(FileError::BroadcastAlreadyExists(
+                    Box::new(s.clone()),
+                    Box::new(self.clone()),
+                ));
+            }
+        }
+        broadcasts.push(self);
+        Ok(())
+    }
+}
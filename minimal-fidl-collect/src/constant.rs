@@ -0,0 +1,451 @@
+use crate::diagnostic::Span;
+use crate::{
+    annotation::{annotation_constructor, Annotation},
+    fidl_file::FileError,
+    structure::Structure,
+    type_ref::{IntegerRange, TypeRef},
+};
+use minimal_fidl_parser::{BasicPublisher, Key, Node, Rules};
+
+/// The evaluated value of a constant initializer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstantValue {
+    Integer(i128),
+    Float(f64),
+    Boolean(bool),
+    String(String),
+    Array(Vec<ConstantValue>),
+    Struct(Vec<(String, ConstantValue)>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Constant {
+    start_position: u32,
+    end_position: u32,
+    pub annotations: Vec<Annotation>,
+    pub name: String,
+    pub type_n: String,
+    pub is_array: bool,
+    pub range: Option<IntegerRange>,
+    pub value: ConstantValue,
+}
+impl Constant {
+    /// `constants` are the ones declared before this one in the same interface or type collection,
+    /// they are the only constants the initializer can refer to.
+    /// `structures` are the structs of the same interface or type collection, the fields of a struct
+    /// initializer are checked against the one it names.
+    pub fn new(
+        source: &str,
+        publisher: &BasicPublisher,
+        node: &Node,
+        constants: &[Constant],
+        structures: &[Structure],
+    ) -> Result<Self, FileError> {
+        debug_assert_eq!(node.rule, Rules::constant);
+        let mut name: Result<String, FileError> = Err(FileError::InternalLogicError(
+            "Uninitialized value: name in Constant::new".to_string(),
+        ));
+        let mut type_ref: Result<TypeRef, FileError> = Err(FileError::InternalLogicError(
+            "Uninitialized value: type_ref in Constant::new".to_string(),
+        ));
+        let mut initializer: Option<Key> = None;
+        let mut annotations: Vec<Annotation> = Vec::new();
+
+        for child_key in node.get_children() {
+            let child = publisher.get_node(*child_key);
+            match child.rule {
+                Rules::comment | Rules::multiline_comment => {}
+                Rules::annotation_block => {
+                    annotations = annotation_constructor(source, publisher, child)?;
+                }
+                Rules::type_ref => {
+                    type_ref = Ok(TypeRef::new(source, publisher, child)?);
+                }
+                Rules::variable_name => {
+                    name = Ok(child.get_string(source));
+                }
+                Rules::expression | Rules::array_initializer | Rules::struct_initializer => {
+                    initializer = Some(*child_key);
+                }
+                rule => {
+                    return Err(FileError::UnexpectedNode(rule, "Constant::new".to_string()));
+                }
+            }
+        }
+        let name = name?;
+        let type_ref = type_ref?;
+        let initializer = match initializer {
+            Some(key) => publisher.get_node(key),
+            None => {
+                return Err(FileError::InternalLogicError(
+                    "Uninitialized value: initializer in Constant::new".to_string(),
+                ))
+            }
+        };
+        let evaluator = Evaluator {
+            source,
+            publisher,
+            name: &name,
+            constants,
+            structures,
+            span: Span::of(node),
+        };
+        let value = evaluator.initializer(initializer)?;
+        let value = evaluator.coerce(value, &type_ref.name, type_ref.is_array, type_ref.range)?;
+        Ok(Self {
+            name,
+            type_n: type_ref.name,
+            is_array: type_ref.is_array,
+            range: type_ref.range,
+            value,
+            annotations,
+            start_position: node.start_position,
+            end_position: node.end_position,
+        })
+    }
+
+    pub fn span(&self) -> Span {
+        Span::new(self.start_position, self.end_position)
+    }
+
+    pub fn push_if_not_exists_else_err(self, constants: &mut Vec<Constant>) -> Result<(), FileError> {
+        for s in &mut *constants {
+            if s.name == self.name {
+                return Err(FileError::ConstantAlreadyExists(
+                    Box::new(s.clone()),
+                    Box::new(self.clone()),
+                ));
+            }
+        }
+        constants.push(self);
+        Ok(())
+    }
+}
+
+/// Walks an initializer's parse tree and folds it into a single value.
+struct Evaluator<'a> {
+    source: &'a str,
+    publisher: &'a BasicPublisher,
+    name: &'a str,
+    constants: &'a [Constant],
+    structures: &'a [Structure],
+    /// The whole declaration, initializers are not precise enough to point at the bad part.
+    span: Span,
+}
+impl Evaluator<'_> {
+    fn invalid(&self, reason: String) -> FileError {
+        FileError::InvalidConstant(self.name.to_string(), reason, self.span)
+    }
+
+    fn initializer(&self, node: &Node) -> Result<ConstantValue, FileError> {
+        match node.rule {
+            Rules::expression => self.expression(node),
+            Rules::array_initializer => {
+                let mut values: Vec<ConstantValue> = Vec::new();
+                for child in node.get_children() {
+                    values.push(self.initializer(self.publisher.get_node(*child))?);
+                }
+                Ok(ConstantValue::Array(values))
+            }
+            Rules::struct_initializer => {
+                let mut fields: Vec<(String, ConstantValue)> = Vec::new();
+                for child in node.get_children() {
+                    let field = self.field_initializer(self.publisher.get_node(*child))?;
+                    if fields.iter().any(|(name, _)| *name == field.0) {
+                        return Err(self.invalid(format!("the field '{}' is set twice", field.0)));
+                    }
+                    fields.push(field);
+                }
+                Ok(ConstantValue::Struct(fields))
+            }
+            rule => Err(FileError::UnexpectedNode(
+                rule,
+                "Evaluator::initializer".to_string(),
+            )),
+        }
+    }
+
+    fn field_initializer(&self, node: &Node) -> Result<(String, ConstantValue), FileError> {
+        debug_assert_eq!(node.rule, Rules::field_initializer);
+        let mut name: Result<String, FileError> = Err(FileError::InternalLogicError(
+            "Uninitialized value: name in Evaluator::field_initializer".to_string(),
+        ));
+        let mut value: Result<ConstantValue, FileError> = Err(FileError::InternalLogicError(
+            "Uninitialized value: value in Evaluator::field_initializer".to_string(),
+        ));
+        for child in node.get_children() {
+            let child = self.publisher.get_node(*child);
+            match child.rule {
+                Rules::variable_name => name = Ok(child.get_string(self.source)),
+                _ => value = Ok(self.initializer(child)?),
+            }
+        }
+        Ok((name?, value?))
+    }
+
+    fn expression(&self, node: &Node) -> Result<ConstantValue, FileError> {
+        debug_assert_eq!(node.rule, Rules::expression);
+        self.fold(node, Rules::term, Rules::additive_operator)
+    }
+
+    fn term(&self, node: &Node) -> Result<ConstantValue, FileError> {
+        debug_assert_eq!(node.rule, Rules::term);
+        self.fold(node, Rules::factor, Rules::multiplicative_operator)
+    }
+
+    /// Both levels of binary operators are left associative: `operand (operator operand)*`.
+    fn fold(&self, node: &Node, operand: Rules, operator: Rules) -> Result<ConstantValue, FileError> {
+        let mut result: Option<ConstantValue> = None;
+        let mut pending_operator: Option<String> = None;
+        for child in node.get_children() {
+            let child = self.publisher.get_node(*child);
+            if child.rule == operator {
+                pending_operator = Some(child.get_string(self.source));
+                continue;
+            }
+            let value = match child.rule {
+                rule if rule != operand => {
+                    return Err(FileError::UnexpectedNode(rule, "Evaluator::fold".to_string()));
+                }
+                Rules::term => self.term(child)?,
+                _ => self.factor(child)?,
+            };
+            result = match (result, pending_operator.take()) {
+                (None, _) => Some(value),
+                (Some(lhs), Some(op)) => Some(self.binary(lhs, &op, value)?),
+                (Some(_), None) => {
+                    return Err(FileError::InternalLogicError(
+                        "Two operands without an operator in Evaluator::fold".to_string(),
+                    ))
+                }
+            };
+        }
+        result.ok_or(FileError::InternalLogicError(
+            "Empty expression in Evaluator::fold".to_string(),
+        ))
+    }
+
+    fn factor(&self, node: &Node) -> Result<ConstantValue, FileError> {
+        debug_assert_eq!(node.rule, Rules::factor);
+        let children = node.get_children();
+        debug_assert_eq!(children.len(), 1);
+        let child = self.publisher.get_node(children[0]);
+        match child.rule {
+            Rules::number => self.number(child),
+            Rules::boolean => Ok(ConstantValue::Boolean(child.get_string(self.source) == "true")),
+            Rules::string_literal => {
+                let literal = child.get_string(self.source);
+                Ok(ConstantValue::String(literal[1..literal.len() - 1].to_string()))
+            }
+            Rules::parenthesised => {
+                let inner = child.get_children();
+                debug_assert_eq!(inner.len(), 1);
+                self.expression(self.publisher.get_node(inner[0]))
+            }
+            Rules::negation => {
+                let inner = child.get_children();
+                debug_assert_eq!(inner.len(), 1);
+                match self.factor(self.publisher.get_node(inner[0]))? {
+                    ConstantValue::Integer(i) => i
+                        .checked_neg()
+                        .map(ConstantValue::Integer)
+                        .ok_or_else(|| self.invalid(format!("'-({})' overflows", i))),
+                    ConstantValue::Float(f) => Ok(ConstantValue::Float(-f)),
+                    value => Err(self.invalid(format!("cannot negate {:?}", value))),
+                }
+            }
+            Rules::constant_ref => {
+                let name = child.get_string(self.source);
+                for constant in self.constants {
+                    if constant.name == name {
+                        return Ok(constant.value.clone());
+                    }
+                }
+                Err(FileError::ConstantNotFound(name, Span::of(child)))
+            }
+            rule => Err(FileError::UnexpectedNode(rule, "Evaluator::factor".to_string())),
+        }
+    }
+
+    fn number(&self, node: &Node) -> Result<ConstantValue, FileError> {
+        debug_assert_eq!(node.rule, Rules::number);
+        // The grammar allows whitespace between a sign and its digits.
+        let text: String = node
+            .get_string(self.source)
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+        let not_a_number = || self.invalid(format!("'{}' is not a valid number", text));
+        let child = self.publisher.get_node(node.get_children()[0]);
+        match child.rule {
+            Rules::hex => i128::from_str_radix(&text[2..], 16)
+                .map(ConstantValue::Integer)
+                .map_err(|_| not_a_number()),
+            Rules::binary => i128::from_str_radix(&text[2..], 2)
+                .map(ConstantValue::Integer)
+                .map_err(|_| not_a_number()),
+            Rules::float => text
+                .parse::<f64>()
+                .map(ConstantValue::Float)
+                .map_err(|_| not_a_number()),
+            Rules::integer => match text.split_once(['e', 'E']) {
+                None => text
+                    .parse::<i128>()
+                    .map(ConstantValue::Integer)
+                    .map_err(|_| not_a_number()),
+                Some((mantissa, exponent)) => {
+                    let mantissa = mantissa.parse::<i128>().map_err(|_| not_a_number())?;
+                    let exponent = exponent.parse::<i32>().map_err(|_| not_a_number())?;
+                    if exponent < 0 {
+                        return Ok(ConstantValue::Float(mantissa as f64 * 10f64.powi(exponent)));
+                    }
+                    10i128
+                        .checked_pow(exponent as u32)
+                        .and_then(|scale| mantissa.checked_mul(scale))
+                        .map(ConstantValue::Integer)
+                        .ok_or_else(|| self.invalid(format!("'{}' is too large", text)))
+                }
+            },
+            rule => Err(FileError::UnexpectedNode(rule, "Evaluator::number".to_string())),
+        }
+    }
+
+    fn binary(
+        &self,
+        lhs: ConstantValue,
+        op: &str,
+        rhs: ConstantValue,
+    ) -> Result<ConstantValue, FileError> {
+        use ConstantValue::{Float, Integer};
+        let overflow = || self.invalid(format!("'{:?} {} {:?}' overflows", lhs, op, rhs));
+        match (&lhs, &rhs) {
+            (Integer(a), Integer(b)) => {
+                let result = match op {
+                    "+" => a.checked_add(*b),
+                    "-" => a.checked_sub(*b),
+                    "*" => a.checked_mul(*b),
+                    "/" | "%" if *b == 0 => {
+                        return Err(self.invalid("divides by zero".to_string()));
+                    }
+                    "/" => a.checked_div(*b),
+                    "%" => a.checked_rem(*b),
+                    _ => None,
+                };
+                result.map(Integer).ok_or_else(overflow)
+            }
+            (Integer(_) | Float(_), Integer(_) | Float(_)) => {
+                let as_float = |v: &ConstantValue| match v {
+                    Integer(i) => *i as f64,
+                    Float(f) => *f,
+                    _ => unreachable!(),
+                };
+                let (a, b) = (as_float(&lhs), as_float(&rhs));
+                let result = match op {
+                    "+" => a + b,
+                    "-" => a - b,
+                    "*" => a * b,
+                    "/" => a / b,
+                    _ => a % b,
+                };
+                if result.is_finite() {
+                    Ok(Float(result))
+                } else {
+                    Err(overflow())
+                }
+            }
+            (ConstantValue::String(a), ConstantValue::String(b)) if op == "+" => {
+                Ok(ConstantValue::String(format!("{}{}", a, b)))
+            }
+            _ => Err(self.invalid(format!("cannot apply '{}' to {:?} and {:?}", op, lhs, rhs))),
+        }
+    }
+
+    /// Checks the value fits the declared type, widening integers where a float is expected.
+    /// The fields of a struct declared next to the constant are checked the same way, other
+    /// types that are not built in are taken as is since they are declared elsewhere.
+    fn coerce(
+        &self,
+        value: ConstantValue,
+        type_n: &str,
+        is_array: bool,
+        integer_range: Option<IntegerRange>,
+    ) -> Result<ConstantValue, FileError> {
+        if is_array {
+            return match value {
+                ConstantValue::Array(values) => Ok(ConstantValue::Array(
+                    values
+                        .into_iter()
+                        .map(|v| self.coerce(v, type_n, false, integer_range))
+                        .collect::<Result<Vec<ConstantValue>, FileError>>()?,
+                )),
+                value => Err(self.invalid(format!("expected an array but got {:?}", value))),
+            };
+        }
+        let range: Option<(i128, i128)> = match type_n {
+            "UInt8" => Some((0, u8::MAX as i128)),
+            "Int8" => Some((i8::MIN as i128, i8::MAX as i128)),
+            "UInt16" => Some((0, u16::MAX as i128)),
+            "Int16" => Some((i16::MIN as i128, i16::MAX as i128)),
+            "UInt32" => Some((0, u32::MAX as i128)),
+            "Int32" => Some((i32::MIN as i128, i32::MAX as i128)),
+            "UInt64" => Some((0, u64::MAX as i128)),
+            "Int64" => Some((i64::MIN as i128, i64::MAX as i128)),
+            "Integer" => match integer_range {
+                Some(r) => Some((r.min as i128, r.max as i128)),
+                None => Some((i64::MIN as i128, i64::MAX as i128)),
+            },
+            _ => None,
+        };
+        match (type_n, value) {
+            (_, ConstantValue::Integer(i)) if range.is_some() => {
+                let (min, max) = range.unwrap_or_default();
+                if i < min || i > max {
+                    return Err(self.invalid(format!("{} does not fit in {}", i, type_n)));
+                }
+                Ok(ConstantValue::Integer(i))
+            }
+            ("Float" | "Double", ConstantValue::Integer(i)) => Ok(ConstantValue::Float(i as f64)),
+            ("Float" | "Double", ConstantValue::Float(f)) => Ok(ConstantValue::Float(f)),
+            ("Boolean", ConstantValue::Boolean(b)) => Ok(ConstantValue::Boolean(b)),
+            ("String", ConstantValue::String(s)) => Ok(ConstantValue::String(s)),
+            ("ByteBuffer", value) => self.coerce(value, "UInt8", true, None),
+            (type_n, ConstantValue::Struct(fields)) => {
+                match self.structures.iter().find(|s| s.name == type_n) {
+                    Some(structure) => self.coerce_fields(structure, fields),
+                    None => Ok(ConstantValue::Struct(fields)),
+                }
+            }
+            (type_n, value) if range.is_some() || is_built_in(type_n) => Err(
+                self.invalid(format!("{:?} is not a valid {}", value, type_n)),
+            ),
+            (_, value) => Ok(value),
+        }
+    }
+
+    /// Fields inherited from a base are taken as is, the base may be declared in another file.
+    fn coerce_fields(
+        &self,
+        structure: &Structure,
+        fields: Vec<(String, ConstantValue)>,
+    ) -> Result<ConstantValue, FileError> {
+        let mut coerced: Vec<(String, ConstantValue)> = Vec::new();
+        for (name, value) in fields {
+            let value = match structure.contents.iter().find(|field| field.name == name) {
+                Some(field) => self.coerce(value, &field.type_n, field.is_array, field.range)?,
+                None if structure.extends.is_some() => value,
+                None => {
+                    return Err(self.invalid(format!(
+                        "the struct '{}' has no field '{}'",
+                        structure.name, name
+                    )))
+                }
+            };
+            coerced.push((name, value));
+        }
+        Ok(ConstantValue::Struct(coerced))
+    }
+}
+
+fn is_built_in(type_n: &str) -> bool {
+    matches!(type_n, "Float" | "Double" | "Boolean" | "String" | "ByteBuffer")
+}
@@ -0,0 +1,207 @@
+use std::path::Path;
+
+use crate::fidl_file::FileError;
+use minimal_fidl_parser::Node;
+
+/// A range of byte offsets into a file's source, the end is exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Span {
+    pub start: u32,
+    pub end: u32,
+}
+impl Span {
+    pub fn new(start: u32, end: u32) -> Self {
+        Self { start, end }
+    }
+
+    pub(crate) fn of(node: &Node) -> Self {
+        Self::new(node.start_position, node.end_position)
+    }
+
+    /// Shrinks the span so it does not start or end with whitespace.
+    /// Most declarations include the whitespace before them in the parse tree.
+    pub fn trim(&self, source: &str) -> Self {
+        let start = (self.start as usize).min(source.len());
+        let end = (self.end as usize).clamp(start, source.len());
+        let text = &source[start..end];
+        let leading = text.len() - text.trim_start().len();
+        let trailing = text.len() - text.trim_end().len();
+        if leading == text.len() {
+            return Self::new(start as u32, start as u32);
+        }
+        Self::new((start + leading) as u32, (end - trailing) as u32)
+    }
+}
+
+/// Converts byte offsets in a source string into 1 based lines and columns.
+#[derive(Debug)]
+pub struct LineIndex<'a> {
+    source: &'a str,
+    /// The byte offset each line starts at.
+    line_starts: Vec<usize>,
+}
+impl<'a> LineIndex<'a> {
+    pub fn new(source: &'a str) -> Self {
+        let mut line_starts: Vec<usize> = vec![0];
+        line_starts.extend(source.match_indices('\n').map(|(i, _)| i + 1));
+        Self {
+            source,
+            line_starts,
+        }
+    }
+
+    /// The line and column of a byte offset, columns count characters rather than bytes.
+    pub fn line_column(&self, offset: u32) -> (usize, usize) {
+        let offset = (offset as usize).min(self.source.len());
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(next_line) => next_line - 1,
+        };
+        let column = self.source[self.line_starts[line]..offset].chars().count() + 1;
+        (line + 1, column)
+    }
+
+    /// The text of a 1 based line without its line ending.
+    pub fn line(&self, line: usize) -> &'a str {
+        let start = self.line_starts[line - 1];
+        let end = match self.line_starts.get(line) {
+            Some(next) => *next,
+            None => self.source.len(),
+        };
+        self.source[start..end].trim_end_matches(['\n', '\r'])
+    }
+}
+
+impl FileError {
+    /// Where in the file the error happened, for errors that can be pinned to a location.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            FileError::InterfaceAlreadyExists(_, second) => Some(second.span()),
+            FileError::StructAlreadyExists(_, second) => Some(second.span()),
+            FileError::UnionAlreadyExists(_, second) => Some(second.span()),
+            FileError::AttributeAlreadyExists(_, second) => Some(second.span()),
+            FileError::TypeDefAlreadyExists(_, second) => Some(second.span()),
+            FileError::ArrayTypeAlreadyExists(_, second) => Some(second.span()),
+            FileError::MapAlreadyExists(_, second) => Some(second.span()),
+            FileError::MethodAlreadyExists(_, second) => Some(second.span()),
+            FileError::BroadcastAlreadyExists(_, second) => Some(second.span()),
+            FileError::ConstantAlreadyExists(_, second) => Some(second.span()),
+            FileError::EnumerationAlreadyExists(_, second) => Some(second.span()),
+            FileError::EnumValueAlreadyExists(_, second) => Some(second.span()),
+            FileError::TypeCollectionAlreadyExists(_, second) => Some(second.span()),
+            FileError::VersionAlreadyExists(version) => Some(version.span()),
+            FileError::PackageAlreadyExists(package) => Some(package.span()),
+            FileError::FireAndForgetMethodHasResponse(method) => Some(method.span()),
+            FileError::CouldNotParseFile(_, failure) | FileError::CouldNotParseSourceString(failure) => {
+                let found = failure.found.as_ref().map_or(0, |found| found.len() as u32);
+                Some(Span::new(failure.position, failure.position + found))
+            }
+            FileError::FieldAlreadyExists(_, span)
+            | FileError::LengthWithoutArray(_, span)
+            | FileError::MapKeyIsArray(_, span)
+            | FileError::ConstantNotFound(_, span)
+            | FileError::InvalidConstant(_, _, span)
+            | FileError::InvalidIntegerRange(_, _, span)
+            | FileError::InvalidArrayLength(_, _, span)
+            | FileError::TypeCollectionRequiresAName(span)
+            | FileError::ExtendsBaseNotFound(_, _, span)
+            | FileError::CyclicExtends(_, span)
+            | FileError::UnresolvedTypeRef(_, span, _)
+            | FileError::AmbiguousTypeRef(_, span, _, _)
+            | FileError::UndefinedType(_, span)
+            | FileError::UnusedType(_, span) => Some(*span),
+            FileError::UnexpectedNode(_, _)
+            | FileError::CouldNotReadFile(_)
+            | FileError::InternalLogicError(_)
+            | FileError::CouldNotConvertToInteger(_)
+            | FileError::ImportNotFound(_, _) => None,
+        }
+    }
+
+    /// Whether the file is still valid despite the error, i.e a type that is declared but never used.
+    pub fn is_warning(&self) -> bool {
+        matches!(self, FileError::UnusedType(..))
+    }
+
+    /// The earlier declaration an `AlreadyExists` error clashes with.
+    pub fn first_declaration(&self) -> Option<Span> {
+        match self {
+            FileError::InterfaceAlreadyExists(first, _) => Some(first.span()),
+            FileError::StructAlreadyExists(first, _) => Some(first.span()),
+            FileError::UnionAlreadyExists(first, _) => Some(first.span()),
+            FileError::AttributeAlreadyExists(first, _) => Some(first.span()),
+            FileError::TypeDefAlreadyExists(first, _) => Some(first.span()),
+            FileError::ArrayTypeAlreadyExists(first, _) => Some(first.span()),
+            FileError::MapAlreadyExists(first, _) => Some(first.span()),
+            FileError::MethodAlreadyExists(first, _) => Some(first.span()),
+            FileError::BroadcastAlreadyExists(first, _) => Some(first.span()),
+            FileError::ConstantAlreadyExists(first, _) => Some(first.span()),
+            FileError::EnumerationAlreadyExists(first, _) => Some(first.span()),
+            FileError::EnumValueAlreadyExists(first, _) => Some(first.span()),
+            FileError::TypeCollectionAlreadyExists(first, _) => Some(first.span()),
+            _ => None,
+        }
+    }
+
+    /// Renders the error with its location and the offending source line underlined, i.e
+    /// ```text
+    /// error: The Struct: 'Point' already exists.
+    ///  --> types.fidl:7:5
+    ///   |
+    /// 7 |     struct Point {
+    ///   |     ^^^^^^^^^^^^^^
+    ///   = note: first declared at types.fidl:3:5
+    /// ```
+    /// `source` must be the source of the file the error came from.
+    pub fn render(&self, path: Option<&Path>, source: &str) -> String {
+        let severity = match self.is_warning() {
+            true => "warning",
+            false => "error",
+        };
+        let mut rendered = format!("{}: {}", severity, self);
+        let span = match self.span() {
+            Some(span) => span.trim(source),
+            None => return rendered,
+        };
+        let index = LineIndex::new(source);
+        let location = |span: Span| {
+            let (line, column) = index.line_column(span.start);
+            match path {
+                Some(path) => format!("{}:{}:{}", path.display(), line, column),
+                None => format!("{}:{}", line, column),
+            }
+        };
+        let (line, column) = index.line_column(span.start);
+        let text = index.line(line);
+        // Multi line spans are only underlined up to the end of their first line.
+        let (end_line, end_column) = index.line_column(span.end);
+        let end_column = match end_line == line {
+            true => end_column,
+            false => text.chars().count() + 1,
+        };
+        let gutter = " ".repeat(line.to_string().len());
+        rendered.push_str(&format!("\n{}--> {}", gutter, location(span)));
+        rendered.push_str(&format!("\n{} |", gutter));
+        rendered.push_str(&format!("\n{} | {}", line, text));
+        // Keep tabs so the carets line up with the text above them.
+        let indent: String = text
+            .chars()
+            .take(column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        rendered.push_str(&format!(
+            "\n{} | {}{}",
+            gutter,
+            indent,
+            "^".repeat(end_column.saturating_sub(column).max(1))
+        ));
+        if let Some(first) = self.first_declaration() {
+            rendered.push_str(&format!(
+                "\n{} = note: first declared at {}",
+                gutter,
+                location(first.trim(source))
+            ));
+        }
+        rendered
+    }
+}
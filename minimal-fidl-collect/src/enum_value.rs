@@ -3,6 +3,7 @@ use std::{
     str::FromStr,
 };
 
+use crate::diagnostic::Span;
 use crate::{annotation::{annotation_constructor, Annotation}, fidl_file::FileError};
 use minimal_fidl_parser::{BasicPublisher, Key, Node, Rules};
 #[derive(Debug, Clone)]
@@ -54,13 +55,20 @@ impl EnumValue {
         })
     }
 
+    pub fn span(&self) -> Span {
+        Span::new(self.start_position, self.end_position)
+    }
+
     pub fn push_if_not_exists_else_err(
         self,
         enum_values: &mut Vec<EnumValue>,
     ) -> Result<(), FileError> {
         for s in &mut *enum_values {
             if s.name == self.name {
-                return Err(FileError::EnumValueAlreadyExists(s.clone(), self.clone()));
+                return Err(FileError::EnumValueAlreadyExists(
+                    Box::new(s.clone()),
+                    Box::new(self.clone()),
+                ));
             }
         }
         enum_values.push(self);
@@ -3,7 +3,9 @@ use std::{
     str::FromStr,
 };
 
-use crate::{annotation::{annotation_constructor, Annotation}, enum_value::EnumValue, fidl_file::FileError, VariableDeclaration};
+use crate::diagnostic::Span;
+use crate::resolver::SymbolKind;
+use crate::{annotation::{annotation_constructor, Annotation}, enum_value::EnumValue, fidl_file::{type_declarations, Declarations, Extendable, FidlFileRs, FileError}, TypeRef, VariableDeclaration};
 use minimal_fidl_parser::{BasicPublisher, Key, Node, Rules};
 #[derive(Debug, Clone)]
 pub struct Enumeration {
@@ -11,6 +13,7 @@ pub struct Enumeration {
     end_position: u32,
     pub annotations: Vec<Annotation>,
     pub name: String,
+    pub extends: Option<TypeRef>,
     pub values: Vec<EnumValue>,
 }
 impl Enumeration {
@@ -20,6 +23,7 @@ impl Enumeration {
             "Uninitialized value: name in Enumeration::new".to_string(),
         ));
         let mut annotations: Vec<Annotation> = Vec::new();
+        let mut extends: Option<TypeRef> = None;
 
         let mut values: Vec<EnumValue> = Vec::new();
         for child in node.get_children() {
@@ -35,6 +39,9 @@ impl Enumeration {
                 Rules::type_dec => {
                     name = Ok(child.get_string(source));
                 }
+                Rules::extends => {
+                    extends = Some(TypeRef::new_extends(source, publisher, child)?);
+                }
                 Rules::enum_value => {
                     let enum_val = EnumValue::new(source, publisher, child)?;
                     enum_val.push_if_not_exists_else_err(&mut values)?;
@@ -50,6 +57,52 @@ impl Enumeration {
         }
         Ok(Self {
             name: name?,
+            extends,
+            values,
+            annotations,
+            start_position: node.start_position,
+            end_position: node.end_position,
+        })
+    }
+
+    /// An inline `error { ... }` block on a method is an anonymous enumeration, so it is
+    /// named after the method it belongs to.
+    pub(crate) fn new_method_error(
+        source: &str,
+        publisher: &BasicPublisher,
+        node: &Node,
+        name: String,
+    ) -> Result<Self, FileError> {
+        debug_assert_eq!(node.rule, Rules::method_error);
+        let mut annotations: Vec<Annotation> = Vec::new();
+
+        let mut values: Vec<EnumValue> = Vec::new();
+        for child in node.get_children() {
+            let child = publisher.get_node(*child);
+            match child.rule {
+                Rules::comment
+                | Rules::multiline_comment
+                | Rules::open_bracket
+                | Rules::close_bracket => {}
+                Rules::annotation_block => {
+                    annotations = annotation_constructor(source, publisher, child)?;
+                }
+                Rules::enum_value => {
+                    let enum_val = EnumValue::new(source, publisher, child)?;
+                    enum_val.push_if_not_exists_else_err(&mut values)?;
+                }
+
+                rule => {
+                    return Err(FileError::UnexpectedNode(
+                        rule,
+                        "Enumeration::new_method_error".to_string(),
+                    ));
+                }
+            }
+        }
+        Ok(Self {
+            name,
+            extends: None,
             values,
             annotations,
             start_position: node.start_position,
@@ -57,16 +110,43 @@ impl Enumeration {
         })
     }
 
+    pub fn span(&self) -> Span {
+        Span::new(self.start_position, self.end_position)
+    }
+
     pub fn push_if_not_exists_else_err(
         self,
         Enumerations: &mut Vec<Enumeration>,
     ) -> Result<(), FileError> {
         for s in &mut *Enumerations {
             if s.name == self.name {
-                return Err(FileError::EnumerationAlreadyExists(s.clone(), self.clone()));
+                return Err(FileError::EnumerationAlreadyExists(
+                    Box::new(s.clone()),
+                    Box::new(self.clone()),
+                ));
             }
         }
         Enumerations.push(self);
         Ok(())
     }
 }
+
+impl Extendable for Enumeration {
+    const KIND: SymbolKind = SymbolKind::Type;
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn extends(&self) -> Option<&TypeRef> {
+        self.extends.as_ref()
+    }
+
+    fn span(&self) -> Span {
+        Enumeration::span(self)
+    }
+
+    fn declared_in(file: &FidlFileRs) -> Vec<Declarations<'_, Self>> {
+        type_declarations(file, |tc| &tc.enumerations, |i| &i.enumerations)
+    }
+}
@@ -2,20 +2,30 @@ use core::fmt;
 use std::fs;
 use std::path::PathBuf;
 
+use crate::array_type::ArrayType;
 use crate::attribute::Attribute;
+use crate::broadcast::Broadcast;
+use crate::constant::Constant;
+use crate::diagnostic::Span;
 use crate::enum_value::EnumValue;
 use crate::enumeration::Enumeration;
 use crate::method::Method;
+use crate::resolver::SymbolKind;
 use crate::structure::Structure;
 use crate::type_def::TypeDef;
+use crate::type_ref::TypeRef;
 use crate::version::Version;
 use crate::ImportModel;
 use crate::ImportNamespace;
 use crate::Interface;
+use crate::Map;
 use crate::Package;
 use crate::TypeCollection;
+use crate::Union;
+use crate::VariableDeclaration;
 use minimal_fidl_parser::{
-    BasicContext, Context, Source, _var_name, grammar, BasicPublisher, Key, Rules, RULES_SIZE,
+    BasicContext, Context, Source, _var_name, grammar, BasicPublisher, Key, ParseFailure, Rules,
+    RULES_SIZE,
 };
 use std::cell::RefCell;
 use thiserror::Error;
@@ -24,42 +34,129 @@ use thiserror::Error;
 pub enum FileError {
     #[error("Unexpected Node: {0:?} in '{1}'!")]
     UnexpectedNode(Rules, String),
-    #[error("Could not parse file: {0:?}")]
-    CouldNotParseFile(PathBuf),
-    #[error("Could not parse source string: {0:?}")]
-    CouldNotParseSourceString(String),
+    #[error("Could not parse file {0:?}, {1}")]
+    CouldNotParseFile(PathBuf, ParseFailure),
+    #[error("Could not parse source string, {0}")]
+    CouldNotParseSourceString(ParseFailure),
     #[error("Could not read file: {0:?}")]
     CouldNotReadFile(std::io::Error),
     // #[error("Could not parse `{0}` as an integer.")]
     // IntegerParseError(String),
     #[error["This error means the program has a bug: {0}"]]
     InternalLogicError(String),
-    #[error["The Interface: 'TODO' already exists!\nFirst Interface\n{0:#?}\nSecond Interface\n{1:#?}"]]
-    InterfaceAlreadyExists(Interface, Interface),
-    #[error["The Field: '{0}' already exists!"]]
-    FieldAlreadyExists(String),
-    #[error["The Struct: 'TODO' already exists.\nFirst Struct\n{0:#?}\nSecond Struct\n{1:#?}"]]
-    StructAlreadyExists(Structure, Structure),
-    #[error["The attribute: 'TODO' already exists.\nFirst Attribute\n{0:#?}\nSecond Attribute\n{1:#?}"]]
-    AttributeAlreadyExists(Attribute, Attribute),
-    #[error["The typedef: 'TODO' already exists.\nFirst typedef\n{0:#?}\nSecond typedef\n{1:#?}"]]
-    TypeDefAlreadyExists(TypeDef, TypeDef),
-    #[error["The Version: 'TODO' already exists.\n{0:#?}"]]
-    VersionAlreadyExists(Version),
-    #[error["The Method: 'TODO' already exists.\nFirst Struct\n{0:#?}\nSecond Struct\n{1:#?}"]]
-    MethodAlreadyExists(Method, Method),
-    #[error["The Package: 'TODO' already exists.\n{0:#?}"]]
-    PackageAlreadyExists(Package),
-    #[error["The Enumeration: 'TODO' already exists.\nFirst Enum\n{0:#?}\nSecond Enum\n{1:#?}"]]
-    EnumerationAlreadyExists(Enumeration, Enumeration),
+    #[error["The Interface: '{}' already exists.", .1.name]]
+    InterfaceAlreadyExists(Box<Interface>, Box<Interface>),
+    #[error["The Field: '{0}' already exists."]]
+    FieldAlreadyExists(String, Span),
+    #[error["The Struct: '{}' already exists.", .1.name]]
+    StructAlreadyExists(Box<Structure>, Box<Structure>),
+    #[error["The Union: '{}' already exists.", .1.name]]
+    UnionAlreadyExists(Box<Union>, Box<Union>),
+    #[error["The attribute: '{}' already exists.", .1.name]]
+    AttributeAlreadyExists(Box<Attribute>, Box<Attribute>),
+    #[error["The typedef: '{}' already exists.", .1.name]]
+    TypeDefAlreadyExists(Box<TypeDef>, Box<TypeDef>),
+    #[error["The Array: '{}' already exists.", .1.name]]
+    ArrayTypeAlreadyExists(Box<ArrayType>, Box<ArrayType>),
+    #[error["'{0}' has a fixed length but is not an array."]]
+    LengthWithoutArray(String, Span),
+    #[error["The array length of '{0}' is invalid, {1}."]]
+    InvalidArrayLength(String, String, Span),
+    #[error["The Map: '{}' already exists.", .1.name]]
+    MapAlreadyExists(Box<Map>, Box<Map>),
+    #[error["The Map: '{0}' has an array key, a map key must be a single value."]]
+    MapKeyIsArray(String, Span),
+    #[error["The Version already exists."]]
+    VersionAlreadyExists(Box<Version>),
+    #[error["The Method: '{}' already exists.", .1.name]]
+    MethodAlreadyExists(Box<Method>, Box<Method>),
+    #[error["The fireAndForget Method: '{}' cannot have out parameters or errors.", .0.name]]
+    FireAndForgetMethodHasResponse(Box<Method>),
+    #[error["The Broadcast: '{}' already exists.", .1.name]]
+    BroadcastAlreadyExists(Box<Broadcast>, Box<Broadcast>),
+    #[error["The Constant: '{}' already exists.", .1.name]]
+    ConstantAlreadyExists(Box<Constant>, Box<Constant>),
+    #[error["The constant: '{0}' does not exist or is declared after it is used."]]
+    ConstantNotFound(String, Span),
+    #[error["The constant: '{0}' is invalid, {1}."]]
+    InvalidConstant(String, String, Span),
+    #[error["The Package: '{}' already exists.", .0.path.join(".")]]
+    PackageAlreadyExists(Box<Package>),
+    #[error["The Enumeration: '{}' already exists.", .1.name]]
+    EnumerationAlreadyExists(Box<Enumeration>, Box<Enumeration>),
+    #[error["The integer range: '{0}' is invalid, {1}."]]
+    InvalidIntegerRange(String, String, Span),
     #[error["Could not convert '{0}' to an Integer."]]
     CouldNotConvertToInteger(String),
-    #[error["The Enum Value: 'TODO' already exists.\nFirst Enum Value\n{0:#?}\nSecond Enum Value\n{1:#?}"]]
-    EnumValueAlreadyExists(EnumValue, EnumValue),
-    #[error["The Type Collection: 'TODO' already exists.\nFirst Type Collection\n{0:#?}\nSecond Type Collection\n{1:#?}"]]
-    TypeCollectionAlreadyExists(TypeCollection, TypeCollection),
-    #[error["The Type collection requires a name\n{0}"]]
-    TypeCollectionRequiresAName(String),
+    #[error["The Enum Value: '{}' already exists.", .1.name]]
+    EnumValueAlreadyExists(Box<EnumValue>, Box<EnumValue>),
+    #[error["The Type Collection: '{}' already exists.", .1.name]]
+    TypeCollectionAlreadyExists(Box<TypeCollection>, Box<TypeCollection>),
+    #[error["The Type collection requires a name."]]
+    TypeCollectionRequiresAName(Span),
+    #[error["'{0}' extends '{1}' but '{1}' does not exist."]]
+    ExtendsBaseNotFound(String, String, Span),
+    #[error["'{0}' extends itself through its base types."]]
+    CyclicExtends(String, Span),
+    #[error["{0:?} imports {1:?} which could not be read."]]
+    ImportNotFound(PathBuf, PathBuf),
+    #[error["The type '{2}' could not be resolved."]]
+    UnresolvedTypeRef(PathBuf, Span, String),
+    #[error["The type '{2}' is ambiguous, it could be any of {3:?}."]]
+    AmbiguousTypeRef(PathBuf, Span, String, Vec<String>),
+    #[error["The type '{0}' is not a built-in type, is not declared in this file and is not imported."]]
+    UndefinedType(String, Span),
+    #[error["The type '{0}' is declared but never used."]]
+    UnusedType(String, Span),
+}
+
+/// The result of looking up the base named in an `extends` clause.
+enum Resolved<'a, T> {
+    /// Found in this file, along with the name of the interface or type collection it lives in.
+    Local(&'a T, &'a str),
+    /// Qualified with a package or type collection from another file.
+    External,
+    Missing,
+}
+
+/// A declaration that can extend another declaration of the same kind.
+pub(crate) trait Extendable: Sized {
+    /// What kind of symbol a base declared in another file is.
+    const KIND: SymbolKind;
+    fn name(&self) -> &str;
+    fn extends(&self) -> Option<&TypeRef>;
+    fn span(&self) -> Span;
+    /// The declarations of this kind in `file` grouped by where they are declared.
+    fn declared_in(file: &FidlFileRs) -> Vec<Declarations<'_, Self>>;
+}
+
+/// The declarations of one kind in an interface or type collection.
+pub(crate) struct Declarations<'a, T> {
+    /// The interface or type collection, empty for interfaces themselves.
+    pub(crate) container: &'a str,
+    pub(crate) items: &'a [T],
+    /// Whether unqualified names see them from outside of the container too.
+    pub(crate) visible_everywhere: bool,
+}
+
+/// The declarations of a type collection and interface member kind, i.e structures.
+/// Those in type collections are visible everywhere, those in interfaces only inside the interface.
+pub(crate) fn type_declarations<'a, T>(
+    file: &'a FidlFileRs,
+    from_type_collection: fn(&TypeCollection) -> &Vec<T>,
+    from_interface: fn(&Interface) -> &Vec<T>,
+) -> Vec<Declarations<'a, T>> {
+    let type_collections = file.type_collections.iter().map(|tc| Declarations {
+        container: tc.name.as_str(),
+        items: from_type_collection(tc).as_slice(),
+        visible_everywhere: true,
+    });
+    let interfaces = file.interfaces.iter().map(|i| Declarations {
+        container: i.name.as_str(),
+        items: from_interface(i).as_slice(),
+        visible_everywhere: false,
+    });
+    type_collections.chain(interfaces).collect()
 }
 
 pub struct FidlFileRs {
@@ -120,9 +217,11 @@ impl FidlFileRs {
         };
         let result = resp.create_symbol_table(&publisher);
         match result {
-            Ok(()) => Ok(resp),
-            Err(err) => Err(err),
+            Ok(()) => {}
+            Err(err) => return Err(err),
         }
+        resp.validate_extends()?;
+        Ok(resp)
     }
 
     fn create_symbol_table(&mut self, publisher: &BasicPublisher) -> Result<(), FileError> {
@@ -159,7 +258,8 @@ impl FidlFileRs {
                 | Rules::multiline_comment
                 | Rules::open_bracket
                 | Rules::annotation_block
-                | Rules::close_bracket => {}
+                | Rules::close_bracket
+                | Rules::skipped => {}
                 rule => {
                     return Err(FileError::UnexpectedNode(
                         rule,
@@ -171,3 +271,299 @@ impl FidlFileRs {
         Ok(())
     }
 }
+
+/// Looks up the base named by an `extends` clause of `file` that is not declared in `file`.
+/// Returns the file it is declared in and its interface or type collection, empty for interfaces.
+pub(crate) type FindExternal<'a> =
+    dyn Fn(&'a FidlFileRs, &str, &TypeRef, SymbolKind) -> Option<(&'a FidlFileRs, &'a str)> + 'a;
+
+impl FidlFileRs {
+    /// `name` prefixed with the package of this file, i.e `org.example.MyTypes`.
+    pub fn qualified_name(&self, name: &str) -> String {
+        match &self.package {
+            Some(package) => format!("{}.{}", package.path.join("."), name),
+            None => name.to_string(),
+        }
+    }
+
+    /// Returns a copy of the type collection with inherited struct fields and enum values
+    /// flattened into each derived type. Base types first, then the derived type's own.
+    /// Bases must be declared in this file, use `FidlProject::flattened_type_collection`
+    /// for bases that are imported.
+    pub fn flattened_type_collection(
+        &self,
+        type_collection: &TypeCollection,
+    ) -> Result<TypeCollection, FileError> {
+        self.flattened_type_collection_with(type_collection, &|_, _, _, _| None)
+    }
+
+    /// Returns a copy of the interface with every member of its base interfaces prepended,
+    /// and inherited struct fields and enum values flattened.
+    /// Bases must be declared in this file, use `FidlProject::flattened_interface`
+    /// for bases that are imported.
+    pub fn flattened_interface(&self, interface: &Interface) -> Result<Interface, FileError> {
+        self.flattened_interface_with(interface, &|_, _, _, _| None)
+    }
+
+    pub(crate) fn flattened_type_collection_with<'a>(
+        &'a self,
+        type_collection: &'a TypeCollection,
+        external: &FindExternal<'a>,
+    ) -> Result<TypeCollection, FileError> {
+        let mut flattened = type_collection.clone();
+        flattened.structures = type_collection
+            .structures
+            .iter()
+            .map(|s| self.flattened_structure(&type_collection.name, s, external))
+            .collect::<Result<_, _>>()?;
+        flattened.enumerations = type_collection
+            .enumerations
+            .iter()
+            .map(|e| self.flattened_enumeration(&type_collection.name, e, external))
+            .collect::<Result<_, _>>()?;
+        Ok(flattened)
+    }
+
+    pub(crate) fn flattened_interface_with<'a>(
+        &'a self,
+        interface: &'a Interface,
+        external: &FindExternal<'a>,
+    ) -> Result<Interface, FileError> {
+        let mut flattened = interface.clone();
+        let mut interfaces = self.ancestors("", interface, Some(external))?;
+        interfaces.reverse();
+        interfaces.push((self, interface));
+
+        flattened.typedefs = Vec::new();
+        flattened.maps = Vec::new();
+        flattened.arrays = Vec::new();
+        flattened.unions = Vec::new();
+        flattened.attributes = Vec::new();
+        flattened.methods = Vec::new();
+        flattened.broadcasts = Vec::new();
+        flattened.structures = Vec::new();
+        flattened.enumerations = Vec::new();
+        for (file, member) in interfaces {
+            flattened.typedefs.extend(member.typedefs.iter().cloned());
+            flattened.maps.extend(member.maps.iter().cloned());
+            flattened.arrays.extend(member.arrays.iter().cloned());
+            flattened.unions.extend(member.unions.iter().cloned());
+            flattened.attributes.extend(member.attributes.iter().cloned());
+            flattened.methods.extend(member.methods.iter().cloned());
+            flattened.broadcasts.extend(member.broadcasts.iter().cloned());
+            for structure in &member.structures {
+                let structure = file.flattened_structure(&member.name, structure, external)?;
+                flattened.structures.push(structure);
+            }
+            for enumeration in &member.enumerations {
+                let enumeration = file.flattened_enumeration(&member.name, enumeration, external)?;
+                flattened.enumerations.push(enumeration);
+            }
+        }
+        Ok(flattened)
+    }
+
+    /// `scope` is the name of the interface or type collection the structure is declared in.
+    fn flattened_structure<'a>(
+        &'a self,
+        scope: &'a str,
+        structure: &'a Structure,
+        external: &FindExternal<'a>,
+    ) -> Result<Structure, FileError> {
+        let mut flattened = structure.clone();
+        let mut contents: Vec<VariableDeclaration> = Vec::new();
+        let ancestors = self.ancestors(scope, structure, Some(external))?;
+        for (_, base) in ancestors.iter().rev() {
+            contents.extend(base.contents.iter().cloned());
+        }
+        contents.extend(structure.contents.iter().cloned());
+        flattened.contents = contents;
+        // Only the root of a family is marked polymorphic, every struct derived from it is too.
+        flattened.polymorphic =
+            structure.polymorphic || ancestors.iter().any(|(_, s)| s.polymorphic);
+        Ok(flattened)
+    }
+
+    /// `scope` is the name of the interface or type collection the enumeration is declared in.
+    fn flattened_enumeration<'a>(
+        &'a self,
+        scope: &'a str,
+        enumeration: &'a Enumeration,
+        external: &FindExternal<'a>,
+    ) -> Result<Enumeration, FileError> {
+        let mut flattened = enumeration.clone();
+        let mut values: Vec<EnumValue> = Vec::new();
+        let ancestors = self.ancestors(scope, enumeration, Some(external))?;
+        for (_, base) in ancestors.iter().rev() {
+            values.extend(base.values.iter().cloned());
+        }
+        values.extend(enumeration.values.iter().cloned());
+        flattened.values = values;
+        Ok(flattened)
+    }
+
+    /// Base interfaces declared in this file, nearest first.
+    pub fn interface_ancestors<'a>(&'a self, interface: &'a Interface) -> Vec<&'a Interface> {
+        // A loaded file has been validated, so its local bases exist and do not form a cycle.
+        match self.ancestors("", interface, None) {
+            Ok(ancestors) => ancestors.into_iter().map(|(_, base)| base).collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    fn validate_extends(&self) -> Result<(), FileError> {
+        for type_collection in &self.type_collections {
+            let scope = &type_collection.name;
+            self.validate_extends_of(scope, &type_collection.structures)?;
+            self.validate_extends_of(scope, &type_collection.unions)?;
+            self.validate_extends_of(scope, &type_collection.enumerations)?;
+        }
+        for interface in &self.interfaces {
+            let scope = &interface.name;
+            self.validate_extends_of(scope, &interface.structures)?;
+            self.validate_extends_of(scope, &interface.unions)?;
+            self.validate_extends_of(scope, &interface.enumerations)?;
+        }
+        self.validate_extends_of("", &self.interfaces)
+    }
+
+    /// Bases declared in another file can only be checked once it is loaded.
+    fn validate_extends_of<T: Extendable>(
+        &self,
+        scope: &str,
+        items: &[T],
+    ) -> Result<(), FileError> {
+        for item in items {
+            self.ancestors(scope, item, None)?;
+        }
+        Ok(())
+    }
+
+    /// Base declarations along with the file each is declared in, nearest first.
+    /// `scope` is the name of the interface or type collection `item` is declared in.
+    /// Bases that are not declared in this file are looked up with `external` and their own bases are
+    /// resolved relative to the file they are declared in. Without `external` the walk stops at them.
+    fn ancestors<'a, T: Extendable>(
+        &'a self,
+        scope: &'a str,
+        item: &'a T,
+        external: Option<&FindExternal<'a>>,
+    ) -> Result<Vec<(&'a FidlFileRs, &'a T)>, FileError> {
+        let mut ancestors: Vec<(&'a FidlFileRs, &'a T)> = Vec::new();
+        let mut file = self;
+        let mut scope = scope;
+        let mut current = item;
+        while let Some(base) = current.extends() {
+            let found = match (file.resolve::<T>(scope, base), external) {
+                (Resolved::Local(base, base_scope), _) => Some((file, base_scope, base)),
+                (Resolved::External, None) => break,
+                (Resolved::External, Some(external)) => external(file, scope, base, T::KIND)
+                    .and_then(|(base_file, container)| {
+                        let (base_scope, base) =
+                            base_file.find_in::<T>(container, unqualified(&base.name))?;
+                        Some((base_file, base_scope, base))
+                    }),
+                (Resolved::Missing, _) => None,
+            };
+            let (base_file, base_scope, base) = found.ok_or_else(|| {
+                FileError::ExtendsBaseNotFound(
+                    current.name().to_string(),
+                    base.name.clone(),
+                    current.span(),
+                )
+            })?;
+            // Walking back to the item, or to any base already walked, never ends.
+            if std::ptr::eq(base, item) || ancestors.iter().any(|(_, a)| std::ptr::eq(*a, base)) {
+                return Err(FileError::CyclicExtends(
+                    item.name().to_string(),
+                    item.span(),
+                ));
+            }
+            ancestors.push((base_file, base));
+            file = base_file;
+            scope = base_scope;
+            current = base;
+        }
+        Ok(ancestors)
+    }
+
+    /// The declaration called `type_name` in the interface or type collection called `container`.
+    fn find_in<'a, T: Extendable>(
+        &'a self,
+        container: &str,
+        type_name: &str,
+    ) -> Option<(&'a str, &'a T)> {
+        T::declared_in(self)
+            .into_iter()
+            .filter(|declarations| declarations.container == container)
+            .find_map(|declarations| {
+                declarations
+                    .items
+                    .iter()
+                    .find(|item| item.name() == type_name)
+                    .map(|item| (declarations.container, item))
+            })
+    }
+
+    /// An unqualified name is looked up in `scope` first and then wherever else it is visible.
+    /// A qualified name must match an interface or type collection in this file, optionally
+    /// prefixed with the package, otherwise it is assumed to come from an import.
+    fn resolve<'a, T: Extendable>(&'a self, scope: &str, type_ref: &TypeRef) -> Resolved<'a, T> {
+        let declarations = T::declared_in(self);
+        let (qualifier, type_name) = match type_ref.name.rsplit_once('.') {
+            Some((qualifier, type_name)) => (Some(qualifier), type_name),
+            None => (None, type_ref.name.as_str()),
+        };
+        let containers: Vec<&Declarations<T>> = match qualifier {
+            Some(qualifier) => declarations
+                .iter()
+                .filter(|d| self.qualifier_matches(qualifier, d.container))
+                .collect(),
+            None => {
+                let (inside, outside): (Vec<&Declarations<T>>, Vec<&Declarations<T>>) =
+                    declarations.iter().partition(|d| d.container == scope);
+                let outside = outside.into_iter().filter(|d| d.visible_everywhere);
+                inside.into_iter().chain(outside).collect()
+            }
+        };
+        if qualifier.is_some() && containers.is_empty() {
+            return Resolved::External;
+        }
+        for declarations in containers {
+            for item in declarations.items {
+                if item.name() == type_name {
+                    return Resolved::Local(item, declarations.container);
+                }
+            }
+        }
+        // Unqualified names may also come from an `import some.namespace.* from` import.
+        if qualifier.is_none() && !self.namespaces.is_empty() {
+            return Resolved::External;
+        }
+        Resolved::Missing
+    }
+
+    /// Whether `qualifier` names `container` in this file, either bare or prefixed with the package.
+    /// An empty `container` matches the package on its own.
+    fn qualifier_matches(&self, qualifier: &str, container: &str) -> bool {
+        if !container.is_empty() && qualifier == container {
+            return true;
+        }
+        match &self.package {
+            Some(package) => {
+                let package = package.path.join(".");
+                if container.is_empty() {
+                    qualifier == package
+                } else {
+                    qualifier == format!("{}.{}", package, container)
+                }
+            }
+            None => false,
+        }
+    }
+}
+
+/// The last part of a possibly qualified name, i.e `Point` for `Types.Point`.
+fn unqualified(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name)
+}
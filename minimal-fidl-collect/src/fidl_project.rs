@@ -1,24 +1,241 @@
 use minimal_fidl_parser::{
-    BasicContext, BasicPublisher, Context, Rules, Source, _var_name, grammar, Key, RULES_SIZE,
+    parse_with_context, parse_with_recovery, BasicContext, BasicPublisher, Context, ParseFailure,
+    RULES_SIZE,
 };
+use rayon::prelude::*;
 use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use crate::fidl_file::{FidlFileRs, FileError};
+use crate::resolver::{Resolver, Symbol, SymbolKind};
+use crate::{Interface, TypeCollection};
 
-#[derive(Debug)]
-pub struct FidlProject {}
+thread_local! {
+    /// Reused for every file parsed on the same thread so a project does not allocate a cache per file.
+    static CONTEXT: RefCell<BasicContext> = RefCell::new(BasicContext::new(0, RULES_SIZE as usize));
+}
+
+/// Every file of a project along with the files they import, indexed by package.
+#[derive(Debug, Default)]
+pub struct FidlProject {
+    resolver: Resolver,
+    packages: HashMap<String, Vec<usize>>,
+}
 impl FidlProject {
-    pub fn new(dir: impl Into<PathBuf>) -> Result<Vec<PathBuf>, std::io::Error> {
-        let files = Self::walk_dirs(&dir.into());
+    /// Parses every `.fidl` file under `dir` and anything they import.
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self, FileError> {
+        let paths = Self::paths(dir).map_err(FileError::CouldNotReadFile)?;
+        Self::from_paths(paths)
+    }
+
+    /// Parses each of `paths` and anything they import, failing on the first file that can not be loaded.
+    pub fn from_paths(paths: impl IntoIterator<Item = PathBuf>) -> Result<Self, FileError> {
+        let (project, errors) = Self::from_paths_with_recovery(paths);
+        match errors.into_iter().next() {
+            Some((_, err)) => Err(err),
+            None => Ok(project),
+        }
+    }
+
+    /// Like `from_paths` but a file that can not be loaded is returned as an error along with its path
+    /// and the rest are still loaded, so references between them can be resolved.
+    /// Declarations that do not parse are skipped and returned as errors, the rest of their file is still
+    /// loaded so references into it resolve.
+    /// The given paths are parsed in parallel on the rayon thread pool, imports outside of them one by one afterwards.
+    pub fn from_paths_with_recovery(
+        paths: impl IntoIterator<Item = PathBuf>,
+    ) -> (Self, Vec<(PathBuf, FileError)>) {
+        let paths: Vec<PathBuf> = paths.into_iter().collect();
+        let parsed: Vec<Result<(FidlFileRs, Vec<FileError>), FileError>> = paths
+            .par_iter()
+            .map(|path| {
+                path.canonicalize().map_err(FileError::CouldNotReadFile)?;
+                Self::generate_file_with_recovery(path)
+            })
+            .collect();
+        let mut project = Self::default();
+        let mut errors: Vec<(PathBuf, FileError)> = Vec::new();
+        let mut pending: Vec<PathBuf> = Vec::new();
+        // The same file can be reached through different relative paths, it is loaded or reported once.
+        let mut seen: HashSet<PathBuf> = HashSet::new();
+        for (path, file) in paths.into_iter().zip(parsed) {
+            if seen.insert(path.canonicalize().unwrap_or_else(|_| path.clone())) {
+                project.add_recovered(path, file, &mut pending, &mut errors);
+            }
+        }
+        while let Some(path) = pending.pop() {
+            if seen.insert(path.canonicalize().unwrap_or_else(|_| path.clone())) {
+                let file = Self::generate_file_with_recovery(&path);
+                project.add_recovered(path, file, &mut pending, &mut errors);
+            }
+        }
+        project.index_packages();
+        (project, errors)
+    }
+
+    /// Adds what could be collected from the file at `path`, appending why the rest could not to `errors`.
+    fn add_recovered(
+        &mut self,
+        path: PathBuf,
+        file: Result<(FidlFileRs, Vec<FileError>), FileError>,
+        pending: &mut Vec<PathBuf>,
+        errors: &mut Vec<(PathBuf, FileError)>,
+    ) {
+        let added = file.and_then(|(file, parse_errors)| {
+            errors.extend(parse_errors.into_iter().map(|err| (path.clone(), err)));
+            self.resolver.add_file(path.clone(), file, pending)
+        });
+        if let Err(err) = added {
+            errors.push((path, err));
+        }
+    }
+
+    /// Parses the file at `path` again after it changed, or for the first time if it is new,
+    /// without parsing the other files again.
+    pub fn reload(&mut self, path: impl Into<PathBuf>) -> Result<(), FileError> {
+        let result = self.resolver.reload(path);
+        self.index_packages();
+        result
+    }
+
+    /// Forgets the file at `path` after it was deleted.
+    pub fn remove(&mut self, path: &Path) {
+        self.resolver.unload(path);
+        self.index_packages();
+    }
+
+    fn index_packages(&mut self) {
+        self.packages.clear();
+        for (index, (_, file)) in self.resolver.files.iter().enumerate() {
+            self.packages
+                .entry(Self::package_name(file))
+                .or_default()
+                .push(index);
+        }
+    }
+
+    /// The paths of every `.fidl` file under `dir` sorted, without parsing them.
+    /// A path to a `.fidl` file is returned as it is.
+    pub fn paths(dir: impl Into<PathBuf>) -> Result<Vec<PathBuf>, std::io::Error> {
+        let dir = dir.into();
+        if dir.is_file() && Self::is_fidl_file(&dir) {
+            return Ok(vec![dir]);
+        }
+        let files = Self::walk_dirs(&dir);
         Ok(files?)
     }
 
+    pub fn files(&self) -> impl Iterator<Item = (&Path, &FidlFileRs)> {
+        self.resolver
+            .files
+            .iter()
+            .map(|(path, file)| (path.as_path(), file))
+    }
+
+    pub fn file(&self, path: &Path) -> Option<&FidlFileRs> {
+        self.resolver.file(path)
+    }
+
+    /// The files declaring `package`, i.e `org.example`.
+    pub fn package_files(&self, package: &str) -> Vec<(&Path, &FidlFileRs)> {
+        match self.packages.get(package) {
+            Some(indices) => indices
+                .iter()
+                .map(|index| {
+                    let (path, file) = &self.resolver.files[*index];
+                    (path.as_path(), file)
+                })
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Finds an interface by its fully qualified name, i.e `org.example.MyInterface`.
+    pub fn find_interface(&self, name: &str) -> Option<(&Path, &Interface)> {
+        let (package, name) = match name.rsplit_once('.') {
+            Some((package, name)) => (package, name),
+            None => ("", name),
+        };
+        self.package_files(package).into_iter().find_map(|(path, file)| {
+            file.interfaces
+                .iter()
+                .find(|interface| interface.name == name)
+                .map(|interface| (path, interface))
+        })
+    }
+
+    /// Every type declared in `package` across all of its files and containers.
+    pub fn types_in_package(&self, package: &str) -> Vec<&Symbol> {
+        self.resolver
+            .symbols
+            .package(package)
+            .iter()
+            .filter(|symbol| symbol.kind == SymbolKind::Type)
+            .collect()
+    }
+
+    /// The files that `path` imports.
+    pub fn dependencies(&self, path: &Path) -> Vec<&Path> {
+        self.resolver.dependencies(path)
+    }
+
+    /// The files that import `path`, also after it was deleted until it is removed from the project.
+    pub fn reverse_dependencies(&self, path: &Path) -> Vec<&Path> {
+        self.resolver.reverse_dependencies(path)
+    }
+
+    /// The type collection of `file`, a file of the project, with inherited struct fields and enum
+    /// values flattened into each derived type. Bases may be declared in the files it imports.
+    pub fn flattened_type_collection(
+        &self,
+        file: &FidlFileRs,
+        type_collection: &TypeCollection,
+    ) -> Result<TypeCollection, FileError> {
+        self.resolver.flattened_type_collection(file, type_collection)
+    }
+
+    /// The interface of `file`, a file of the project, with every member of its base interfaces
+    /// prepended. Bases may be declared in the files it imports.
+    pub fn flattened_interface(
+        &self,
+        file: &FidlFileRs,
+        interface: &Interface,
+    ) -> Result<Interface, FileError> {
+        self.resolver.flattened_interface(file, interface)
+    }
+
+    /// Type references that resolve to nothing or to more than one type.
+    pub fn check(&self) -> Vec<FileError> {
+        self.resolver.check()
+    }
+
+    /// Types declared in a file of the project that no file of the project uses, along with that file.
+    pub fn unused_types(&self) -> Vec<(&Path, FileError)> {
+        self.resolver.unused_types()
+    }
+
+    /// Renders an error that came from the file at `path` along with the source it points at.
+    pub fn render(&self, path: &Path, error: &FileError) -> String {
+        self.resolver.render(path, error)
+    }
+
+    pub fn resolver(&self) -> &Resolver {
+        &self.resolver
+    }
+
+    pub(crate) fn package_name(file: &FidlFileRs) -> String {
+        match &file.package {
+            Some(package) => package.path.join("."),
+            None => String::new(),
+        }
+    }
+
     pub fn generate_file_from_string(src: String) -> Result<FidlFileRs, FileError> {
         let publisher = Self::parse(&src);
         let publisher: BasicPublisher = match publisher {
-            None => return Err(FileError::CouldNotParseSourceString(src)),
-            Some(res) => res,
+            Err(failure) => return Err(FileError::CouldNotParseSourceString(failure)),
+            Ok(res) => res,
         };
         Ok(FidlFileRs::new(src, &publisher)?)
     }
@@ -32,29 +249,54 @@ impl FidlProject {
         };
         let publisher = Self::parse(&src);
         let publisher: BasicPublisher = match publisher {
-            None => return Err(FileError::CouldNotParseFile(path.clone())),
-            Some(res) => res,
+            Err(failure) => return Err(FileError::CouldNotParseFile(path.clone(), failure)),
+            Ok(res) => res,
         };
         Ok(FidlFileRs::new(src, &publisher)?)
     }
 
-    fn parse(input: &str) -> Option<BasicPublisher> {
-        let string = input.to_string();
-        let src_len = string.len() as u32;
-        let source = Source::new(&string);
-        let position: u32 = 0;
-        let result: (bool, u32);
-        let context = RefCell::new(BasicContext::new(src_len as usize, RULES_SIZE as usize));
-        {
-            let executor = _var_name(Rules::Grammar, &context, grammar);
-            result = executor(Key(0), &source, position);
-        }
-        if result != (true, src_len) {
-            println!("Failed with : {:?}", result);
-            return None;
-        }
-        let publisher = context.into_inner().get_publisher().clear_false();
-        Some(publisher)
+    /// Like `generate_file` but declarations that do not parse are skipped instead of failing the whole
+    /// file. Each skipped declaration is returned as a `CouldNotParseFile` alongside the model of the rest.
+    pub fn generate_file_with_recovery(
+        path: impl Into<PathBuf>,
+    ) -> Result<(FidlFileRs, Vec<FileError>), FileError> {
+        let path = path.into();
+        let src = std::fs::read_to_string(&path).map_err(FileError::CouldNotReadFile)?;
+        let recovered = match parse_with_recovery(&src) {
+            Err(failure) => return Err(FileError::CouldNotParseFile(path, failure)),
+            Ok(recovered) => recovered,
+        };
+        let errors = recovered
+            .failures
+            .into_iter()
+            .map(|failure| FileError::CouldNotParseFile(path.clone(), failure))
+            .collect();
+        Ok((FidlFileRs::new(src, &recovered.publisher)?, errors))
+    }
+
+    pub fn generate_file_from_string_with_recovery(
+        src: String,
+    ) -> Result<(FidlFileRs, Vec<FileError>), FileError> {
+        let recovered = match parse_with_recovery(&src) {
+            Err(failure) => return Err(FileError::CouldNotParseSourceString(failure)),
+            Ok(recovered) => recovered,
+        };
+        let errors = recovered
+            .failures
+            .into_iter()
+            .map(FileError::CouldNotParseSourceString)
+            .collect();
+        Ok((FidlFileRs::new(src, &recovered.publisher)?, errors))
+    }
+
+    fn parse(input: &str) -> Result<BasicPublisher, ParseFailure> {
+        CONTEXT.with(|context| {
+            let (result, position, publisher, failure) = parse_with_context(input, context);
+            if (result, position) != (true, input.len() as u32) {
+                return Err(failure);
+            }
+            Ok(publisher)
+        })
     }
 
     fn is_fidl_file(path: &Path) -> bool {
@@ -79,11 +321,13 @@ impl FidlProject {
                 }
             }
         }
-        let ret_vec: Vec<PathBuf> = ret_vec
+        let mut ret_vec: Vec<PathBuf> = ret_vec
             .iter()
             .filter(|path| Self::is_fidl_file(path))
             .map(|path| path.to_path_buf())
             .collect();
+        // `read_dir` order depends on the file system, sorting keeps loading and generating reproducible.
+        ret_vec.sort();
         Ok(ret_vec)
     }
 }
@@ -3,14 +3,22 @@ use std::{
     str::FromStr,
 };
 
+use crate::diagnostic::Span;
 use crate::{
     annotation::{annotation_constructor, Annotation},
+    array_type::ArrayType,
     attribute::{self, Attribute},
+    broadcast::Broadcast,
+    constant::Constant,
     enumeration::{self, Enumeration},
-    fidl_file::FileError,
+    fidl_file::{Declarations, Extendable, FidlFileRs, FileError},
     method::Method,
+    resolver::SymbolKind,
     structure::Structure,
     type_def::TypeDef,
+    type_ref::TypeRef,
+    union::Union,
+    map::Map,
     Version,
 };
 use minimal_fidl_parser::{BasicPublisher, Key, Node, Rules};
@@ -20,11 +28,17 @@ pub struct Interface {
     end_position: u32,
     pub annotations: Vec<Annotation>,
     pub name: String,
+    pub extends: Option<TypeRef>,
     pub version: Option<Version>,
     pub attributes: Vec<Attribute>,
     pub structures: Vec<Structure>,
+    pub unions: Vec<Union>,
+    pub maps: Vec<Map>,
+    pub arrays: Vec<ArrayType>,
     pub typedefs: Vec<TypeDef>,
+    pub constants: Vec<Constant>,
     pub methods: Vec<Method>,
+    pub broadcasts: Vec<Broadcast>,
     pub enumerations: Vec<Enumeration>,
 }
 impl Interface {
@@ -33,11 +47,18 @@ impl Interface {
         let mut name: Result<String, FileError> = Err(FileError::InternalLogicError(
             "Uninitialized value: 'name' in Interface::new".to_string(),
         ));
+        let mut extends: Option<TypeRef> = None;
         let mut version: Option<Version> = None;
         let mut structures: Vec<Structure> = Vec::new();
+        let mut unions: Vec<Union> = Vec::new();
+        let mut maps: Vec<Map> = Vec::new();
+        let mut arrays: Vec<ArrayType> = Vec::new();
         let mut attributes: Vec<Attribute> = Vec::new();
         let mut typedefs: Vec<TypeDef> = Vec::new();
+        let mut constants: Vec<Constant> = Vec::new();
+        let mut constant_nodes: Vec<&Node> = Vec::new();
         let mut methods: Vec<Method> = Vec::new();
+        let mut broadcasts: Vec<Broadcast> = Vec::new();
         let mut enumerations: Vec<Enumeration> = Vec::new();
         let mut annotations: Vec<Annotation> = Vec::new();
 
@@ -48,6 +69,9 @@ impl Interface {
                     let name_str = Self::variable_name(source, publisher, child);
                     name = Ok(name_str);
                 }
+                Rules::extends => {
+                    extends = Some(TypeRef::new_extends(source, publisher, child)?);
+                }
                 Rules::version => {
                     let ver = Version::new(source, publisher, child)?;
                     ver.push_if_not_exists_else_err(&mut version)?;
@@ -56,6 +80,18 @@ impl Interface {
                     let structure = Structure::new(source, publisher, child)?;
                     structure.push_if_not_exists_else_err(&mut structures)?;
                 }
+                Rules::union => {
+                    let union = Union::new(source, publisher, child)?;
+                    union.push_if_not_exists_else_err(&mut unions)?;
+                }
+                Rules::map => {
+                    let map = Map::new(source, publisher, child)?;
+                    map.push_if_not_exists_else_err(&mut maps)?;
+                }
+                Rules::array_type => {
+                    let array = ArrayType::new(source, publisher, child)?;
+                    array.push_if_not_exists_else_err(&mut arrays)?;
+                }
                 Rules::attribute => {
                     let attribute = Attribute::new(source, publisher, child)?;
                     attribute.push_if_not_exists_else_err(&mut attributes)?;
@@ -64,10 +100,15 @@ impl Interface {
                     let typedef = TypeDef::new(source, publisher, child)?;
                     typedef.push_if_not_exists_else_err(&mut typedefs)?;
                 }
+                Rules::constant => constant_nodes.push(child),
                 Rules::method => {
                     let method = Method::new(source, publisher, child)?;
                     method.push_if_not_exists_else_err(&mut methods)?;
                 }
+                Rules::broadcast => {
+                    let broadcast = Broadcast::new(source, publisher, child)?;
+                    broadcast.push_if_not_exists_else_err(&mut broadcasts)?;
+                }
                 Rules::enumeration => {
                     let enumeration = Enumeration::new(source, publisher, child)?;
                     enumeration.push_if_not_exists_else_err(&mut enumerations)?;
@@ -78,7 +119,8 @@ impl Interface {
                 Rules::comment
                 | Rules::multiline_comment
                 | Rules::open_bracket
-                | Rules::close_bracket => {}
+                | Rules::close_bracket
+                | Rules::skipped => {}
                 rule => {
                     return Err(FileError::UnexpectedNode(
                         rule,
@@ -87,14 +129,25 @@ impl Interface {
                 }
             }
         }
+        // Constants are evaluated last so a struct initializer can use any struct declared here.
+        for child in constant_nodes {
+            let constant = Constant::new(source, publisher, child, &constants, &structures)?;
+            constant.push_if_not_exists_else_err(&mut constants)?;
+        }
         Ok(Self {
             annotations,
             name: name?,
+            extends,
             version,
             structures,
+            unions,
+            maps,
+            arrays,
             attributes,
             typedefs,
+            constants,
             methods,
+            broadcasts,
             enumerations,
             start_position: node.start_position,
             end_position: node.end_position,
@@ -105,16 +158,48 @@ impl Interface {
         node.get_string(source)
     }
 
+    pub fn span(&self) -> Span {
+        Span::new(self.start_position, self.end_position)
+    }
+
     pub fn push_if_not_exists_else_err(
         self,
         interfaces: &mut Vec<Interface>,
     ) -> Result<(), FileError> {
         for s in &mut *interfaces {
             if s.name == self.name {
-                return Err(FileError::InterfaceAlreadyExists(s.clone(), self.clone()));
+                return Err(FileError::InterfaceAlreadyExists(
+                    Box::new(s.clone()),
+                    Box::new(self.clone()),
+                ));
             }
         }
         interfaces.push(self);
         Ok(())
     }
 }
+
+impl Extendable for Interface {
+    const KIND: SymbolKind = SymbolKind::Interface;
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn extends(&self) -> Option<&TypeRef> {
+        self.extends.as_ref()
+    }
+
+    fn span(&self) -> Span {
+        Interface::span(self)
+    }
+
+    fn declared_in(file: &FidlFileRs) -> Vec<Declarations<'_, Self>> {
+        // Interfaces are not declared inside anything, so they are all visible everywhere.
+        vec![Declarations {
+            container: "",
+            items: &file.interfaces,
+            visible_everywhere: true,
+        }]
+    }
+}
@@ -1,5 +1,9 @@
 pub mod annotation;
+pub mod array_type;
 pub mod attribute;
+pub mod broadcast;
+pub mod constant;
+pub mod diagnostic;
 pub mod enum_value;
 pub mod enumeration;
 pub mod fidl_file;
@@ -7,17 +11,27 @@ pub mod fidl_project;
 pub mod import_model;
 pub mod import_namespace;
 pub mod interface;
+pub mod map;
 pub mod method;
 pub mod package;
+pub mod resolver;
 pub mod structure;
 pub mod type_collection;
 pub mod type_def;
 pub mod type_ref;
+pub mod union;
+pub mod validation;
 pub mod variable_declaration;
 pub mod version;
 pub use annotation::annotation_constructor;
 pub use annotation::Annotation;
+pub use array_type::ArrayType;
 pub use attribute::Attribute;
+pub use broadcast::Broadcast;
+pub use constant::Constant;
+pub use constant::ConstantValue;
+pub use diagnostic::LineIndex;
+pub use diagnostic::Span;
 pub use enum_value::EnumValue;
 pub use enumeration::Enumeration;
 pub use fidl_file::FidlFileRs;
@@ -26,18 +40,25 @@ pub use fidl_project::FidlProject;
 pub use import_model::ImportModel;
 pub use import_namespace::ImportNamespace;
 pub use interface::Interface;
+pub use map::Map;
 pub use method::Method;
+pub use method::MethodError;
 pub use package::Package;
+pub use resolver::Resolver;
+pub use resolver::Symbol;
+pub use resolver::SymbolTable;
 pub use structure::Structure;
 pub use type_collection::TypeCollection;
 pub use type_def::TypeDef;
+pub use type_ref::IntegerRange;
 pub use type_ref::TypeRef;
+pub use union::Union;
 pub use variable_declaration::VariableDeclaration;
 pub use version::Version;
 
 #[cfg(test)]
 mod tests {
-    use crate::{FidlFileRs, FidlProject};
+    use crate::{FidlFileRs, FidlProject, FileError, LineIndex, MethodError, Resolver};
     use minimal_fidl_parser::{
         BasicContext, BasicPublisher, Context, Key, Rules, Source, _var_name, grammar, RULES_SIZE,
     };
@@ -755,10 +776,1116 @@ typeCollection MyTypeCollection10 {
         println!("Formatted:\n\n{:#?}", output.unwrap());
     }
 
+    #[test]
+    fn test_fidl_file_26() {
+        let src = r#"
+        package org.javaohjavawhyareyouso
+        typeCollection MyTypes {
+            union MyUnion01 {
+                UInt8 e1
+                String e2
+            }
+        }
+        interface name {
+            // union extending another union
+            union MyUnion02 extends MyTypes.MyUnion01 {
+                Boolean e3
+            }
+        }"#
+        .to_string();
+        let publisher = parse(&src).unwrap();
+        //        publisher.print(Key(0), Some(true));
+        let fmt = FidlFileRs::new(src, &publisher);
+        let output = fmt.unwrap();
+        let union = &output.interfaces[0].unions[0];
+        assert_eq!(union.name, "MyUnion02");
+        assert_eq!(union.extends.as_ref().unwrap().name, "MyTypes.MyUnion01");
+        let contents: Vec<(&str, &str)> = union
+            .contents
+            .iter()
+            .map(|c| (c.type_n.as_str(), c.name.as_str()))
+            .collect();
+        assert_eq!(contents, vec![("Boolean", "e3")]);
+        let contents: Vec<(&str, &str)> = output.type_collections[0].unions[0]
+            .contents
+            .iter()
+            .map(|c| (c.type_n.as_str(), c.name.as_str()))
+            .collect();
+        assert_eq!(contents, vec![("UInt8", "e1"), ("String", "e2")]);
+    }
+
+    #[test]
+    fn test_fidl_file_27() {
+        let src = r#"
+        package org.javaohjavawhyareyouso
+        interface name {
+            union MyUnion {
+                UInt8 e1
+            }
+            union MyUnion {
+                UInt16 e1
+            }
+        }"#
+        .to_string();
+        let publisher = parse(&src).unwrap();
+        let fmt = FidlFileRs::new(src, &publisher);
+        assert!(matches!(fmt, Err(crate::FileError::UnionAlreadyExists(_, _))));
+    }
+
+    #[test]
+    fn test_fidl_file_28() {
+        let src = r#"
+        package org.javaohjavawhyareyouso
+        typeCollection MyTypes {
+            map MyMap05 { UInt16 to String }
+        }
+        interface name {
+            <** @description: Configuration table **>
+            map ConfigTable {
+                MyTypes.MyMap05 to UInt8[]
+            }
+        }"#
+        .to_string();
+        let publisher = parse(&src).unwrap();
+        //        publisher.print(Key(0), Some(true));
+        let fmt = FidlFileRs::new(src, &publisher);
+        let output = fmt.unwrap();
+        let map = &output.type_collections[0].maps[0];
+        assert_eq!((map.key_type.as_str(), map.value_type.as_str()), ("UInt16", "String"));
+        let map = &output.interfaces[0].maps[0];
+        assert_eq!(map.name, "ConfigTable");
+        assert_eq!(map.key_type, "MyTypes.MyMap05");
+        assert_eq!(map.value_type, "UInt8");
+        assert!(map.value_is_array);
+        println!("Formatted:\n\n{:#?}", output);
+    }
+
+    #[test]
+    fn test_fidl_file_29() {
+        let src = r#"
+        package org.javaohjavawhyareyouso
+        interface name {
+            broadcast statusChanged selective {
+                out {
+                    String status
+                    UInt8[] codes
+                }
+            }
+            <** @description: Fired when done **>
+            broadcast done { }
+        }"#
+        .to_string();
+        let publisher = parse(&src).unwrap();
+        //        publisher.print(Key(0), Some(true));
+        let fmt = FidlFileRs::new(src, &publisher);
+        let output = fmt.unwrap();
+        let broadcasts = &output.interfaces[0].broadcasts;
+        assert_eq!(broadcasts.len(), 2);
+        assert!(broadcasts[0].selective);
+        assert_eq!(broadcasts[0].output_parameters.len(), 2);
+        assert!(!broadcasts[1].selective);
+        assert_eq!(broadcasts[1].output_parameters.len(), 0);
+        println!("Formatted:\n\n{:#?}", output);
+    }
+
+    #[test]
+    fn test_fidl_file_30() {
+        let src = r#"
+        package org.javaohjavawhyareyouso
+        interface name {
+            method connect {
+                in {
+                    String device
+                }
+                error {
+                    TIMEOUT
+                    REFUSED = 4
+                }
+            }
+            method disconnect {
+                error MyTypes.DisconnectError
+            }
+            method ping { }
+        }"#
+        .to_string();
+        let publisher = parse(&src).unwrap();
+        //        publisher.print(Key(0), Some(true));
+        let fmt = FidlFileRs::new(src, &publisher);
+        let output = fmt.unwrap();
+        let methods = &output.interfaces[0].methods;
+        match &methods[0].errors {
+            Some(MethodError::Inline(enumeration)) => {
+                assert_eq!(enumeration.name, "connectError");
+                assert_eq!(enumeration.values.len(), 2);
+            }
+            e => panic!("Expected an inline error enumeration, got: {:?}", e),
+        }
+        match &methods[1].errors {
+            Some(MethodError::Reference(type_n)) => assert_eq!(type_n, "MyTypes.DisconnectError"),
+            e => panic!("Expected an error type reference, got: {:?}", e),
+        }
+        assert!(methods[2].errors.is_none());
+        println!("Formatted:\n\n{:#?}", output);
+    }
+
+    #[test]
+    fn test_fidl_file_31() {
+        let src = r#"
+        package org.javaohjavawhyareyouso
+        interface name {
+            attribute UInt8 volume readonly noSubscriptions
+            attribute UInt8 balance noSubscriptions
+            attribute UInt8 bass
+            method log fireAndForget {
+                in {
+                    String message
+                }
+            }
+        }"#
+        .to_string();
+        let publisher = parse(&src).unwrap();
+        //        publisher.print(Key(0), Some(true));
+        let fmt = FidlFileRs::new(src, &publisher);
+        let output = fmt.unwrap();
+        let attributes = &output.interfaces[0].attributes;
+        assert!(attributes[0].readonly && attributes[0].no_subscriptions);
+        assert!(!attributes[1].readonly && attributes[1].no_subscriptions);
+        assert!(!attributes[2].readonly && !attributes[2].no_subscriptions);
+        assert!(output.interfaces[0].methods[0].fire_and_forget);
+        println!("Formatted:\n\n{:#?}", output);
+    }
+
+    #[test]
+    fn test_fidl_file_32() {
+        let src = r#"
+        package org.javaohjavawhyareyouso
+        interface name {
+            method log fireAndForget {
+                out {
+                    UInt8 result
+                }
+            }
+        }"#
+        .to_string();
+        let publisher = parse(&src).unwrap();
+        let fmt = FidlFileRs::new(src, &publisher);
+        assert!(matches!(
+            fmt,
+            Err(crate::FileError::FireAndForgetMethodHasResponse(_))
+        ));
+    }
+
+    #[test]
+    fn test_fidl_file_33() {
+        let src = r#"
+        package org.javaohjavawhyareyouso
+        typeCollection MyTypes {
+            struct Base {
+                UInt8 b1
+            }
+            enumeration BaseEnum {
+                A
+                B
+            }
+        }
+        interface Base {
+            attribute UInt8 base_value
+        }
+        interface Derived extends Base {
+            struct Derived extends MyTypes.Base {
+                String d1
+            }
+            struct MoreDerived extends Derived {
+                Boolean d2
+            }
+            enumeration DerivedEnum extends org.javaohjavawhyareyouso.MyTypes.BaseEnum {
+                C
+            }
+            attribute UInt16 derived_value
+        }"#
+        .to_string();
+        let publisher = parse(&src).unwrap();
+        let output = FidlFileRs::new(src, &publisher).unwrap();
+        let derived = &output.interfaces[1];
+        assert_eq!(derived.extends.as_ref().unwrap().name, "Base");
+        assert_eq!(derived.structures[0].extends.as_ref().unwrap().name, "MyTypes.Base");
+
+        let flattened = output.flattened_interface(derived).unwrap();
+        let names: Vec<&str> = flattened.attributes.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["base_value", "derived_value"]);
+        let names: Vec<&str> = flattened.structures[1].contents.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["b1", "d1", "d2"]);
+        let names: Vec<&str> = flattened.enumerations[0].values.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["A", "B", "C"]);
+    }
+
+    #[test]
+    fn test_fidl_file_34() {
+        let src = r#"
+        package org.javaohjavawhyareyouso
+        typeCollection MyTypes {
+            struct Derived extends Missing {
+                UInt8 d1
+            }
+        }"#
+        .to_string();
+        let publisher = parse(&src).unwrap();
+        let fmt = FidlFileRs::new(src, &publisher);
+        assert!(matches!(fmt, Err(crate::FileError::ExtendsBaseNotFound(_, _, _))));
+
+        // Bases qualified with something from another file can only be found through a project.
+        let src = r#"
+        package org.javaohjavawhyareyouso
+        import model "other.fidl"
+        typeCollection MyTypes {
+            struct Derived extends OtherTypes.Base {
+                UInt8 d1
+            }
+        }"#
+        .to_string();
+        let publisher = parse(&src).unwrap();
+        let output = FidlFileRs::new(src, &publisher).unwrap();
+        let flattened = output.flattened_type_collection(&output.type_collections[0]);
+        assert!(matches!(
+            flattened,
+            Err(crate::FileError::ExtendsBaseNotFound(name, base, _)) if name == "Derived" && base == "OtherTypes.Base"
+        ));
+    }
+
+    #[test]
+    fn test_fidl_file_35() {
+        let src = r#"
+        package org.javaohjavawhyareyouso
+        typeCollection MyTypes {
+            enumeration A extends B {
+                A1
+            }
+            enumeration B extends A {
+                B1
+            }
+        }"#
+        .to_string();
+        let publisher = parse(&src).unwrap();
+        let fmt = FidlFileRs::new(src, &publisher);
+        assert!(matches!(fmt, Err(crate::FileError::CyclicExtends(_, _))));
+
+        let src = r#"
+        package org.javaohjavawhyareyouso
+        interface Loop extends Loop {
+        }"#
+        .to_string();
+        let publisher = parse(&src).unwrap();
+        let fmt = FidlFileRs::new(src, &publisher);
+        assert!(matches!(fmt, Err(crate::FileError::CyclicExtends(_, _))));
+    }
+
+    #[test]
+    fn test_fidl_file_36() {
+        let src = r#"
+        package org.javaohjavawhyareyouso
+        typeCollection MyTypes {
+            struct Shape polymorphic {
+                UInt8 colour
+            }
+            struct Circle extends Shape {
+                Float radius
+            }
+            struct Point {
+                Float x
+            }
+        }"#
+        .to_string();
+        let publisher = parse(&src).unwrap();
+        let output = FidlFileRs::new(src, &publisher).unwrap();
+        let structures = &output.type_collections[0].structures;
+        assert!(structures[0].polymorphic);
+        assert!(!structures[1].polymorphic);
+        let scope = output.qualified_name("MyTypes");
+        assert_eq!(scope, "org.javaohjavawhyareyouso.MyTypes");
+        assert_ne!(structures[0].type_tag(&scope), structures[1].type_tag(&scope));
+        // The same struct name in another package gets another tag.
+        assert_ne!(structures[0].type_tag(&scope), structures[0].type_tag("org.other.MyTypes"));
+
+        let flattened = output.flattened_type_collection(&output.type_collections[0]).unwrap();
+        assert!(flattened.structures[0].polymorphic);
+        assert!(flattened.structures[1].polymorphic);
+        assert!(!flattened.structures[2].polymorphic);
+    }
+
+    #[test]
+    fn test_fidl_file_37() {
+        use crate::ConstantValue;
+        let src = r#"
+        package org.javaohjavawhyareyouso
+        typeCollection MyTypes {
+            const UInt32 BASE = 0x10
+            const UInt32 MAX = (BASE + 2) * 3 - 1e1 % 3
+            const Int8 MIN = -BASE
+            const Double RATIO = MAX / 4.0
+            const String GREETING = "Hello, " + "World"
+            const Boolean ENABLED = true
+            const UInt8[] DEFAULTS = [1, 0b10, BASE]
+            const Point ORIGIN = { x: 0.5, label: GREETING }
+        }"#
+        .to_string();
+        let publisher = parse(&src).unwrap();
+        let output = FidlFileRs::new(src, &publisher).unwrap();
+        let constants = &output.type_collections[0].constants;
+        let values: Vec<&ConstantValue> = constants.iter().map(|c| &c.value).collect();
+        assert_eq!(
+            values,
+            vec![
+                &ConstantValue::Integer(16),
+                &ConstantValue::Integer(53),
+                &ConstantValue::Integer(-16),
+                &ConstantValue::Float(13.25),
+                &ConstantValue::String("Hello, World".to_string()),
+                &ConstantValue::Boolean(true),
+                &ConstantValue::Array(vec![
+                    ConstantValue::Integer(1),
+                    ConstantValue::Integer(2),
+                    ConstantValue::Integer(16)
+                ]),
+                &ConstantValue::Struct(vec![
+                    ("x".to_string(), ConstantValue::Float(0.5)),
+                    (
+                        "label".to_string(),
+                        ConstantValue::String("Hello, World".to_string())
+                    )
+                ]),
+            ]
+        );
+        assert!(constants[6].is_array);
+        assert_eq!(constants[7].type_n, "Point");
+    }
+
+    #[test]
+    fn test_fidl_file_38() {
+        let cases = [
+            ("const UInt8 TOO_BIG = 256", "InvalidConstant"),
+            ("const UInt32 NEGATIVE = 0 - 1", "InvalidConstant"),
+            ("const Int32 DIV = 1 / (2 - 2)", "InvalidConstant"),
+            (r#"const String MIXED = "a" + 1"#, "InvalidConstant"),
+            ("const UInt8[] NOT_ARRAY = 1", "InvalidConstant"),
+            ("const UInt8 LATER = AFTER\n const UInt8 AFTER = 1", "ConstantNotFound"),
+            ("const UInt8 TWICE = 1\n const UInt8 TWICE = 2", "ConstantAlreadyExists"),
+            ("const Int64 NEGATED = -(-170141183460469231731687303715884105728)", "InvalidConstant"),
+            ("const Integer HUGE = 0x8000000000000000", "InvalidConstant"),
+            ("struct Point {\n UInt8 x\n }\n const Point BIG = { x: 256 }", "InvalidConstant"),
+            ("struct Point {\n UInt8 x\n }\n const Point MISSING = { y: 1 }", "InvalidConstant"),
+        ];
+        for (constants, expected) in cases {
+            let src = format!(
+                "package org.javaohjavawhyareyouso
+                interface MyInterface {{
+                    {}
+                }}",
+                constants
+            );
+            let publisher = parse(&src).unwrap();
+            let err = FidlFileRs::new(src, &publisher).unwrap_err();
+            assert!(format!("{:?}", err).starts_with(expected), "{}: {:?}", constants, err);
+        }
+    }
+
+    #[test]
+    fn test_fidl_file_39() {
+        use crate::IntegerRange;
+        let src = r#"
+        package org.javaohjavawhyareyouso
+        interface MyInterface {
+            typedef Percent is Integer(0, 100)
+            const Integer(-5, 5) SMALL = -5
+            attribute Integer(minInt, maxInt) anything
+            struct Ranges {
+                Integer(-200, 200) a
+                Integer(0, 0x10000)[] b
+            }
+        }"#
+        .to_string();
+        let publisher = parse(&src).unwrap();
+        let output = FidlFileRs::new(src, &publisher).unwrap();
+        let interface = &output.interfaces[0];
+        let percent = interface.typedefs[0].range.unwrap();
+        assert_eq!(interface.typedefs[0].type_n, "Integer");
+        assert_eq!(percent, IntegerRange { min: 0, max: 100 });
+        assert_eq!(percent.wire_type(), "UInt8");
+        assert_eq!(interface.constants[0].range.unwrap().wire_type(), "Int8");
+        assert_eq!(interface.attributes[0].type_n, "Integer");
+        assert_eq!(interface.attributes[0].range.unwrap().wire_type(), "Int64");
+        let contents = &interface.structures[0].contents;
+        assert_eq!(contents[0].range.unwrap().wire_type(), "Int16");
+        assert_eq!(contents[1].type_n, "Integer");
+        assert!(contents[1].is_array);
+        assert_eq!(contents[1].range.unwrap().wire_type(), "UInt32");
+    }
+
+    #[test]
+    fn test_fidl_file_40() {
+        let sources = [
+            "typedef Backwards is Integer(10, 0)",
+            "typedef NotAnInteger is UInt8(0, 10)",
+            "typedef TooBig is Integer(0, 0xFFFFFFFFFFFFFFFF)",
+            "const Integer(0, 10) OUT_OF_RANGE = 11",
+        ];
+        for body in sources {
+            let src = format!(
+                "package org.javaohjavawhyareyouso\ntypeCollection MyTypes {{\n{}\n}}",
+                body
+            );
+            let publisher = parse(&src).unwrap();
+            let output = FidlFileRs::new(src.clone(), &publisher);
+            assert!(output.is_err(), "{} should not be valid", body);
+        }
+    }
+
+    #[test]
+    fn test_fidl_file_41() {
+        let src = r#"
+        package org.javaohjavawhyareyouso
+        typeCollection MyTypes {
+            array Names of String
+            <** @length: 0x4 **>
+            array Matrix of UInt8[]
+        }
+        interface MyInterface {
+            array Percentages of Integer(0, 100)
+            struct Fixed {
+                <** @length: 3 **>
+                UInt16[] rgb
+                Names names
+            }
+        }"#
+        .to_string();
+        let publisher = parse(&src).unwrap();
+        let output = FidlFileRs::new(src, &publisher).unwrap();
+        let arrays = &output.type_collections[0].arrays;
+        assert_eq!(arrays[0].name, "Names");
+        assert_eq!(arrays[0].type_n, "String");
+        assert_eq!(arrays[0].length, None);
+        assert_eq!(arrays[1].type_n, "UInt8");
+        assert!(arrays[1].is_array);
+        assert_eq!(arrays[1].length, Some(4));
+        let interface = &output.interfaces[0];
+        assert_eq!(interface.arrays[0].range.unwrap().max, 100);
+        let contents = &interface.structures[0].contents;
+        assert_eq!(contents[0].length, Some(3));
+        assert_eq!(contents[1].length, None);
+    }
+
+    #[test]
+    fn test_fidl_file_42() {
+        let sources = [
+            "array Names of String\narray Names of UInt8",
+            "<** @length: four **>\narray Names of String",
+            "struct NotAnArray {\n<** @length: 4 **>\nUInt8 value\n}",
+            "<** @length: 0 **>\narray Names of String",
+            "<** @length: 2 @length: 3 **>\narray Names of String",
+        ];
+        for body in sources {
+            let src = format!(
+                "package org.javaohjavawhyareyouso\ntypeCollection MyTypes {{\n{}\n}}",
+                body
+            );
+            let publisher = parse(&src).unwrap();
+            let output = FidlFileRs::new(src.clone(), &publisher);
+            assert!(output.is_err(), "{} should not be valid", body);
+        }
+        let src = "package org.javaohjavawhyareyouso\ntypeCollection MyTypes {\n<** @length: four **>\narray Names of String\n}";
+        let publisher = parse(src).unwrap();
+        let error = FidlFileRs::new(src.to_string(), &publisher).unwrap_err();
+        assert!(matches!(error, FileError::InvalidArrayLength(..)));
+        assert_eq!(
+            error.to_string(),
+            "The array length of 'Names' is invalid, 'four' is not a 64-bit unsigned integer."
+        );
+    }
+
+    #[test]
+    fn test_resolver_1() {
+        let resolver = Resolver::new("tests/resolver_fidls/main.fidl").unwrap();
+        assert_eq!(resolver.files.len(), 3);
+        assert_eq!(resolver.symbols.package("org.example.types").len(), 2);
+        assert_eq!(resolver.symbols.named("Point").len(), 2);
+        let errors = resolver.check();
+        assert!(errors.is_empty(), "{:#?}", errors);
+
+        let main = Path::new("tests/resolver_fidls/main.fidl");
+        let point = resolver.lookup(main, "Point");
+        assert_eq!(point.len(), 1);
+        assert_eq!(point[0].qualified_name(), "org.example.types.Common.Point");
+        let colour = resolver.lookup(main, "Other.Colour");
+        assert_eq!(colour.len(), 1);
+        assert_eq!(colour[0].qualified_name(), "org.example.other.Other.Colour");
+        assert!(resolver.lookup(main, "Colour").is_empty());
+    }
+
+    #[test]
+    fn test_resolver_2() {
+        let resolver = Resolver::new("tests/resolver_fidls/broken.fidl").unwrap();
+        let errors = resolver.check();
+        assert_eq!(errors.len(), 2, "{:#?}", errors);
+        let source = &resolver.files[0].1.source;
+        let lines = LineIndex::new(source);
+        match &errors[0] {
+            FileError::AmbiguousTypeRef(_, span, name, candidates) => {
+                assert_eq!(lines.line_column(span.trim(source).start), (8, 9));
+                assert_eq!(name, "Point");
+                assert_eq!(candidates.len(), 2);
+            }
+            err => panic!("Expected an ambiguous type, got {:?}", err),
+        }
+        match &errors[1] {
+            FileError::UnresolvedTypeRef(_, span, name) => {
+                assert_eq!(lines.line_column(span.trim(source).start), (9, 9));
+                assert_eq!(name, "Missing");
+            }
+            err => panic!("Expected an unresolved type, got {:?}", err),
+        }
+    }
+
+    #[test]
+    fn test_resolver_3() {
+        let dir = std::env::temp_dir().join("minimal_fidl_resolver_3");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("missing_import.fidl");
+        std::fs::write(
+            &path,
+            "package org.example\nimport model \"does_not_exist.fidl\"\ntypeCollection Types {\n}",
+        )
+        .unwrap();
+        let output = Resolver::new(&path);
+        assert!(matches!(output, Err(FileError::ImportNotFound(_, _))));
+    }
+
+    #[test]
+    fn test_resolver_4() {
+        let path = Path::new("tests/resolver_fidls/main.fidl");
+        // An unsaved edit with a new annotation and a broken method.
+        let source = std::fs::read_to_string(path)
+            .unwrap()
+            .replace(
+                "    struct Local {",
+                "    <** @description: Only used here **>\n    struct Local {",
+            )
+            .replace("    method move {", "    metod broken {\n    }\n    method move {");
+        let mut resolver = Resolver::default();
+        let errors = resolver.load_source(path, source.clone()).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(resolver.files.len(), 3);
+
+        let offset = source.find("Point origin").unwrap() as u32 + 2;
+        let point = resolver.definition(path, offset).unwrap();
+        assert_eq!(point.qualified_name(), "org.example.types.Common.Point");
+        let common = resolver.file(&point.file).unwrap();
+        let declaration = &common.source[point.span.trim(&common.source).start as usize..];
+        assert!(declaration.starts_with("struct Point {"));
+
+        let offset = source.find("Local local").unwrap() as u32;
+        let local = resolver.definition(path, offset).unwrap();
+        assert_eq!(local.annotations[0].contents.trim(), "Only used here");
+        // The name of the field is not a type.
+        let offset = source.find("local\n").unwrap() as u32 + 1;
+        assert!(resolver.definition(path, offset).is_none());
+    }
+
+    #[test]
+    fn test_resolver_5() {
+        use crate::resolver::name_at;
+        let source = "struct Größe {\n    Types.Point p\n}";
+        // Offsets in the middle of a character do not panic.
+        let offset = source.find('ö').unwrap() as u32 + 1;
+        assert_eq!(name_at(source, offset), Some("Größe"));
+        let offset = source.find("Point").unwrap() as u32;
+        assert_eq!(name_at(source, offset), Some("Types.Point"));
+    }
+
+    #[test]
+    fn test_fidl_project_2() {
+        let project = FidlProject::new("tests/resolver_fidls").unwrap();
+        assert_eq!(project.files().count(), 4);
+        assert_eq!(project.package_files("org.example.types").len(), 1);
+
+        let (path, interface) = project
+            .find_interface("org.example.app.App")
+            .unwrap();
+        assert_eq!(interface.name, "App");
+        assert!(path.ends_with("main.fidl"));
+        assert!(project.find_interface("org.example.types.App").is_none());
+
+        let mut types: Vec<String> = project
+            .types_in_package("org.example.other")
+            .iter()
+            .map(|symbol| symbol.qualified_name())
+            .collect();
+        types.sort();
+        assert_eq!(
+            types,
+            ["org.example.other.Other.Colour", "org.example.other.Other.Point"]
+        );
+
+        let common = Path::new("tests/resolver_fidls/types/common.fidl");
+        let mut importers: Vec<&Path> = project.reverse_dependencies(common);
+        importers.sort();
+        assert_eq!(importers.len(), 2);
+        assert!(importers[0].ends_with("broken.fidl"));
+        assert!(importers[1].ends_with("main.fidl"));
+        assert_eq!(project.check().len(), 2);
+    }
+
+    #[test]
+    fn test_fidl_project_3() {
+        let dir = std::env::temp_dir().join("minimal_fidl_project_3");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let types = dir.join("types.fidl");
+        let main = dir.join("main.fidl");
+        std::fs::write(
+            &types,
+            "package org.types\ntypeCollection Common {\n    struct Point {\n        Int32 x\n    }\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            &main,
+            "package org.app\nimport org.types.Common.* from \"types.fidl\"\ninterface App {\n    attribute Point p\n}\n",
+        )
+        .unwrap();
+        let mut project = FidlProject::new(&dir).unwrap();
+        assert_eq!(project.files().count(), 2);
+        assert!(project.check().is_empty());
+        // Point is only used by the file importing it.
+        assert!(project.unused_types().is_empty());
+
+        std::fs::write(
+            &types,
+            "package org.types\ntypeCollection Common {\n    struct Place {\n        Int32 x\n    }\n}\n",
+        )
+        .unwrap();
+        project.reload(&types).unwrap();
+        assert_eq!(project.files().count(), 2);
+        assert_eq!(project.types_in_package("org.types")[0].name, "Place");
+        let errors = project.check();
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            FileError::UnresolvedTypeRef(path, _, name) if *path == main && name == "Point"
+        ));
+        let unused = project.unused_types();
+        assert_eq!(unused.len(), 1);
+        assert!(matches!(
+            &unused[0],
+            (path, FileError::UnusedType(name, _)) if *path == types && name == "Place"
+        ));
+
+        std::fs::remove_file(&main).unwrap();
+        project.remove(&main);
+        assert_eq!(project.files().count(), 1);
+        assert!(project.find_interface("org.app.App").is_none());
+        assert!(project.check().is_empty());
+    }
+
+    #[test]
+    fn test_fidl_project_5() {
+        let dir = std::env::temp_dir().join("minimal_fidl_project_5");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("a.fidl"),
+            "package org.a\ntypeCollection Common {\n    struct Point {\n        Int32 x\n    }\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("b.fidl"),
+            "package org.b\nimport org.a.Common.* from \"a.fidl\"\ninterface App {\n    attribute Point p\n}\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("c.fidl"), "package org.c\ninterface {\n").unwrap();
+        std::fs::write(
+            dir.join("d.fidl"),
+            "package org.d\nimport model \"missing.fidl\"\ninterface Lonely {\n}\n",
+        )
+        .unwrap();
+        let paths = FidlProject::paths(&dir).unwrap();
+
+        // One bad file does not stop the others from loading and resolving against each other.
+        let (mut project, errors) = FidlProject::from_paths_with_recovery(paths.clone());
+        let mut failed: Vec<&str> = errors
+            .iter()
+            .map(|(path, _)| path.file_name().unwrap().to_str().unwrap())
+            .collect();
+        failed.sort();
+        assert_eq!(failed, ["c.fidl", "d.fidl"]);
+        assert_eq!(project.files().count(), 3);
+        assert!(project.find_interface("org.d.Lonely").is_some());
+        assert!(project.check().is_empty(), "{:#?}", project.check());
+
+        assert!(FidlProject::from_paths(paths).is_err());
+
+        // The importers of a deleted file are still found until it is removed from the project.
+        std::fs::remove_file(dir.join("a.fidl")).unwrap();
+        let importers = project.reverse_dependencies(&dir.join("a.fidl"));
+        assert_eq!(importers.len(), 1);
+        assert!(importers[0].ends_with("b.fidl"));
+        project.remove(&dir.join("a.fidl"));
+        assert!(project.reverse_dependencies(&dir.join("a.fidl")).is_empty());
+        assert_eq!(project.files().count(), 2);
+    }
+
+    #[test]
+    fn test_fidl_project_6() {
+        // Files are found, loaded and indexed in the same order whatever order the file system lists them in.
+        let dir = std::env::temp_dir().join("minimal_fidl_project_6");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("a")).unwrap();
+        for name in ["z.fidl", "a/b.fidl", "m.fidl", "a/a.fidl"] {
+            let interface = name.trim_end_matches(".fidl").replace('/', "_");
+            std::fs::write(
+                dir.join(name),
+                format!("package org.example\ninterface I_{} {{\n}}\n", interface),
+            )
+            .unwrap();
+        }
+        std::fs::write(dir.join("notes.txt"), "not a fidl file").unwrap();
+        let expected = ["a/a.fidl", "a/b.fidl", "m.fidl", "z.fidl"].map(|name| dir.join(name));
+
+        assert_eq!(FidlProject::paths(&dir).unwrap(), expected);
+        let project = FidlProject::new(&dir).unwrap();
+        let files: Vec<&Path> = project.files().map(|(path, _)| path).collect();
+        assert_eq!(files, expected);
+        let interfaces: Vec<&str> = project
+            .package_files("org.example")
+            .iter()
+            .map(|(_, file)| file.interfaces[0].name.as_str())
+            .collect();
+        assert_eq!(interfaces, ["I_a_a", "I_a_b", "I_m", "I_z"]);
+    }
+
+    #[test]
+    fn test_fidl_project_7() {
+        // A broken file that is both walked and imported through another relative path is reported once.
+        let dir = std::env::temp_dir().join("minimal_fidl_project_7");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+        std::fs::write(
+            dir.join("a.fidl"),
+            "package org.a\nimport org.b.Common.* from \"../minimal_fidl_project_7/sub/broken.fidl\"\ninterface A {\n    attribute Point p\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("sub/broken.fidl"),
+            "package org.b\ntypeCollection Common {\n    struct Point {\n        Int32 x\n    }\n}\ninterface {\n}\n",
+        )
+        .unwrap();
+        let paths = FidlProject::paths(&dir).unwrap();
+
+        let (project, errors) = FidlProject::from_paths_with_recovery(paths);
+        assert_eq!(errors.len(), 1, "{:#?}", errors);
+        assert_eq!(errors[0].0, dir.join("sub/broken.fidl"));
+        // What does parse of the broken file is loaded, so references into it still resolve.
+        assert_eq!(project.files().count(), 2);
+        assert!(project.check().is_empty(), "{:#?}", project.check());
+    }
+
+    #[test]
+    fn test_fidl_project_4() {
+        let dir = std::env::temp_dir().join("minimal_fidl_project_4");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("base.fidl"),
+            r#"package org.base
+typeCollection Common {
+    struct Shape polymorphic {
+        UInt8 colour
+    }
+    struct Rounded extends Shape {
+        Float corner
+    }
+    enumeration Colour {
+        Red
+    }
+}
+interface Base {
+    attribute UInt8 base_value
+}
+"#,
+        )
+        .unwrap();
+        let main = dir.join("main.fidl");
+        std::fs::write(
+            &main,
+            r#"package org.app
+import org.base.Common.* from "base.fidl"
+typeCollection App {
+    struct Circle extends Shape {
+        Float radius
+    }
+    struct Square extends org.base.Common.Rounded {
+        Float side
+    }
+    enumeration More extends Colour {
+        Blue
+    }
+}
+interface Derived extends org.base.Base {
+    attribute UInt8 derived_value
+}
+interface Broken extends org.base.Missing {
+}
+"#,
+        )
+        .unwrap();
+        let project = FidlProject::new(&dir).unwrap();
+        let file = project.file(&main).unwrap();
+
+        let flattened = project
+            .flattened_type_collection(file, &file.type_collections[0])
+            .unwrap();
+        let names: Vec<&str> = flattened.structures[0].contents.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["colour", "radius"]);
+        assert!(flattened.structures[0].polymorphic);
+        // The base of an imported base is looked up in the file that declares it.
+        let names: Vec<&str> = flattened.structures[1].contents.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["colour", "corner", "side"]);
+        let names: Vec<&str> = flattened.enumerations[0].values.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["Red", "Blue"]);
+
+        let flattened = project.flattened_interface(file, &file.interfaces[0]).unwrap();
+        let names: Vec<&str> = flattened.attributes.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["base_value", "derived_value"]);
+        let broken = project.flattened_interface(file, &file.interfaces[1]);
+        assert!(matches!(
+            broken,
+            Err(FileError::ExtendsBaseNotFound(name, base, _)) if name == "Broken" && base == "org.base.Missing"
+        ));
+
+        // On its own the file can not see the imported bases.
+        assert!(matches!(
+            file.flattened_type_collection(&file.type_collections[0]),
+            Err(FileError::ExtendsBaseNotFound(_, _, _))
+        ));
+    }
+
+    #[test]
+    fn test_fidl_file_43() {
+        let src = r#"package org.javaohjavawhyareyouso
+        typeCollection MyTypes {
+            struct thing {
+                p1 p1
+                UInt8 p2
+                Other p3
+                MyTypes.Other p4
+                MyInterface.Nested p5
+                MyTypes.Missing p6
+            }
+            typedef Other is Missing
+            struct Other2 {
+                Other[] p1
+            }
+        }
+        interface MyInterface {
+            attribute Nested[] a1
+            attribute Gone a2
+            struct Nested {
+                thing t
+            }
+            method m1 {
+                in {
+                    Nested n
+                }
+                out {
+                    Vanished v
+                }
+                error Absent
+            }
+        }"#;
+        let publisher = parse(&src).unwrap();
+        let output = FidlFileRs::new(src.to_string(), &publisher).unwrap();
+        let undefined: Vec<String> = output
+            .undefined_types()
+            .iter()
+            .map(|err| match err {
+                FileError::UndefinedType(name, _) => name.clone(),
+                err => panic!("Expected an undefined type, got {:?}", err),
+            })
+            .collect();
+        assert_eq!(
+            undefined,
+            ["Missing", "p1", "MyTypes.Missing", "Gone", "Vanished", "Absent"]
+        );
+        let unused: Vec<String> = output
+            .unused_types()
+            .iter()
+            .map(|err| err.render(None, &src))
+            .collect();
+        assert_eq!(
+            unused,
+            ["warning: The type 'Other2' is declared but never used.\n  --> 12:13\n   |\n12 |             struct Other2 {\n   |             ^^^^^^^^^^^^^^^"]
+        );
+    }
+
+    #[test]
+    fn test_fidl_file_44() {
+        // Names that may come from an import are left for the resolver to check.
+        let src = r#"package org.javaohjavawhyareyouso
+        import org.example.Common.* from "common.fidl"
+        import model "other.fidl"
+        typeCollection MyTypes {
+            struct thing {
+                Point p1
+                org.example.Other.Colour p2
+                Other.Colour p3
+            }
+        }"#;
+        let publisher = parse(&src).unwrap();
+        let output = FidlFileRs::new(src.to_string(), &publisher).unwrap();
+        assert!(output.undefined_types().is_empty());
+
+        // Without an imported model a qualified name has to be under an imported namespace.
+        let src = r#"package org.javaohjavawhyareyouso
+        import org.example.Common.* from "common.fidl"
+        typeCollection MyTypes {
+            struct thing {
+                org.example.Common.Colour p1
+                Common.Colour p2
+                Other.Colour p3
+                org.other.Common.Colour p4
+            }
+        }"#;
+        let publisher = parse(&src).unwrap();
+        let output = FidlFileRs::new(src.to_string(), &publisher).unwrap();
+        let undefined: Vec<String> = output
+            .undefined_types()
+            .iter()
+            .map(|err| match err {
+                FileError::UndefinedType(name, _) => name.clone(),
+                err => panic!("Expected an undefined type, got {:?}", err),
+            })
+            .collect();
+        assert_eq!(undefined, ["Other.Colour", "org.other.Common.Colour"]);
+
+        let path = Path::new("../minimal-fidl-generator/tests/test_fidls/test1.fidl");
+        let output = FidlProject::generate_file(path).unwrap();
+        assert!(output.undefined_types().is_empty(), "{:#?}", output.undefined_types());
+    }
+
+    #[test]
+    fn test_fidl_file_45() {
+        let src = "package org.javaohjavawhyareyouso\ntypeCollection MyTypes {\n    struct Point {\n        UInt8 x\n    }\n\tstruct Point {\n        UInt8 y\n    }\n}";
+        let publisher = parse(&src).unwrap();
+        let output = FidlFileRs::new(src.to_string(), &publisher);
+        let err = output.unwrap_err();
+        assert_eq!(err.to_string(), "The Struct: 'Point' already exists.");
+        assert_eq!(
+            err.render(Some(Path::new("types.fidl")), src),
+            "error: The Struct: 'Point' already exists.\n --> types.fidl:6:2\n  |\n6 | \tstruct Point {\n  | \t^^^^^^^^^^^^^^\n  = note: first declared at types.fidl:3:5"
+        );
+
+        let lines = LineIndex::new("ab\nc\u{e9}d\n");
+        assert_eq!(lines.line_column(0), (1, 1));
+        assert_eq!(lines.line_column(3), (2, 1));
+        assert_eq!(lines.line_column(6), (2, 3));
+        assert_eq!(lines.line_column(8), (3, 1));
+        assert_eq!(lines.line(2), "c\u{e9}d");
+    }
+
+    #[test]
+    fn test_fidl_file_46() {
+        let src = "package org.javaohjavawhyareyouso\ninterface MyInterface {\n    metod doThing {\n    }\n}";
+        let err = FidlProject::generate_file_from_string(src.to_string()).unwrap_err();
+        match &err {
+            FileError::CouldNotParseSourceString(failure) => {
+                assert_eq!((failure.line, failure.column), (3, 5));
+                assert_eq!(failure.found.as_deref(), Some("metod"));
+            }
+            err => panic!("Expected a parse failure, got {:?}", err),
+        }
+        let rendered = err.render(Some(Path::new("my.fidl")), src);
+        assert!(rendered.ends_with(" --> my.fidl:3:5\n  |\n3 |     metod doThing {\n  |     ^^^^^"));
+    }
+
+    #[test]
+    fn test_fidl_file_47() {
+        let src = "package org.javaohjavawhyareyouso\ninterface MyInterface {\n    metod doThing {\n    }\n    method doOther {\n    }\n    struct Broken {\n        UInt8\n    }\n}\ntypeCollection MyTypes {\n    struct Fine {\n        UInt8 a\n    }\n}";
+        let (fidl_file, errors) =
+            FidlProject::generate_file_from_string_with_recovery(src.to_string()).unwrap();
+        assert_eq!(fidl_file.interfaces[0].methods.len(), 1);
+        assert_eq!(fidl_file.interfaces[0].methods[0].name, "doOther");
+        assert!(fidl_file.interfaces[0].structures.is_empty());
+        assert_eq!(fidl_file.type_collections[0].structures[0].name, "Fine");
+        let lines: Vec<usize> = errors
+            .iter()
+            .map(|err| match err {
+                FileError::CouldNotParseSourceString(failure) => failure.line,
+                err => panic!("Expected a parse failure, got {:?}", err),
+            })
+            .collect();
+        assert_eq!(lines, vec![3, 9]);
+        let rendered = errors[1].render(None, src);
+        assert!(rendered.ends_with("9 |     }\n  |     ^"));
+    }
+
+    #[test]
+    fn test_fidl_file_48() {
+        let src = r#"
+        package org.javaohjavawhyareyouso
+        typeCollection MyTypes {
+            map Ranged { Integer(0, 10) to Integer(-5, 5)[] }
+            map Broken { UInt8[] to String }
+        }"#
+        .to_string();
+        let publisher = parse(&src).unwrap();
+        let err = FidlFileRs::new(src.clone(), &publisher).unwrap_err();
+        assert!(matches!(&err, FileError::MapKeyIsArray(name, _) if name == "Broken"));
+
+        let src = src.replace("map Broken { UInt8[] to String }", "");
+        let publisher = parse(&src).unwrap();
+        let output = FidlFileRs::new(src, &publisher).unwrap();
+        let map = &output.type_collections[0].maps[0];
+        let range = map.key_range.unwrap();
+        assert_eq!((range.min, range.max), (0, 10));
+        let range = map.value_range.unwrap();
+        assert_eq!((range.min, range.max), (-5, 5));
+        assert!(map.value_is_array);
+    }
+
+    #[test]
+    fn test_fidl_file_49() {
+        use crate::ConstantValue;
+        // The struct is declared after the constant and its fields are coerced to their types.
+        let src = r#"
+        package org.javaohjavawhyareyouso
+        typeCollection MyTypes {
+            const Point ORIGIN = { x: 1, y: 2, inner: { value: 3 } }
+            struct Point {
+                Float x
+                Int8 y
+                Inner inner
+            }
+            struct Inner {
+                Double value
+            }
+        }"#
+        .to_string();
+        let publisher = parse(&src).unwrap();
+        let output = FidlFileRs::new(src, &publisher).unwrap();
+        assert_eq!(
+            output.type_collections[0].constants[0].value,
+            ConstantValue::Struct(vec![
+                ("x".to_string(), ConstantValue::Float(1.0)),
+                ("y".to_string(), ConstantValue::Integer(2)),
+                (
+                    "inner".to_string(),
+                    ConstantValue::Struct(vec![("value".to_string(), ConstantValue::Float(3.0))])
+                ),
+            ])
+        );
+    }
+
     #[test]
     fn test_fidl_project_1() {
         let path = Path::new("../");
-        let mut fmt = FidlProject::new(&path).unwrap();
+        let mut fmt = FidlProject::paths(&path).unwrap();
         println!("{:?}", fmt);
         let fidl_file = FidlProject::generate_file(fmt.pop().unwrap());
         println!("{:#?}", fidl_file)
@@ -0,0 +1,93 @@
+use crate::diagnostic::Span;
+use crate::{annotation::{annotation_constructor, Annotation}, fidl_file::FileError, type_ref::{IntegerRange, TypeRef}};
+use minimal_fidl_parser::{BasicPublisher, Node, Rules};
+#[derive(Debug, Clone)]
+pub struct Map {
+    start_position: u32,
+    end_position: u32,
+    pub annotations: Vec<Annotation>,
+    pub name: String,
+    pub key_type: String,
+    pub key_range: Option<IntegerRange>,
+    pub value_type: String,
+    pub value_is_array: bool,
+    pub value_range: Option<IntegerRange>,
+}
+impl Map {
+    pub fn new(source: &str, publisher: &BasicPublisher, node: &Node) -> Result<Self, FileError> {
+        debug_assert_eq!(node.rule, Rules::map);
+        let mut name: Result<String, FileError> = Err(FileError::InternalLogicError(
+            "Uninitialized value: name in Map::new".to_string(),
+        ));
+        let mut key_type: Option<TypeRef> = None;
+        let mut value_type: Option<TypeRef> = None;
+        let mut annotations: Vec<Annotation> = Vec::new();
+
+        for child in node.get_children() {
+            let child = publisher.get_node(*child);
+            match child.rule {
+                Rules::comment
+                | Rules::multiline_comment
+                | Rules::open_bracket
+                | Rules::close_bracket => {}
+                Rules::annotation_block => {
+                    annotations = annotation_constructor(source, publisher, child)?;
+                }
+                Rules::type_dec => {
+                    name = Ok(child.get_string(source));
+                }
+                Rules::type_ref => {
+                    // The key always comes before the value, i.e `map Name { Key to Value }`
+                    let res = TypeRef::new(source, publisher, child)?;
+                    if key_type.is_none() {
+                        key_type = Some(res);
+                    } else {
+                        value_type = Some(res);
+                    }
+                }
+                rule => {
+                    return Err(FileError::UnexpectedNode(rule, "Map::new".to_string()));
+                }
+            }
+        }
+        let key_type = key_type.ok_or(FileError::InternalLogicError(
+            "Uninitialized value: key_type in Map::new".to_string(),
+        ))?;
+        let value_type = value_type.ok_or(FileError::InternalLogicError(
+            "Uninitialized value: value_type in Map::new".to_string(),
+        ))?;
+        let name = name?;
+        // Arrays are not hashable in every generated language, i.e Python lists.
+        if key_type.is_array {
+            return Err(FileError::MapKeyIsArray(name, Span::of(node)));
+        }
+        Ok(Self {
+            name,
+            key_type: key_type.name,
+            key_range: key_type.range,
+            value_type: value_type.name,
+            value_is_array: value_type.is_array,
+            value_range: value_type.range,
+            annotations,
+            start_position: node.start_position,
+            end_position: node.end_position,
+        })
+    }
+
+    pub fn span(&self) -> Span {
+        Span::new(self.start_position, self.end_position)
+    }
+
+    pub fn push_if_not_exists_else_err(self, maps: &mut Vec<Map>) -> Result<(), FileError> {
+        for m in &mut *maps {
+            if m.name == self.name {
+                return Err(FileError::MapAlreadyExists(
+                    Box::new(m.clone()),
+                    Box::new(self.clone()),
+                ));
+            }
+        }
+        maps.push(self);
+        Ok(())
+    }
+}
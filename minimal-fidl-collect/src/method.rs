@@ -3,9 +3,17 @@ use std::{
     str::FromStr,
 };
 
-use crate::{annotation::{annotation_constructor, Annotation}, fidl_file::FileError, VariableDeclaration};
+use crate::diagnostic::Span;
+use crate::{annotation::{annotation_constructor, Annotation}, enumeration::Enumeration, fidl_file::FileError, type_ref::TypeRef, VariableDeclaration};
 use minimal_fidl_parser::{BasicPublisher, Key, Node, Rules};
 #[derive(Debug, Clone)]
+pub enum MethodError {
+    /// `error { A B C }`, named `<method name>Error`.
+    Inline(Enumeration),
+    /// `error SomeErrorEnum`
+    Reference(String),
+}
+#[derive(Debug, Clone)]
 pub struct Method {
     start_position: u32,
     end_position: u32,
@@ -13,6 +21,8 @@ pub struct Method {
     pub name: String,
     pub input_parameters: Vec<VariableDeclaration>,
     pub output_parameters: Vec<VariableDeclaration>,
+    pub errors: Option<MethodError>,
+    pub fire_and_forget: bool,
 }
 impl Method {
     pub fn new(source: &str, publisher: &BasicPublisher, node: &Node) -> Result<Self, FileError> {
@@ -23,6 +33,8 @@ impl Method {
         let mut input_parameters: Vec<VariableDeclaration> = Vec::new();
         let mut output_parameters: Vec<VariableDeclaration> = Vec::new();
         let mut annotations: Vec<Annotation> = Vec::new();
+        let mut error_node: Option<&Node> = None;
+        let mut fire_and_forget = false;
 
         for child in node.get_children() {
             let child = publisher.get_node(*child);
@@ -40,37 +52,88 @@ impl Method {
                 Rules::type_dec => {
                     name = Ok(child.get_string(source));
                 }
+                Rules::fire_and_forget => {
+                    fire_and_forget = true;
+                }
                 Rules::input_params => {
                     Self::params(source, publisher, child, &mut input_parameters)?;
                 }
                 Rules::output_params => {
                     Self::params(source, publisher, child, &mut output_parameters)?;
                 }
+                Rules::method_error => {
+                    error_node = Some(child);
+                }
                 rule => {
                     return Err(FileError::UnexpectedNode(rule, "Method::new".to_string()));
                 }
             }
         }
-        Ok(Self {
-            name: name?,
+        let name = name?;
+        // Inline errors are named after the method so we can only build them once the name is known.
+        let errors = match error_node {
+            None => None,
+            Some(error_node) => Some(Self::error(source, publisher, error_node, &name)?),
+        };
+        let method = Self {
+            name,
             start_position: node.start_position,
             annotations,
             end_position: node.end_position,
             input_parameters,
             output_parameters,
-        })
+            errors,
+            fire_and_forget,
+        };
+        // Nobody waits for a reply so there is nothing to put out params or errors in.
+        if method.fire_and_forget
+            && (!method.output_parameters.is_empty() || method.errors.is_some())
+        {
+            return Err(FileError::FireAndForgetMethodHasResponse(Box::new(method)));
+        }
+        Ok(method)
+    }
+    pub fn span(&self) -> Span {
+        Span::new(self.start_position, self.end_position)
     }
+
     pub fn push_if_not_exists_else_err(self, methods: &mut Vec<Method>) -> Result<(), FileError> {
         for s in &mut *methods {
             if s.name == self.name {
-                return Err(FileError::MethodAlreadyExists(s.clone(), self.clone()));
+                return Err(FileError::MethodAlreadyExists(
+                    Box::new(s.clone()),
+                    Box::new(self.clone()),
+                ));
             }
         }
         methods.push(self);
         Ok(())
     }
 
-    fn params(
+    fn error(
+        source: &str,
+        publisher: &BasicPublisher,
+        node: &Node,
+        method_name: &str,
+    ) -> Result<MethodError, FileError> {
+        debug_assert_eq!(node.rule, Rules::method_error);
+        for child in node.get_children() {
+            let child = publisher.get_node(*child);
+            if child.rule == Rules::type_ref {
+                let type_ref = TypeRef::new(source, publisher, child)?;
+                return Ok(MethodError::Reference(type_ref.name));
+            }
+        }
+        let enumeration = Enumeration::new_method_error(
+            source,
+            publisher,
+            node,
+            format!("{}Error", method_name),
+        )?;
+        Ok(MethodError::Inline(enumeration))
+    }
+
+    pub(crate) fn params(
         source: &str,
         publisher: &BasicPublisher,
         node: &Node,
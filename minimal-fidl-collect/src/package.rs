@@ -1,7 +1,10 @@
+use crate::diagnostic::Span;
 use crate::fidl_file::FileError;
 use minimal_fidl_parser::{BasicPublisher, Key, Node, Rules};
 #[derive(Debug, Clone)]
 pub struct Package {
+    start_position: u32,
+    end_position: u32,
     pub path: Vec<String>,
 }
 impl Package {
@@ -23,8 +26,16 @@ impl Package {
                 }
             }
         }
-        Ok(Self { path: path? })
+        Ok(Self {
+            path: path?,
+            start_position: node.start_position,
+            end_position: node.end_position,
+        })
     }
+    pub fn span(&self) -> Span {
+        Span::new(self.start_position, self.end_position)
+    }
+
     pub fn push_if_not_exists_else_err(
         self,
         package: &mut Option<Package>,
@@ -38,7 +49,7 @@ impl Package {
                 *package = Some(self);
                 Ok(())
             }
-            Some(package) => Err(FileError::PackageAlreadyExists(package.clone())),
+            Some(package) => Err(FileError::PackageAlreadyExists(Box::new(package.clone()))),
         }
     }
 }
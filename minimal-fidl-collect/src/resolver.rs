@@ -0,0 +1,813 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::diagnostic::Span;
+use crate::fidl_file::{FidlFileRs, FileError};
+use crate::fidl_project::FidlProject;
+use crate::method::MethodError;
+use crate::type_ref::TypeRef;
+use crate::{Annotation, Interface, TypeCollection, VariableDeclaration};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    Type,
+    Interface,
+}
+
+/// A type or interface declared in one of the loaded files.
+#[derive(Debug, Clone)]
+pub struct Symbol {
+    pub file: PathBuf,
+    pub package: String,
+    /// The interface or type collection the type is declared in, empty for interfaces.
+    pub container: String,
+    pub name: String,
+    pub kind: SymbolKind,
+    /// The whole declaration in the source of `file`.
+    pub span: Span,
+    pub annotations: Vec<Annotation>,
+}
+impl Symbol {
+    pub fn qualified_name(&self) -> String {
+        [
+            self.package.as_str(),
+            self.container.as_str(),
+            self.name.as_str(),
+        ]
+        .iter()
+        .filter(|part| !part.is_empty())
+        .copied()
+        .collect::<Vec<&str>>()
+        .join(".")
+    }
+
+    /// Whether `qualifier` names this symbol's container, bare or prefixed with the package.
+    fn qualified_by(&self, qualifier: &str) -> bool {
+        let container = match self.kind {
+            SymbolKind::Type => self.container.as_str(),
+            SymbolKind::Interface => "",
+        };
+        if !container.is_empty() && qualifier == container {
+            return true;
+        }
+        match (self.package.is_empty(), container.is_empty()) {
+            (true, _) => false,
+            (false, true) => qualifier == self.package,
+            (false, false) => qualifier == format!("{}.{}", self.package, container),
+        }
+    }
+}
+
+/// Every symbol of the loaded files keyed by package path.
+#[derive(Debug, Default)]
+pub struct SymbolTable {
+    packages: HashMap<String, Vec<Symbol>>,
+    /// The packages declaring each name, so a name is found without going through every symbol.
+    names: HashMap<String, Vec<String>>,
+}
+impl SymbolTable {
+    /// The symbols declared under `package`, i.e `org.example`.
+    pub fn package(&self, package: &str) -> &[Symbol] {
+        match self.packages.get(package) {
+            Some(symbols) => symbols,
+            None => &[],
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Symbol> {
+        self.packages.values().flatten()
+    }
+
+    /// The symbols called `name` in any package.
+    pub fn named(&self, name: &str) -> Vec<&Symbol> {
+        match self.names.get(name) {
+            Some(packages) => packages
+                .iter()
+                .flat_map(|package| self.package(package))
+                .filter(|symbol| symbol.name == name)
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn remove_file(&mut self, path: &Path) {
+        for symbols in self.packages.values_mut() {
+            symbols.retain(|symbol| symbol.file != path);
+        }
+        self.names.clear();
+        for (package, symbols) in &self.packages {
+            for symbol in symbols {
+                let packages = self.names.entry(symbol.name.clone()).or_default();
+                if !packages.contains(package) {
+                    packages.push(package.clone());
+                }
+            }
+        }
+    }
+
+    fn insert(&mut self, symbol: Symbol) {
+        let packages = self.names.entry(symbol.name.clone()).or_default();
+        if !packages.contains(&symbol.package) {
+            packages.push(symbol.package.clone());
+        }
+        self.packages
+            .entry(symbol.package.clone())
+            .or_default()
+            .push(symbol);
+    }
+}
+
+/// A type used somewhere in a file along with the declaration it appears in.
+pub(crate) struct Reference<'a> {
+    pub(crate) name: &'a str,
+    pub(crate) span: Span,
+    pub(crate) kind: SymbolKind,
+    /// The containers searched for an unqualified name before the rest of the file, nearest first.
+    pub(crate) scope: Vec<&'a str>,
+}
+
+/// What a file can see, worked out once per file so resolving its references does not touch the disk.
+struct Visible<'a> {
+    /// The file itself and the files it imports directly, imports are not transitive.
+    files: Vec<&'a Path>,
+    /// Each `import some.namespace.* from "file"` along with the loaded file it points at.
+    namespaces: Vec<(String, &'a Path)>,
+}
+
+/// Loads a file along with everything it imports and resolves type references across them.
+#[derive(Debug, Default)]
+pub struct Resolver {
+    /// Paths are kept as they were given or joined onto the importing file's directory.
+    pub files: Vec<(PathBuf, FidlFileRs)>,
+    pub symbols: SymbolTable,
+    /// Used to recognise the same file reached through different relative paths.
+    canonical_paths: Vec<PathBuf>,
+}
+impl Resolver {
+    pub fn new(path: impl Into<PathBuf>) -> Result<Self, FileError> {
+        let mut resolver = Self::default();
+        resolver.load(path)?;
+        Ok(resolver)
+    }
+
+    /// Loads `path` and, recursively, the files it imports. Imports are relative to the importing file.
+    /// Files that are already loaded are skipped.
+    pub fn load(&mut self, path: impl Into<PathBuf>) -> Result<(), FileError> {
+        let path = path.into();
+        path.canonicalize().map_err(FileError::CouldNotReadFile)?;
+        let mut pending: Vec<PathBuf> = vec![path];
+        while let Some(path) = pending.pop() {
+            if self.file_index(&path).is_some() {
+                continue;
+            }
+            let file = FidlProject::generate_file(&path)?;
+            self.add_file(path, file, &mut pending)?;
+        }
+        Ok(())
+    }
+
+    /// Like `load` but the file at `path` is parsed from `source` instead of being read, i.e an unsaved
+    /// editor buffer. Declarations that do not parse are skipped and returned as errors.
+    /// The files it imports are still read from disk.
+    pub fn load_source(
+        &mut self,
+        path: impl Into<PathBuf>,
+        source: String,
+    ) -> Result<Vec<FileError>, FileError> {
+        let path = path.into();
+        path.canonicalize().map_err(FileError::CouldNotReadFile)?;
+        let (file, errors) = FidlProject::generate_file_from_string_with_recovery(source)?;
+        let mut pending: Vec<PathBuf> = Vec::new();
+        self.add_file(path, file, &mut pending)?;
+        for import in pending {
+            self.load(import)?;
+        }
+        Ok(errors)
+    }
+
+    /// Parses the file at `path` again after it changed on disk, loading any new imports.
+    /// The other loaded files are left as they are.
+    pub fn reload(&mut self, path: impl Into<PathBuf>) -> Result<(), FileError> {
+        let path = path.into();
+        self.unload(&path);
+        self.load(path)
+    }
+
+    /// Forgets the file at `path`, i.e after it was deleted. Files it imported stay loaded.
+    pub fn unload(&mut self, path: &Path) -> Option<FidlFileRs> {
+        // A file deleted along with its directory can not be canonicalized anymore.
+        let index = self
+            .file_index(path)
+            .or_else(|| self.files.iter().position(|(p, _)| p == path))?;
+        let (path, file) = self.files.remove(index);
+        self.canonical_paths.remove(index);
+        self.symbols.remove_file(&path);
+        Some(file)
+    }
+
+    /// The file is added even when one of its imports is missing, the first missing import is returned
+    /// as an error after it and the imports that do exist are added to `pending`.
+    pub(crate) fn add_file(
+        &mut self,
+        path: PathBuf,
+        file: FidlFileRs,
+        pending: &mut Vec<PathBuf>,
+    ) -> Result<(), FileError> {
+        let canonical_path = path.canonicalize().map_err(FileError::CouldNotReadFile)?;
+        let mut missing: Option<PathBuf> = None;
+        for import in Self::imports(&file) {
+            let import_path = Self::import_path(&path, import);
+            if import_path.is_file() {
+                pending.push(import_path);
+            } else if missing.is_none() {
+                missing = Some(import.to_path_buf());
+            }
+        }
+        self.add_symbols(&path, &file);
+        self.canonical_paths.push(canonical_path);
+        self.files.push((path.clone(), file));
+        match missing {
+            Some(import) => Err(FileError::ImportNotFound(path, import)),
+            None => Ok(()),
+        }
+    }
+
+    /// Every type reference in the loaded files that resolves to no symbol or to more than one.
+    pub fn check(&self) -> Vec<FileError> {
+        let mut errors: Vec<FileError> = Vec::new();
+        for (index, (path, file)) in self.files.iter().enumerate() {
+            let visible = self.visible(index);
+            for reference in Self::references(file) {
+                let candidates = self.resolve(index, &visible, &reference);
+                if candidates.len() == 1 {
+                    continue;
+                }
+                let name = reference.name.to_string();
+                let span = reference.span;
+                if candidates.is_empty() {
+                    errors.push(FileError::UnresolvedTypeRef(path.clone(), span, name));
+                } else {
+                    let candidates = candidates.iter().map(|s| s.qualified_name()).collect();
+                    errors.push(FileError::AmbiguousTypeRef(
+                        path.clone(),
+                        span,
+                        name,
+                        candidates,
+                    ));
+                }
+            }
+        }
+        errors
+    }
+
+    /// Every type declared in the loaded files that no loaded file uses, along with the file declaring it.
+    pub fn unused_types(&self) -> Vec<(&Path, FileError)> {
+        let mut used: Vec<(&Path, Span)> = Vec::new();
+        for (index, (_, file)) in self.files.iter().enumerate() {
+            let visible = self.visible(index);
+            for reference in Self::references(file) {
+                for symbol in self.resolve(index, &visible, &reference) {
+                    used.push((symbol.file.as_path(), symbol.span));
+                }
+            }
+        }
+        let mut unused: Vec<(&Path, FileError)> = Vec::new();
+        for (path, file) in &self.files {
+            for err in file.unused_types() {
+                match err {
+                    FileError::UnusedType(_, span) if used.contains(&(path.as_path(), span)) => {}
+                    err => unused.push((path.as_path(), err)),
+                }
+            }
+        }
+        unused
+    }
+
+    /// Renders an error that came from the file at `path` along with the source it points at.
+    pub fn render(&self, path: &Path, error: &FileError) -> String {
+        match self.file(path) {
+            Some(file) => error.render(Some(path), &file.source),
+            None => format!("error: {}", error),
+        }
+    }
+
+    /// The symbols a type name used in `path` could refer to.
+    pub fn lookup(&self, path: &Path, name: &str) -> Vec<&Symbol> {
+        let index = match self.file_index(path) {
+            Some(index) => index,
+            None => return Vec::new(),
+        };
+        let reference = Reference {
+            name,
+            span: Span::default(),
+            kind: SymbolKind::Type,
+            scope: Vec::new(),
+        };
+        self.resolve(index, &self.visible(index), &reference)
+    }
+
+    /// The symbol the type name at byte `offset` of `path` refers to, if it resolves to exactly one.
+    pub fn definition(&self, path: &Path, offset: u32) -> Option<&Symbol> {
+        let index = self.file_index(path)?;
+        let file = &self.files[index].1;
+        let name = name_at(&file.source, offset)?;
+        // A map's key and value share a span, so the name tells them apart.
+        let reference = Self::references(file)
+            .into_iter()
+            .filter(|r| r.name == name && r.span.start <= offset && offset <= r.span.end)
+            .min_by_key(|r| r.span.end - r.span.start)?;
+        match self.resolve(index, &self.visible(index), &reference)[..] {
+            [symbol] => Some(symbol),
+            _ => None,
+        }
+    }
+
+    /// Like `FidlFileRs::flattened_type_collection` of `file`, a loaded file, but bases may also be
+    /// declared in the files it imports.
+    pub fn flattened_type_collection(
+        &self,
+        file: &FidlFileRs,
+        type_collection: &TypeCollection,
+    ) -> Result<TypeCollection, FileError> {
+        file.flattened_type_collection_with(type_collection, &|file, scope, base, kind| {
+            self.find_external(file, scope, base, kind)
+        })
+    }
+
+    /// Like `FidlFileRs::flattened_interface` of `file`, a loaded file, but bases may also be
+    /// declared in the files it imports.
+    pub fn flattened_interface(
+        &self,
+        file: &FidlFileRs,
+        interface: &Interface,
+    ) -> Result<Interface, FileError> {
+        file.flattened_interface_with(interface, &|file, scope, base, kind| {
+            self.find_external(file, scope, base, kind)
+        })
+    }
+
+    /// The loaded file and container declaring the base named in an `extends` clause of `file`.
+    fn find_external<'a>(
+        &'a self,
+        file: &FidlFileRs,
+        scope: &str,
+        base: &TypeRef,
+        kind: SymbolKind,
+    ) -> Option<(&'a FidlFileRs, &'a str)> {
+        let index = self.files.iter().position(|(_, f)| std::ptr::eq(f, file))?;
+        let reference = Reference {
+            name: &base.name,
+            span: Span::default(),
+            kind,
+            scope: vec![scope],
+        };
+        match self.resolve(index, &self.visible(index), &reference)[..] {
+            [symbol] => {
+                let (_, base_file) = &self.files[self.file_index(&symbol.file)?];
+                Some((base_file, symbol.container.as_str()))
+            }
+            _ => None,
+        }
+    }
+
+    /// The parsed file at `path` if it has been loaded.
+    pub fn file(&self, path: &Path) -> Option<&FidlFileRs> {
+        self.file_index(path).map(|index| &self.files[index].1)
+    }
+
+    /// The loaded files that `path` imports directly.
+    pub fn dependencies(&self, path: &Path) -> Vec<&Path> {
+        let file = match self.file(path) {
+            Some(file) => file,
+            None => return Vec::new(),
+        };
+        Self::imports(file)
+            .into_iter()
+            .filter_map(|import| self.file_index(&Self::import_path(path, import)))
+            .map(|index| self.files[index].0.as_path())
+            .collect()
+    }
+
+    /// Deleted files are found through the directory they were in, which can still be canonicalized.
+    fn file_index(&self, path: &Path) -> Option<usize> {
+        let path = path.canonicalize().ok().or_else(|| {
+            let dir = match path.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => dir,
+                _ => Path::new("."),
+            };
+            Some(dir.canonicalize().ok()?.join(path.file_name()?))
+        })?;
+        self.canonical_paths.iter().position(|p| *p == path)
+    }
+
+    /// The loaded files that import `path` directly, which can already be deleted as long as it is loaded.
+    pub fn reverse_dependencies(&self, path: &Path) -> Vec<&Path> {
+        let target = match self.file_index(path) {
+            Some(index) => self.files[index].0.as_path(),
+            None => return Vec::new(),
+        };
+        self.files
+            .iter()
+            .map(|(importer, _)| importer.as_path())
+            .filter(|importer| self.dependencies(importer).contains(&target))
+            .collect()
+    }
+
+    fn imports(file: &FidlFileRs) -> Vec<&Path> {
+        let namespaces = file.namespaces.iter().map(|n| n.from.as_path());
+        let models = file.import_models.iter().map(|m| m.file_path.as_path());
+        namespaces.chain(models).collect()
+    }
+
+    fn import_path(importer: &Path, import: &Path) -> PathBuf {
+        match importer.parent() {
+            Some(dir) => dir.join(import),
+            None => import.to_path_buf(),
+        }
+    }
+
+    fn add_symbols(&mut self, path: &Path, file: &FidlFileRs) {
+        let package = FidlProject::package_name(file);
+        let symbol = |container: &str,
+                      name: &str,
+                      kind: SymbolKind,
+                      span: Span,
+                      annotations: &[Annotation]| Symbol {
+            file: path.to_path_buf(),
+            package: package.clone(),
+            container: container.to_string(),
+            name: name.to_string(),
+            kind,
+            span,
+            annotations: annotations.to_vec(),
+        };
+        for type_collection in &file.type_collections {
+            for (name, span, annotations) in Self::declared_types(type_collection) {
+                self.symbols.insert(symbol(
+                    &type_collection.name,
+                    name,
+                    SymbolKind::Type,
+                    span,
+                    annotations,
+                ));
+            }
+        }
+        for interface in &file.interfaces {
+            self.symbols.insert(symbol(
+                "",
+                &interface.name,
+                SymbolKind::Interface,
+                interface.span(),
+                &interface.annotations,
+            ));
+            for (name, span, annotations) in Self::declared_types_of_interface(interface) {
+                self.symbols.insert(symbol(
+                    &interface.name,
+                    name,
+                    SymbolKind::Type,
+                    span,
+                    annotations,
+                ));
+            }
+        }
+    }
+
+    pub(crate) fn declared_types(
+        type_collection: &TypeCollection,
+    ) -> impl Iterator<Item = (&str, Span, &[Annotation])> {
+        let typedefs = type_collection
+            .typedefs
+            .iter()
+            .map(|t| (t.name.as_str(), t.span(), &t.annotations[..]));
+        let structures = type_collection
+            .structures
+            .iter()
+            .map(|s| (s.name.as_str(), s.span(), &s.annotations[..]));
+        let unions = type_collection
+            .unions
+            .iter()
+            .map(|u| (u.name.as_str(), u.span(), &u.annotations[..]));
+        let enumerations = type_collection
+            .enumerations
+            .iter()
+            .map(|e| (e.name.as_str(), e.span(), &e.annotations[..]));
+        let maps = type_collection
+            .maps
+            .iter()
+            .map(|m| (m.name.as_str(), m.span(), &m.annotations[..]));
+        let arrays = type_collection
+            .arrays
+            .iter()
+            .map(|a| (a.name.as_str(), a.span(), &a.annotations[..]));
+        typedefs
+            .chain(structures)
+            .chain(unions)
+            .chain(enumerations)
+            .chain(maps)
+            .chain(arrays)
+    }
+
+    pub(crate) fn declared_types_of_interface(
+        interface: &Interface,
+    ) -> impl Iterator<Item = (&str, Span, &[Annotation])> {
+        let typedefs = interface
+            .typedefs
+            .iter()
+            .map(|t| (t.name.as_str(), t.span(), &t.annotations[..]));
+        let structures = interface
+            .structures
+            .iter()
+            .map(|s| (s.name.as_str(), s.span(), &s.annotations[..]));
+        let unions = interface
+            .unions
+            .iter()
+            .map(|u| (u.name.as_str(), u.span(), &u.annotations[..]));
+        let enumerations = interface
+            .enumerations
+            .iter()
+            .map(|e| (e.name.as_str(), e.span(), &e.annotations[..]));
+        let maps = interface
+            .maps
+            .iter()
+            .map(|m| (m.name.as_str(), m.span(), &m.annotations[..]));
+        let arrays = interface
+            .arrays
+            .iter()
+            .map(|a| (a.name.as_str(), a.span(), &a.annotations[..]));
+        // Inline method errors declare an enumeration named after the method.
+        let errors = interface.methods.iter().filter_map(|m| match &m.errors {
+            Some(MethodError::Inline(e)) => Some((e.name.as_str(), e.span(), &e.annotations[..])),
+            _ => None,
+        });
+        typedefs
+            .chain(structures)
+            .chain(unions)
+            .chain(enumerations)
+            .chain(maps)
+            .chain(arrays)
+            .chain(errors)
+    }
+
+    pub(crate) fn references(file: &FidlFileRs) -> Vec<Reference<'_>> {
+        let mut references: Vec<Reference> = Vec::new();
+        for type_collection in &file.type_collections {
+            let scope = vec![type_collection.name.as_str()];
+            Self::container_references(
+                &mut references,
+                &scope,
+                &type_collection.typedefs,
+                &type_collection.structures,
+                &type_collection.unions,
+                &type_collection.enumerations,
+                &type_collection.maps,
+                &type_collection.arrays,
+                &type_collection.constants,
+            );
+        }
+        for interface in &file.interfaces {
+            // Members of base interfaces are visible in derived ones.
+            let mut scope = vec![interface.name.as_str()];
+            scope.extend(
+                file.interface_ancestors(interface)
+                    .into_iter()
+                    .map(|i| i.name.as_str()),
+            );
+            if let Some(extends) = &interface.extends {
+                references.push(Reference {
+                    name: &extends.name,
+                    span: interface.span(),
+                    kind: SymbolKind::Interface,
+                    scope: Vec::new(),
+                });
+            }
+            Self::container_references(
+                &mut references,
+                &scope,
+                &interface.typedefs,
+                &interface.structures,
+                &interface.unions,
+                &interface.enumerations,
+                &interface.maps,
+                &interface.arrays,
+                &interface.constants,
+            );
+            for attribute in &interface.attributes {
+                references.push(Reference {
+                    name: attribute.type_n.trim_end_matches("[]"),
+                    span: attribute.span(),
+                    kind: SymbolKind::Type,
+                    scope: scope.clone(),
+                });
+            }
+            for method in &interface.methods {
+                for parameter in method
+                    .input_parameters
+                    .iter()
+                    .chain(&method.output_parameters)
+                {
+                    references.push(Self::variable_reference(parameter, &scope));
+                }
+                if let Some(MethodError::Reference(name)) = &method.errors {
+                    references.push(Reference {
+                        name,
+                        span: method.span(),
+                        kind: SymbolKind::Type,
+                        scope: scope.clone(),
+                    });
+                }
+            }
+            for broadcast in &interface.broadcasts {
+                for parameter in &broadcast.output_parameters {
+                    references.push(Self::variable_reference(parameter, &scope));
+                }
+            }
+        }
+        references.retain(|r| !is_built_in_type(r.name));
+        references
+    }
+
+    fn variable_reference<'a>(
+        variable: &'a VariableDeclaration,
+        scope: &[&'a str],
+    ) -> Reference<'a> {
+        Reference {
+            name: &variable.type_n,
+            span: variable.span(),
+            kind: SymbolKind::Type,
+            scope: scope.to_vec(),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn container_references<'a>(
+        references: &mut Vec<Reference<'a>>,
+        scope: &[&'a str],
+        typedefs: &'a [crate::TypeDef],
+        structures: &'a [crate::Structure],
+        unions: &'a [crate::Union],
+        enumerations: &'a [crate::Enumeration],
+        maps: &'a [crate::Map],
+        arrays: &'a [crate::ArrayType],
+        constants: &'a [crate::Constant],
+    ) {
+        let mut push = |name: &'a str, span: Span| {
+            references.push(Reference {
+                name,
+                span,
+                kind: SymbolKind::Type,
+                scope: scope.to_vec(),
+            })
+        };
+        for typedef in typedefs {
+            push(&typedef.type_n, typedef.span());
+        }
+        for structure in structures {
+            if let Some(extends) = &structure.extends {
+                push(&extends.name, structure.span());
+            }
+            for field in &structure.contents {
+                push(&field.type_n, field.span());
+            }
+        }
+        for union in unions {
+            if let Some(extends) = &union.extends {
+                push(&extends.name, union.span());
+            }
+            for field in &union.contents {
+                push(&field.type_n, field.span());
+            }
+        }
+        for enumeration in enumerations {
+            if let Some(extends) = &enumeration.extends {
+                push(&extends.name, enumeration.span());
+            }
+        }
+        for map in maps {
+            push(&map.key_type, map.span());
+            push(&map.value_type, map.span());
+        }
+        for array in arrays {
+            push(&array.type_n, array.span());
+        }
+        for constant in constants {
+            push(&constant.type_n, constant.span());
+        }
+    }
+
+    fn visible(&self, index: usize) -> Visible<'_> {
+        let (path, file) = &self.files[index];
+        let mut files: Vec<&Path> = vec![path.as_path()];
+        files.extend(self.dependencies(path));
+        let namespaces = file
+            .namespaces
+            .iter()
+            .filter_map(|namespace| {
+                let import = self.file_index(&Self::import_path(path, &namespace.from))?;
+                Some((namespace.import.join("."), self.files[import].0.as_path()))
+            })
+            .collect();
+        Visible { files, namespaces }
+    }
+
+    fn resolve(&self, index: usize, visible: &Visible, reference: &Reference) -> Vec<&Symbol> {
+        let (path, file) = &self.files[index];
+        let (qualifier, name) = match reference.name.rsplit_once('.') {
+            Some((qualifier, name)) => (Some(qualifier), name),
+            None => (None, reference.name),
+        };
+        let candidates: Vec<&Symbol> = self
+            .symbols
+            .named(name)
+            .into_iter()
+            .filter(|s| s.kind == reference.kind && visible.files.contains(&s.file.as_path()))
+            .collect();
+        if let Some(qualifier) = qualifier {
+            return candidates
+                .into_iter()
+                .filter(|s| s.qualified_by(qualifier))
+                .collect();
+        }
+
+        let local: Vec<&Symbol> = candidates
+            .iter()
+            .copied()
+            .filter(|s| s.file == *path)
+            .collect();
+        for container in &reference.scope {
+            let in_scope: Vec<&Symbol> = local
+                .iter()
+                .copied()
+                .filter(|s| s.container == *container)
+                .collect();
+            if !in_scope.is_empty() {
+                return in_scope;
+            }
+        }
+        // Types of any type collection in the same file, and interfaces of the same file.
+        let type_collections: Vec<&str> = file
+            .type_collections
+            .iter()
+            .map(|tc| tc.name.as_str())
+            .collect();
+        let in_file: Vec<&Symbol> = local
+            .into_iter()
+            .filter(|s| match s.kind {
+                SymbolKind::Type => type_collections.contains(&s.container.as_str()),
+                SymbolKind::Interface => true,
+            })
+            .collect();
+        if !in_file.is_empty() {
+            return in_file;
+        }
+        // `import some.namespace.* from "file"` makes the namespace's names usable unqualified.
+        let mut imported_symbols: Vec<&Symbol> = Vec::new();
+        for (namespace, import_file) in &visible.namespaces {
+            for symbol in &candidates {
+                let in_namespace = symbol.package == *namespace || symbol.qualified_by(namespace);
+                let already_found = imported_symbols.iter().any(|s| std::ptr::eq(*s, *symbol));
+                if symbol.file == *import_file && in_namespace && !already_found {
+                    imported_symbols.push(symbol);
+                }
+            }
+        }
+        imported_symbols
+    }
+}
+
+/// The possibly qualified name around byte `offset`, i.e `Types.Point` with the offset anywhere in it.
+pub fn name_at(source: &str, offset: u32) -> Option<&str> {
+    let mut offset = (offset as usize).min(source.len());
+    // An offset inside a multi-byte character is moved back to its first byte.
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let is_name = |c: char| c.is_alphanumeric() || c == '_' || c == '.';
+    let start = source[..offset]
+        .rfind(|c: char| !is_name(c))
+        .map_or(0, |index| index + 1);
+    let end = source[offset..]
+        .find(|c: char| !is_name(c))
+        .map_or(source.len(), |index| offset + index);
+    let name = source[start..end].trim_matches('.');
+    (!name.is_empty()).then_some(name)
+}
+
+pub(crate) fn is_built_in_type(name: &str) -> bool {
+    matches!(
+        name,
+        "UInt8"
+            | "Int8"
+            | "UInt16"
+            | "Int16"
+            | "UInt32"
+            | "Int32"
+            | "UInt64"
+            | "Int64"
+            | "Integer"
+            | "Boolean"
+            | "Float"
+            | "Double"
+            | "String"
+            | "ByteBuffer"
+    )
+}
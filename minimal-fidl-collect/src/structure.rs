@@ -3,7 +3,9 @@ use std::{
     str::FromStr,
 };
 
-use crate::{annotation::{annotation_constructor, Annotation}, fidl_file::FileError, VariableDeclaration};
+use crate::diagnostic::Span;
+use crate::resolver::SymbolKind;
+use crate::{annotation::{annotation_constructor, Annotation}, fidl_file::{type_declarations, Declarations, Extendable, FidlFileRs, FileError}, TypeRef, VariableDeclaration};
 use minimal_fidl_parser::{BasicPublisher, Key, Node, Rules};
 #[derive(Debug, Clone)]
 pub struct Structure {
@@ -11,6 +13,8 @@ pub struct Structure {
     end_position: u32,
     pub annotations: Vec<Annotation>,
     pub name: String,
+    pub extends: Option<TypeRef>,
+    pub polymorphic: bool,
     pub contents: Vec<VariableDeclaration>,
 }
 impl Structure {
@@ -20,6 +24,8 @@ impl Structure {
             "Uninitialized value: name in Structure::new".to_string(),
         ));
         let mut annotations: Vec<Annotation> = Vec::new();
+        let mut extends: Option<TypeRef> = None;
+        let mut polymorphic: bool = false;
 
         let mut contents: Vec<VariableDeclaration> = Vec::new();
         for child in node.get_children() {
@@ -35,6 +41,12 @@ impl Structure {
                 Rules::type_dec => {
                     name = Ok(child.get_string(source));
                 }
+                Rules::extends => {
+                    extends = Some(TypeRef::new_extends(source, publisher, child)?);
+                }
+                Rules::polymorphic => {
+                    polymorphic = true;
+                }
                 Rules::variable_declaration => {
                     let var_dec = VariableDeclaration::new(source, publisher, child)?;
                     var_dec.push_if_not_exists_else_err(&mut contents)?;
@@ -50,6 +62,8 @@ impl Structure {
         }
         Ok(Self {
             name: name?,
+            extends,
+            polymorphic,
             contents,
             annotations,
             start_position: node.start_position,
@@ -57,16 +71,57 @@ impl Structure {
         })
     }
 
+    /// The discriminator written before a polymorphic struct so the receiver knows which
+    /// struct of the family it got. This is the 32 bit FNV-1a hash of the fully qualified struct
+    /// name so every generator agrees on it without sharing any state.
+    /// `scope` is the qualified name of the interface or type collection, i.e `org.example.MyTypes`.
+    pub fn type_tag(&self, scope: &str) -> u32 {
+        let mut hash: u32 = 0x811c9dc5;
+        let name = format!("{}.{}", scope, self.name);
+        for byte in name.bytes() {
+            hash ^= byte as u32;
+            hash = hash.wrapping_mul(0x01000193);
+        }
+        hash
+    }
+
+    pub fn span(&self) -> Span {
+        Span::new(self.start_position, self.end_position)
+    }
+
     pub fn push_if_not_exists_else_err(
         self,
         structures: &mut Vec<Structure>,
     ) -> Result<(), FileError> {
         for s in &mut *structures {
             if s.name == self.name {
-                return Err(FileError::StructAlreadyExists(s.clone(), self.clone()));
+                return Err(FileError::StructAlreadyExists(
+                    Box::new(s.clone()),
+                    Box::new(self.clone()),
+                ));
             }
         }
         structures.push(self);
         Ok(())
     }
 }
+
+impl Extendable for Structure {
+    const KIND: SymbolKind = SymbolKind::Type;
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn extends(&self) -> Option<&TypeRef> {
+        self.extends.as_ref()
+    }
+
+    fn span(&self) -> Span {
+        Structure::span(self)
+    }
+
+    fn declared_in(file: &FidlFileRs) -> Vec<Declarations<'_, Self>> {
+        type_declarations(file, |tc| &tc.structures, |i| &i.structures)
+    }
+}
@@ -3,8 +3,9 @@ use std::{
     str::FromStr,
 };
 
+use crate::diagnostic::Span;
 use crate::{
-    annotation::{annotation_constructor, Annotation}, attribute::{self, Attribute}, enumeration::{self, Enumeration}, fidl_file::FileError, method::Method, structure::Structure, type_def::TypeDef, Version
+    annotation::{annotation_constructor, Annotation}, array_type::ArrayType, attribute::{self, Attribute}, constant::Constant, enumeration::{self, Enumeration}, fidl_file::FileError, method::Method, structure::Structure, type_def::TypeDef, union::Union, map::Map, Version
 };
 use minimal_fidl_parser::{BasicPublisher, Key, Node, Rules};
 #[derive(Debug, Clone)]
@@ -15,7 +16,11 @@ pub struct TypeCollection {
     pub name: String,
     pub version: Option<Version>,
     pub typedefs: Vec<TypeDef>,
+    pub constants: Vec<Constant>,
     pub structures: Vec<Structure>,
+    pub unions: Vec<Union>,
+    pub maps: Vec<Map>,
+    pub arrays: Vec<ArrayType>,
     pub enumerations: Vec<Enumeration>,
 }
 impl TypeCollection {
@@ -24,7 +29,12 @@ impl TypeCollection {
         let mut name: String = "".to_string(); // Cos the type collection name can be seemingly empty.
         let mut version: Option<Version> = None;
         let mut structures: Vec<Structure> = Vec::new();
+        let mut unions: Vec<Union> = Vec::new();
+        let mut maps: Vec<Map> = Vec::new();
+        let mut arrays: Vec<ArrayType> = Vec::new();
         let mut typedefs: Vec<TypeDef> = Vec::new();
+        let mut constants: Vec<Constant> = Vec::new();
+        let mut constant_nodes: Vec<&Node> = Vec::new();
         let mut enumerations: Vec<Enumeration> = Vec::new();
         let mut annotations: Vec<Annotation> = Vec::new();
 
@@ -43,10 +53,23 @@ impl TypeCollection {
                     let structure = Structure::new(source, publisher, child)?;
                     structure.push_if_not_exists_else_err(&mut structures)?;
                 }
+                Rules::union => {
+                    let union = Union::new(source, publisher, child)?;
+                    union.push_if_not_exists_else_err(&mut unions)?;
+                }
+                Rules::map => {
+                    let map = Map::new(source, publisher, child)?;
+                    map.push_if_not_exists_else_err(&mut maps)?;
+                }
+                Rules::array_type => {
+                    let array = ArrayType::new(source, publisher, child)?;
+                    array.push_if_not_exists_else_err(&mut arrays)?;
+                }
                 Rules::typedef => {
                     let typedef = TypeDef::new(source, publisher, child)?;
                     typedef.push_if_not_exists_else_err(&mut typedefs)?;
                 }
+                Rules::constant => constant_nodes.push(child),
                 Rules::enumeration => {
                     let enumeration = Enumeration::new(source, publisher, child)?;
                     enumeration.push_if_not_exists_else_err(&mut enumerations)?;
@@ -58,7 +81,8 @@ impl TypeCollection {
                 Rules::comment
                 | Rules::multiline_comment
                 | Rules::open_bracket
-                | Rules::close_bracket => {}
+                | Rules::close_bracket
+                | Rules::skipped => {}
                 rule => {
                     return Err(FileError::UnexpectedNode(
                         rule,
@@ -67,15 +91,24 @@ impl TypeCollection {
                 }
             }
         }
+        // Constants are evaluated last so a struct initializer can use any struct declared here.
+        for child in constant_nodes {
+            let constant = Constant::new(source, publisher, child, &constants, &structures)?;
+            constant.push_if_not_exists_else_err(&mut constants)?;
+        }
         if name.len() == 0 {
-            return Err(FileError::TypeCollectionRequiresAName(source.to_string()));
+            return Err(FileError::TypeCollectionRequiresAName(Span::of(node)));
         }
         Ok(Self {
             annotations,
             name,
             version,
             structures,
+            unions,
+            maps,
+            arrays,
             typedefs,
+            constants,
             enumerations,
             start_position: node.start_position,
             end_position: node.end_position,
@@ -86,6 +119,10 @@ impl TypeCollection {
         node.get_string(source)
     }
 
+    pub fn span(&self) -> Span {
+        Span::new(self.start_position, self.end_position)
+    }
+
     pub fn push_if_not_exists_else_err(
         self,
         type_collections: &mut Vec<TypeCollection>,
@@ -93,8 +130,8 @@ impl TypeCollection {
         for s in &mut *type_collections {
             if s.name == self.name {
                 return Err(FileError::TypeCollectionAlreadyExists(
-                    s.clone(),
-                    self.clone(),
+                    Box::new(s.clone()),
+                    Box::new(self.clone()),
                 ));
             }
         }
@@ -3,7 +3,8 @@ use std::{
     str::FromStr,
 };
 
-use crate::{annotation::{annotation_constructor, Annotation}, fidl_file::FileError, type_ref::TypeRef, VariableDeclaration};
+use crate::diagnostic::Span;
+use crate::{annotation::{annotation_constructor, Annotation}, fidl_file::FileError, type_ref::{IntegerRange, TypeRef}, VariableDeclaration};
 use minimal_fidl_parser::{BasicPublisher, Key, Node, Rules};
 #[derive(Debug, Clone)]
 pub struct TypeDef {
@@ -13,6 +14,7 @@ pub struct TypeDef {
     pub name: String,
     pub type_n: String,
     pub is_array: bool,
+    pub range: Option<IntegerRange>,
 }
 impl TypeDef {
     pub fn new(source: &str, publisher: &BasicPublisher, node: &Node) -> Result<Self, FileError> {
@@ -26,6 +28,7 @@ impl TypeDef {
         let mut annotations: Vec<Annotation> = Vec::new();
 
         let mut is_array = false;
+        let mut range: Option<IntegerRange> = None;
         for child in node.get_children() {
             let child = publisher.get_node(*child);
             match child.rule {
@@ -34,13 +37,13 @@ impl TypeDef {
                     annotations = annotation_constructor(source, publisher, child)?;
                 }
                 Rules::type_dec => {
-                    // TODO!
-                    // println!("Need to actually do this stuff. Types need to be checked for duplicates and whether they exist if using external import after reading file.");
+                    // Whether the aliased type exists is checked by `FidlFileRs::undefined_types`.
                     name = Ok(child.get_string(source))
                 }
                 Rules::type_ref => {
                     let res = TypeRef::new(source, publisher, child)?;
                     is_array = res.is_array;
+                    range = res.range;
                     type_n = Ok(res.name);
                 }
                 rule => {
@@ -52,16 +55,24 @@ impl TypeDef {
             name: name?,
             type_n: type_n?,
             is_array: is_array,
+            range,
             annotations,
             start_position: node.start_position,
             end_position: node.end_position,
         })
     }
 
+    pub fn span(&self) -> Span {
+        Span::new(self.start_position, self.end_position)
+    }
+
     pub fn push_if_not_exists_else_err(self, typedefs: &mut Vec<TypeDef>) -> Result<(), FileError> {
         for t in &mut *typedefs {
             if t.name == self.name {
-                return Err(FileError::TypeDefAlreadyExists(t.clone(), self.clone()));
+                return Err(FileError::TypeDefAlreadyExists(
+                    Box::new(t.clone()),
+                    Box::new(self.clone()),
+                ));
             }
         }
         typedefs.push(self);
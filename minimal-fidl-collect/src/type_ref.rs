@@ -3,24 +3,120 @@ use std::{
     str::FromStr,
 };
 
+use crate::diagnostic::Span;
 use crate::fidl_file::FileError;
 use minimal_fidl_parser::{BasicPublisher, Key, Node, Rules};
+/// The bounds of an `Integer(min, max)` type, both inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntegerRange {
+    pub min: i64,
+    pub max: i64,
+}
+impl IntegerRange {
+    fn new(source: &str, publisher: &BasicPublisher, node: &Node) -> Result<Self, FileError> {
+        debug_assert_eq!(node.rule, Rules::integer_range);
+        let mut bounds: Vec<i64> = Vec::new();
+        for child in node.get_children() {
+            let child = publisher.get_node(*child);
+            // The grammar allows whitespace between a sign and its digits.
+            let text: String = child
+                .get_string(source)
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .collect();
+            let bound = match child.rule {
+                Rules::min_int => Ok(i64::MIN),
+                Rules::max_int => Ok(i64::MAX),
+                Rules::hex => i64::from_str_radix(&text[2..], 16),
+                Rules::binary => i64::from_str_radix(&text[2..], 2),
+                Rules::integer => text.parse::<i64>(),
+                rule => {
+                    return Err(FileError::UnexpectedNode(
+                        rule,
+                        "IntegerRange::new".to_string(),
+                    ));
+                }
+            };
+            let bound = bound.map_err(|_| {
+                FileError::InvalidIntegerRange(
+                    node.get_string(source),
+                    format!("'{}' is not a 64-bit integer", text),
+                    Span::of(child),
+                )
+            })?;
+            bounds.push(bound);
+        }
+        let (min, max) = match bounds[..] {
+            [min, max] => (min, max),
+            _ => {
+                return Err(FileError::InternalLogicError(
+                    "An integer range should have two bounds".to_string(),
+                ))
+            }
+        };
+        if min > max {
+            return Err(FileError::InvalidIntegerRange(
+                node.get_string(source),
+                format!("the minimum {} is greater than the maximum {}", min, max),
+                Span::of(node),
+            ));
+        }
+        Ok(Self { min, max })
+    }
+
+    pub fn contains(&self, value: i128) -> bool {
+        self.min as i128 <= value && value <= self.max as i128
+    }
+
+    /// The smallest fixed width integer type that holds every value in the range.
+    pub fn wire_type(&self) -> &'static str {
+        let (min, max) = (self.min as i128, self.max as i128);
+        let fits = |lower: i128, upper: i128| lower <= min && max <= upper;
+        if min >= 0 {
+            if fits(0, u8::MAX as i128) {
+                "UInt8"
+            } else if fits(0, u16::MAX as i128) {
+                "UInt16"
+            } else if fits(0, u32::MAX as i128) {
+                "UInt32"
+            } else {
+                "UInt64"
+            }
+        } else if fits(i8::MIN as i128, i8::MAX as i128) {
+            "Int8"
+        } else if fits(i16::MIN as i128, i16::MAX as i128) {
+            "Int16"
+        } else if fits(i32::MIN as i128, i32::MAX as i128) {
+            "Int32"
+        } else {
+            "Int64"
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TypeRef {
     pub name: String,
     pub is_array: bool,
+    pub range: Option<IntegerRange>,
 }
 impl TypeRef {
     pub fn new(source: &str, publisher: &BasicPublisher, node: &Node) -> Result<Self, FileError> {
         debug_assert_eq!(node.rule, Rules::type_ref);
         let mut is_array: bool = false;
-        let mut arr_start_position = 0;
+        let mut range: Option<IntegerRange> = None;
+        // The name ends where the range or array suffix starts.
+        let mut name_end_position = node.end_position;
         for child in node.get_children() {
             let child = publisher.get_node(*child);
             match child.rule {
                 Rules::array => {
                     is_array = true;
-                    arr_start_position = child.start_position
+                    name_end_position = name_end_position.min(child.start_position);
+                }
+                Rules::integer_range => {
+                    range = Some(IntegerRange::new(source, publisher, child)?);
+                    name_end_position = child.start_position;
                 }
                 rule => {
                     return Err(FileError::UnexpectedNode(
@@ -30,18 +126,47 @@ impl TypeRef {
                 }
             }
         }
-        if !is_array {
-            return Ok(Self {
-                name: node.get_string(source),
-                is_array: false,
-            });
-        } else {
-            return Ok(Self {
-                name: (node.get_string(source)
-                    [0..(arr_start_position - node.start_position) as usize])
-                    .to_string(),
-                is_array: true,
-            });
+        let name = (node.get_string(source)
+            [0..(name_end_position - node.start_position) as usize])
+            .to_string();
+        if range.is_some() && name != "Integer" {
+            return Err(FileError::InvalidIntegerRange(
+                node.get_string(source),
+                "only Integer can have a range".to_string(),
+                Span::of(node),
+            ));
+        }
+        Ok(Self {
+            name,
+            is_array,
+            range,
+        })
+    }
+
+    /// Reads the base type out of an `extends <type_ref>` clause.
+    pub(crate) fn new_extends(
+        source: &str,
+        publisher: &BasicPublisher,
+        node: &Node,
+    ) -> Result<Self, FileError> {
+        debug_assert_eq!(node.rule, Rules::extends);
+        let mut extends: Result<Self, FileError> = Err(FileError::InternalLogicError(
+            "Uninitialized value: extends in TypeRef::new_extends".to_string(),
+        ));
+        for child in node.get_children() {
+            let child = publisher.get_node(*child);
+            match child.rule {
+                Rules::type_ref => {
+                    extends = Ok(Self::new(source, publisher, child)?);
+                }
+                rule => {
+                    return Err(FileError::UnexpectedNode(
+                        rule,
+                        "TypeRef::new_extends".to_string(),
+                    ));
+                }
+            }
         }
+        extends
     }
 }
@@ -0,0 +1,102 @@
+use crate::diagnostic::Span;
+use crate::resolver::SymbolKind;
+use crate::{annotation::{annotation_constructor, Annotation}, fidl_file::{type_declarations, Declarations, Extendable, FidlFileRs, FileError}, TypeRef, VariableDeclaration};
+use minimal_fidl_parser::{BasicPublisher, Node, Rules};
+#[derive(Debug, Clone)]
+pub struct Union {
+    start_position: u32,
+    end_position: u32,
+    pub annotations: Vec<Annotation>,
+    pub name: String,
+    pub extends: Option<TypeRef>,
+    pub contents: Vec<VariableDeclaration>,
+}
+impl Union {
+    pub fn new(source: &str, publisher: &BasicPublisher, node: &Node) -> Result<Self, FileError> {
+        debug_assert_eq!(node.rule, Rules::union);
+        let mut name: Result<String, FileError> = Err(FileError::InternalLogicError(
+            "Uninitialized value: name in Union::new".to_string(),
+        ));
+        let mut annotations: Vec<Annotation> = Vec::new();
+        let mut extends: Option<TypeRef> = None;
+
+        let mut contents: Vec<VariableDeclaration> = Vec::new();
+        for child in node.get_children() {
+            let child = publisher.get_node(*child);
+            match child.rule {
+                Rules::comment
+                | Rules::multiline_comment
+                | Rules::open_bracket
+                | Rules::close_bracket => {},
+                Rules::annotation_block => {
+                    annotations = annotation_constructor(source, publisher, child)?;
+                }
+                Rules::type_dec => {
+                    name = Ok(child.get_string(source));
+                }
+                Rules::extends => {
+                    extends = Some(TypeRef::new_extends(source, publisher, child)?);
+                }
+                Rules::variable_declaration => {
+                    let var_dec = VariableDeclaration::new(source, publisher, child)?;
+                    var_dec.push_if_not_exists_else_err(&mut contents)?;
+                }
+
+                rule => {
+                    return Err(FileError::UnexpectedNode(
+                        rule,
+                        "Union::new".to_string(),
+                    ));
+                }
+            }
+        }
+        Ok(Self {
+            name: name?,
+            extends,
+            contents,
+            annotations,
+            start_position: node.start_position,
+            end_position: node.end_position,
+        })
+    }
+
+    pub fn span(&self) -> Span {
+        Span::new(self.start_position, self.end_position)
+    }
+
+    pub fn push_if_not_exists_else_err(
+        self,
+        unions: &mut Vec<Union>,
+    ) -> Result<(), FileError> {
+        for s in &mut *unions {
+            if s.name == self.name {
+                return Err(FileError::UnionAlreadyExists(
+                    Box::new(s.clone()),
+                    Box::new(self.clone()),
+                ));
+            }
+        }
+        unions.push(self);
+        Ok(())
+    }
+}
+
+impl Extendable for Union {
+    const KIND: SymbolKind = SymbolKind::Type;
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn extends(&self) -> Option<&TypeRef> {
+        self.extends.as_ref()
+    }
+
+    fn span(&self) -> Span {
+        Union::span(self)
+    }
+
+    fn declared_in(file: &FidlFileRs) -> Vec<Declarations<'_, Self>> {
+        type_declarations(file, |tc| &tc.unions, |i| &i.unions)
+    }
+}
@@ -0,0 +1,136 @@
+use crate::diagnostic::Span;
+use crate::fidl_file::{FidlFileRs, FileError};
+use crate::method::MethodError;
+use crate::resolver::{is_built_in_type, Reference, Resolver, SymbolKind};
+
+/// Checks for a single file that don't need the imported files to be loaded.
+/// Use the `Resolver` to check references into imported files precisely.
+impl FidlFileRs {
+    /// Every type used in this file that is not a built-in, not declared in this file and could not
+    /// come from one of its imports.
+    pub fn undefined_types(&self) -> Vec<FileError> {
+        let mut errors: Vec<FileError> = Vec::new();
+        for reference in Resolver::references(self) {
+            if !self.is_defined(&reference) {
+                errors.push(FileError::UndefinedType(
+                    reference.name.to_string(),
+                    reference.span,
+                ));
+            }
+        }
+        errors
+    }
+
+    /// Every type declared in this file that nothing in this file uses.
+    /// Types in a type collection are often only used by other files so this is a warning at most.
+    pub fn unused_types(&self) -> Vec<FileError> {
+        let references = Resolver::references(self);
+        let is_used = |container: &str, name: &str| {
+            references.iter().any(|r| match r.name.rsplit_once('.') {
+                Some((qualifier, used)) => {
+                    used == name
+                        && (qualifier == container
+                            || qualifier.ends_with(&format!(".{}", container)))
+                }
+                None => r.name == name,
+            })
+        };
+        let mut declared: Vec<(&str, &str, Span)> = Vec::new();
+        for type_collection in &self.type_collections {
+            for (name, span, _) in Resolver::declared_types(type_collection) {
+                declared.push((&type_collection.name, name, span));
+            }
+        }
+        for interface in &self.interfaces {
+            // Inline errors are used by the method that declares them.
+            let inline_errors: Vec<&str> = interface
+                .methods
+                .iter()
+                .filter_map(|m| match &m.errors {
+                    Some(MethodError::Inline(e)) => Some(e.name.as_str()),
+                    _ => None,
+                })
+                .collect();
+            for (name, span, _) in Resolver::declared_types_of_interface(interface) {
+                if !inline_errors.contains(&name) {
+                    declared.push((&interface.name, name, span));
+                }
+            }
+        }
+        declared
+            .into_iter()
+            .filter(|(container, name, _)| !is_used(container, name))
+            .map(|(_, name, span)| FileError::UnusedType(name.to_string(), span))
+            .collect()
+    }
+
+    fn is_defined(&self, reference: &Reference) -> bool {
+        if is_built_in_type(reference.name) {
+            return true;
+        }
+        let package = match &self.package {
+            Some(package) => package.path.join("."),
+            None => String::new(),
+        };
+        let declares = |container: &str, name: &str| match reference.kind {
+            SymbolKind::Interface => self.interfaces.iter().any(|i| i.name == name),
+            SymbolKind::Type => {
+                let type_collection = self.type_collections.iter().find(|tc| tc.name == container);
+                let interface = self.interfaces.iter().find(|i| i.name == container);
+                match (type_collection, interface) {
+                    (Some(tc), _) => Resolver::declared_types(tc).any(|(n, _, _)| n == name),
+                    (_, Some(i)) => {
+                        Resolver::declared_types_of_interface(i).any(|(n, _, _)| n == name)
+                    }
+                    (None, None) => false,
+                }
+            }
+        };
+        match reference.name.rsplit_once('.') {
+            Some((qualifier, name)) => {
+                let container = match qualifier.strip_prefix(&format!("{}.", package)) {
+                    Some(container) => container,
+                    None if qualifier == package => "",
+                    None => qualifier,
+                };
+                let is_local = match reference.kind {
+                    SymbolKind::Interface => container.is_empty(),
+                    SymbolKind::Type => {
+                        self.type_collections.iter().any(|tc| tc.name == container)
+                            || self.interfaces.iter().any(|i| i.name == container)
+                    }
+                };
+                if is_local {
+                    declares(container, name)
+                } else {
+                    // An imported model can declare any package, a namespace import only the names
+                    // the resolver would find under it.
+                    !self.import_models.is_empty()
+                        || self
+                            .namespaces
+                            .iter()
+                            .any(|n| qualifies_namespace(qualifier, &n.import.join(".")))
+                }
+            }
+            None => {
+                let in_scope = reference.scope.iter().any(|c| declares(c, reference.name));
+                let in_type_collection = self
+                    .type_collections
+                    .iter()
+                    .any(|tc| declares(&tc.name, reference.name));
+                // Only a wildcard import brings unqualified names into this file.
+                let imported = self.namespaces.iter().any(|n| n.wildcard);
+                in_scope || in_type_collection || imported || declares("", reference.name)
+            }
+        }
+    }
+}
+
+/// Whether `qualifier` can name something in the file imported as `namespace`, either the namespace
+/// itself, something under it, its package or the last part of it.
+fn qualifies_namespace(qualifier: &str, namespace: &str) -> bool {
+    qualifier == namespace
+        || qualifier.starts_with(&format!("{}.", namespace))
+        || namespace.starts_with(&format!("{}.", qualifier))
+        || namespace.ends_with(&format!(".{}", qualifier))
+}
@@ -3,7 +3,8 @@ use std::{
     str::FromStr,
 };
 
-use crate::{annotation::{annotation_constructor, Annotation}, fidl_file::FileError, type_ref::TypeRef};
+use crate::diagnostic::Span;
+use crate::{annotation::{annotation_constructor, array_length, Annotation}, fidl_file::FileError, type_ref::{IntegerRange, TypeRef}};
 use minimal_fidl_parser::{BasicPublisher, Key, Node, Rules};
 #[derive(Debug, Clone)]
 pub struct VariableDeclaration {
@@ -13,6 +14,9 @@ pub struct VariableDeclaration {
     pub type_n: String,
     pub name: String,
     pub is_array: bool,
+    pub range: Option<IntegerRange>,
+    /// Set by a `@length: N` annotation on an array, otherwise the array is dynamically sized.
+    pub length: Option<u64>,
 }
 impl VariableDeclaration {
     pub fn new(source: &str, publisher: &BasicPublisher, node: &Node) -> Result<Self, FileError> {
@@ -24,6 +28,7 @@ impl VariableDeclaration {
             "Uninitialized value: name in VariableDeclaration::new".to_string(),
         ));
         let mut is_array = false;
+        let mut range: Option<IntegerRange> = None;
         let mut annotations: Vec<Annotation> = Vec::new();
 
 
@@ -37,6 +42,7 @@ impl VariableDeclaration {
                 Rules::type_ref => {
                     let t = TypeRef::new(source, publisher, child)?;
                     is_array = t.is_array;
+                    range = t.range;
                     type_n = Ok(t.name);
                 }
                 Rules::variable_name => {
@@ -50,16 +56,27 @@ impl VariableDeclaration {
                 }
             }
         }
+        let name = name?;
+        let length = array_length(&annotations, &name, Span::of(node))?;
+        if length.is_some() && !is_array {
+            return Err(FileError::LengthWithoutArray(name, Span::of(node)));
+        }
         Ok(Self {
-            name: name?,
+            name,
             type_n: type_n?,
             annotations,
             is_array,
+            range,
+            length,
             start_position: node.start_position,
             end_position: node.end_position,
         })
     }
 
+    pub fn span(&self) -> Span {
+        Span::new(self.start_position, self.end_position)
+    }
+
     pub fn push_if_not_exists_else_err(
         self,
         var_decs: &mut Vec<VariableDeclaration>,
@@ -76,7 +93,7 @@ impl VariableDeclaration {
             var_decs.push(self);
             Ok(())
         } else {
-            Err(FileError::FieldAlreadyExists(self.name))
+            Err(FileError::FieldAlreadyExists(self.name.clone(), self.span()))
         }
     }
 }
@@ -3,6 +3,7 @@ use std::{
     str::FromStr,
 };
 
+use crate::diagnostic::Span;
 use crate::fidl_file::FileError;
 use minimal_fidl_parser::{BasicPublisher, Key, Node, Rules};
 #[derive(Debug, Clone)]
@@ -109,6 +110,10 @@ impl Version {
         Err(FileError::CouldNotConvertToInteger(input))
     }
 
+    pub fn span(&self) -> Span {
+        Span::new(self.start_position, self.end_position)
+    }
+
     pub fn push_if_not_exists_else_err(
         self,
         version: &mut Option<Version>,
@@ -122,7 +127,7 @@ impl Version {
                 *version = Some(self);
                 Ok(())
             }
-            Some(version) => Err(FileError::VersionAlreadyExists(version.clone())),
+            Some(version) => Err(FileError::VersionAlreadyExists(Box::new(version.clone()))),
         }
     }
 }
@@ -267,6 +267,22 @@ impl<'a> Formatter<'a> {
                         ret_vec.push(line);
                     }
                 }
+                Rules::constant => {
+                    match type_collection_name {
+                        Some(..) => {}
+                        None => {
+                            let constant = "typeCollection {\n".to_string();
+                            let mut constant = IndentedString::new(0, constant.to_string());
+                            type_collection_name = Some("No Name Set".to_string());
+                            constant.set_rule(Rules::type_collection);
+                            ret_vec.push(constant);
+                        }
+                    }
+                    for mut line in self.constant(child) {
+                        line.indent();
+                        ret_vec.push(line);
+                    }
+                }
                 Rules::structure => {
                     match type_collection_name {
                         Some(..) => {}
@@ -283,6 +299,54 @@ impl<'a> Formatter<'a> {
                     }
                     ret_vec.push(IndentedString::new(0, "".to_string()))
                 }
+                Rules::union => {
+                    match type_collection_name {
+                        Some(..) => {}
+                        None => {
+                            let union = "typeCollection {\n".to_string();
+                            let union = IndentedString::new(0, union.to_string());
+                            type_collection_name = Some("No Name Set".to_string());
+                            ret_vec.push(union);
+                        }
+                    }
+                    for mut line in self.union(child) {
+                        line.indent();
+                        ret_vec.push(line);
+                    }
+                    ret_vec.push(IndentedString::new(0, "".to_string()))
+                }
+                Rules::array_type => {
+                    match type_collection_name {
+                        Some(..) => {}
+                        None => {
+                            let array = "typeCollection {\n".to_string();
+                            let mut array = IndentedString::new(0, array.to_string());
+                            type_collection_name = Some("No Name Set".to_string());
+                            array.set_rule(Rules::type_collection);
+                            ret_vec.push(array);
+                        }
+                    }
+                    for mut line in self.array_type(child) {
+                        line.indent();
+                        ret_vec.push(line);
+                    }
+                }
+                Rules::map => {
+                    match type_collection_name {
+                        Some(..) => {}
+                        None => {
+                            let map = "typeCollection {\n".to_string();
+                            let map = IndentedString::new(0, map.to_string());
+                            type_collection_name = Some("No Name Set".to_string());
+                            ret_vec.push(map);
+                        }
+                    }
+                    for mut line in self.map(child) {
+                        line.indent();
+                        ret_vec.push(line);
+                    }
+                    ret_vec.push(IndentedString::new(0, "".to_string()))
+                }
                 Rules::version => {
                     let version = self.version(child);
                     for mut line in version {
@@ -324,7 +388,8 @@ impl<'a> Formatter<'a> {
 
     fn interface(&self, node: &Node) -> Vec<IndentedString> {
         debug_assert!(node.rule == Rules::interface);
-        let mut interface_name: Option<String>;
+        let mut interface_name: String = "".to_string();
+        let mut interface_name_index: usize = 0;
         // let mut version: Option<Vec<String>> = None;
         // let mut methods: Vec<Vec<String>> = Vec::new();
         // let mut attributes: Vec<String> = Vec::new();
@@ -341,15 +406,20 @@ impl<'a> Formatter<'a> {
                     self.after_bracket_helper(&mut ret_vec);
                 }
                 Rules::variable_name => {
-                    interface_name = Some(self.variable_name(child));
-                    let interface = format!(
-                        "interface {} {{\n",
-                        interface_name.expect("Interface Name should always exist")
-                    );
+                    interface_name = self.variable_name(child);
+                    let interface = format!("interface {} {{\n", interface_name);
                     let mut interface = IndentedString::new(0, interface.to_string());
                     interface.set_rule(Rules::interface);
+                    interface_name_index = ret_vec.len();
                     ret_vec.push(interface);
                 }
+                Rules::extends => {
+                    let extends = self.extends(child);
+                    let interface = format!("interface {} {} {{\n", interface_name, extends);
+                    let mut interface = IndentedString::new(0, interface);
+                    interface.set_rule(Rules::interface);
+                    ret_vec[interface_name_index] = interface;
+                }
                 Rules::version => {
                     for mut line in self.version(child) {
                         line.indent();
@@ -364,6 +434,12 @@ impl<'a> Formatter<'a> {
                     }
                     // ret_vec.push(IndentedString::new(0, "".to_string()))
                 }
+                Rules::constant => {
+                    for mut line in self.constant(child) {
+                        line.indent();
+                        ret_vec.push(line);
+                    }
+                }
                 Rules::method => {
                     for mut line in self.method(child) {
                         line.indent();
@@ -371,6 +447,13 @@ impl<'a> Formatter<'a> {
                     }
                     ret_vec.push(IndentedString::new(0, "".to_string()))
                 }
+                Rules::broadcast => {
+                    for mut line in self.broadcast(child) {
+                        line.indent();
+                        ret_vec.push(line);
+                    }
+                    ret_vec.push(IndentedString::new(0, "".to_string()))
+                }
                 Rules::attribute => {
                     for mut line in self.attribute(child) {
                         line.indent();
@@ -385,6 +468,26 @@ impl<'a> Formatter<'a> {
                     }
                     ret_vec.push(IndentedString::new(0, "".to_string()))
                 }
+                Rules::union => {
+                    for mut line in self.union(child) {
+                        line.indent();
+                        ret_vec.push(line);
+                    }
+                    ret_vec.push(IndentedString::new(0, "".to_string()))
+                }
+                Rules::array_type => {
+                    for mut line in self.array_type(child) {
+                        line.indent();
+                        ret_vec.push(line);
+                    }
+                }
+                Rules::map => {
+                    for mut line in self.map(child) {
+                        line.indent();
+                        ret_vec.push(line);
+                    }
+                    ret_vec.push(IndentedString::new(0, "".to_string()))
+                }
                 Rules::enumeration => {
                     for mut line in self.enumeration(child) {
                         line.indent();
@@ -526,7 +629,8 @@ impl<'a> Formatter<'a> {
     fn enumeration(&self, node: &Node) -> Vec<IndentedString> {
         debug_assert!(node.rule == Rules::enumeration);
         let mut ret_vec: Vec<IndentedString> = Vec::new();
-        let mut var_name: String;
+        let mut var_name: String = "".to_string();
+        let mut var_name_index: usize = 0;
         let mut open_bracket: bool = false;
         let mut close_bracket: bool = false;
         for child in node.get_children() {
@@ -550,8 +654,14 @@ impl<'a> Formatter<'a> {
                 }
                 Rules::type_dec => {
                     var_name = self.type_dec(child);
+                    var_name_index = ret_vec.len();
                     ret_vec.push(IndentedString::new(0, format!("enumeration {var_name} {{")));
                 }
+                Rules::extends => {
+                    let extends = self.extends(child);
+                    ret_vec[var_name_index] =
+                        IndentedString::new(0, format!("enumeration {var_name} {extends} {{"));
+                }
                 Rules::enum_value => {
                     for mut line in self.enum_value(child) {
                         line.indent();
@@ -659,12 +769,147 @@ impl<'a> Formatter<'a> {
         ret_vec
     }
 
+    fn array_type(&self, node: &Node) -> Vec<IndentedString> {
+        debug_assert!(node.rule == Rules::array_type);
+        let mut type_dec = "".to_string();
+        let mut ret_vec: Vec<IndentedString> = Vec::new();
+        let mut type_ref_happened: bool = false;
+        for child in node.get_children() {
+            let child = self.publisher.get_node(*child);
+            match child.rule {
+                Rules::annotation_block => {
+                    for line in self.annotation_block(child) {
+                        ret_vec.push(line);
+                    }
+                }
+                Rules::multiline_comment => {
+                    let comment = self.multiline_comment(child);
+                    for line in comment {
+                        ret_vec.push(line);
+                    }
+                }
+                Rules::comment => {
+                    if type_ref_happened {
+                        let mut last_element = ret_vec.pop().unwrap();
+                        let comment = self.comment(child, true);
+                        last_element += comment;
+                        ret_vec.push(last_element);
+                    } else {
+                        ret_vec.push(self.comment(child, false));
+                    }
+                }
+                Rules::type_dec => type_dec = self.type_dec(child),
+                Rules::type_ref => {
+                    type_ref_happened = true;
+                    let type_ref = self.type_ref(child);
+                    let result = format!("array {} of {}", type_dec, type_ref);
+                    let result = IndentedString::new(0, result);
+                    ret_vec.push(result);
+                }
+                e => {
+                    panic!("Rule: {:?} should not be the array types child.", e)
+                }
+            }
+        }
+        ret_vec
+    }
+
+    fn constant(&self, node: &Node) -> Vec<IndentedString> {
+        debug_assert!(node.rule == Rules::constant);
+        let mut type_ref = "".to_string();
+        let mut var_name = "".to_string();
+        let mut ret_vec: Vec<IndentedString> = Vec::new();
+        let mut value_happened: bool = false;
+        for child in node.get_children() {
+            let child = self.publisher.get_node(*child);
+            match child.rule {
+                Rules::annotation_block => {
+                    for line in self.annotation_block(child) {
+                        ret_vec.push(line);
+                    }
+                }
+                Rules::multiline_comment => {
+                    let comment = self.multiline_comment(child);
+                    for line in comment {
+                        ret_vec.push(line);
+                    }
+                }
+                Rules::comment => {
+                    if value_happened {
+                        let mut last_element = ret_vec.pop().unwrap();
+                        let comment = self.comment(child, true);
+                        last_element += comment;
+                        ret_vec.push(last_element);
+                    } else {
+                        ret_vec.push(self.comment(child, false));
+                    }
+                }
+                Rules::type_ref => type_ref = self.type_ref(child),
+                Rules::variable_name => var_name = self.variable_name(child),
+                Rules::expression | Rules::array_initializer | Rules::struct_initializer => {
+                    value_happened = true;
+                    let value = self.initializer(child);
+                    let result = format!("const {} {} = {}", type_ref, var_name, value);
+                    ret_vec.push(IndentedString::new(0, result));
+                }
+                e => {
+                    panic!("Rule: {:?} should not be the constants child.", e)
+                }
+            }
+        }
+        ret_vec
+    }
+
+    fn initializer(&self, node: &Node) -> String {
+        // Initializers cannot contain comments so they are rebuilt on one line with normalised spacing.
+        let children: Vec<&Node> = node
+            .get_children()
+            .iter()
+            .map(|child| self.publisher.get_node(*child))
+            .collect();
+        match node.rule {
+            Rules::expression | Rules::term => {
+                let parts: Vec<String> = children.iter().map(|c| self.initializer(c)).collect();
+                parts.join(" ")
+            }
+            Rules::additive_operator | Rules::multiplicative_operator | Rules::boolean => {
+                node.get_string(self.source)
+            }
+            Rules::factor => self.initializer(children[0]),
+            Rules::number | Rules::constant_ref => node
+                .get_string(self.source)
+                .replace([' ', '\t', '\n', '\r'], ""),
+            Rules::string_literal => node.get_string(self.source),
+            Rules::parenthesised => format!("({})", self.initializer(children[0])),
+            Rules::negation => format!("-{}", self.initializer(children[0])),
+            Rules::array_initializer => {
+                let items: Vec<String> = children.iter().map(|c| self.initializer(c)).collect();
+                format!("[{}]", items.join(", "))
+            }
+            Rules::struct_initializer => {
+                if children.is_empty() {
+                    return "{}".to_string();
+                }
+                let fields: Vec<String> = children.iter().map(|c| self.initializer(c)).collect();
+                format!("{{ {} }}", fields.join(", "))
+            }
+            Rules::field_initializer => {
+                format!("{}: {}", self.variable_name(children[0]), self.initializer(children[1]))
+            }
+            e => {
+                panic!("Rule: {:?} should not be in a constant initializer.", e)
+            }
+        }
+    }
+
     fn structure(&self, node: &Node) -> Vec<IndentedString> {
         debug_assert!(node.rule == Rules::structure);
 
         let mut ret_vec: Vec<IndentedString> = Vec::new();
         let mut open_bracket: bool = false;
         let mut close_bracket: bool = false;
+        let mut struct_name: String = "".to_string();
+        let mut struct_name_index: usize = 0;
         for child in node.get_children() {
             let child = self.publisher.get_node(*child);
             match child.rule {
@@ -686,9 +931,18 @@ impl<'a> Formatter<'a> {
                 }
                 Rules::type_dec => {
                     // We know this happens before the contents of struct.
-                    let struct_name = self.type_dec(child);
-                    let struct_name = IndentedString::new(0, format!("struct {} {{", struct_name));
-                    ret_vec.push(struct_name);
+                    struct_name = self.type_dec(child);
+                    struct_name_index = ret_vec.len();
+                    ret_vec.push(IndentedString::new(0, format!("struct {} {{", struct_name)));
+                }
+                Rules::extends => {
+                    let extends = self.extends(child);
+                    ret_vec[struct_name_index] =
+                        IndentedString::new(0, format!("struct {} {} {{", struct_name, extends));
+                }
+                Rules::polymorphic => {
+                    ret_vec[struct_name_index] =
+                        IndentedString::new(0, format!("struct {} polymorphic {{", struct_name));
                 }
                 Rules::variable_declaration => {
                     for mut line in self.variable_declaration(child) {
@@ -704,6 +958,125 @@ impl<'a> Formatter<'a> {
         ret_vec
     }
 
+    fn union(&self, node: &Node) -> Vec<IndentedString> {
+        debug_assert!(node.rule == Rules::union);
+
+        let mut ret_vec: Vec<IndentedString> = Vec::new();
+        let mut open_bracket: bool = false;
+        let mut close_bracket: bool = false;
+        let mut union_name: String = "".to_string();
+        let mut union_name_index: usize = 0;
+        for child in node.get_children() {
+            let child = self.publisher.get_node(*child);
+            match child.rule {
+                Rules::open_bracket => open_bracket = true,
+                Rules::close_bracket => {
+                    close_bracket = true;
+                    self.after_bracket_helper(&mut ret_vec);
+                }
+                Rules::annotation_block => {
+                    for line in self.annotation_block(child) {
+                        ret_vec.push(line);
+                    }
+                }
+                Rules::multiline_comment => {
+                    self.multiline_comment_helper(child, &mut ret_vec, open_bracket, close_bracket);
+                }
+                Rules::comment => {
+                    self.comment_helper(child, &mut ret_vec, open_bracket, close_bracket);
+                }
+                Rules::type_dec => {
+                    // We know this happens before the contents of union.
+                    union_name = self.type_dec(child);
+                    union_name_index = ret_vec.len();
+                    ret_vec.push(IndentedString::new(0, format!("union {} {{", union_name)));
+                }
+                Rules::extends => {
+                    // Comments may already sit after the name so replace the name line in place.
+                    let extends = self.extends(child);
+                    ret_vec[union_name_index] =
+                        IndentedString::new(0, format!("union {} {} {{", union_name, extends));
+                }
+                Rules::variable_declaration => {
+                    for mut line in self.variable_declaration(child) {
+                        line.indent();
+                        ret_vec.push(line);
+                    }
+                }
+                e => {
+                    panic!("Rule: {:?} should not be the unions child.", e)
+                }
+            }
+        }
+        ret_vec
+    }
+
+    fn extends(&self, node: &Node) -> String {
+        debug_assert!(node.rule == Rules::extends);
+        let mut ret_str = "extends".to_string();
+        for child in node.get_children() {
+            let child = self.publisher.get_node(*child);
+            match child.rule {
+                Rules::type_ref => {
+                    ret_str = format!("{} {}", ret_str, self.type_ref(child));
+                }
+                e => {
+                    panic!("Rule: {:?} should not be the extends child.", e)
+                }
+            }
+        }
+        ret_str
+    }
+
+    fn map(&self, node: &Node) -> Vec<IndentedString> {
+        debug_assert!(node.rule == Rules::map);
+
+        let mut ret_vec: Vec<IndentedString> = Vec::new();
+        let mut open_bracket: bool = false;
+        let mut close_bracket: bool = false;
+        let mut key_type: Option<String> = None;
+        for child in node.get_children() {
+            let child = self.publisher.get_node(*child);
+            match child.rule {
+                Rules::open_bracket => open_bracket = true,
+                Rules::close_bracket => {
+                    close_bracket = true;
+                    self.after_bracket_helper(&mut ret_vec);
+                }
+                Rules::annotation_block => {
+                    for line in self.annotation_block(child) {
+                        ret_vec.push(line);
+                    }
+                }
+                Rules::multiline_comment => {
+                    self.multiline_comment_helper(child, &mut ret_vec, open_bracket, close_bracket);
+                }
+                Rules::comment => {
+                    self.comment_helper(child, &mut ret_vec, open_bracket, close_bracket);
+                }
+                Rules::type_dec => {
+                    let map_name = self.type_dec(child);
+                    let map_name = IndentedString::new(0, format!("map {} {{", map_name));
+                    ret_vec.push(map_name);
+                }
+                Rules::type_ref => match key_type {
+                    // The key always comes before the value.
+                    None => key_type = Some(self.type_ref(child)),
+                    Some(ref key) => {
+                        let mut line =
+                            IndentedString::new(0, format!("{} to {}", key, self.type_ref(child)));
+                        line.indent();
+                        ret_vec.push(line);
+                    }
+                },
+                e => {
+                    panic!("Rule: {:?} should not be the maps child.", e)
+                }
+            }
+        }
+        ret_vec
+    }
+
     fn attribute(&self, node: &Node) -> Vec<IndentedString> {
         debug_assert!(node.rule == Rules::attribute);
         let mut type_ref: String = "".to_string();
@@ -724,6 +1097,12 @@ impl<'a> Formatter<'a> {
                         IndentedString::new(0, format!("attribute {} {}", type_ref, var_name));
                     ret_vec.push(attr);
                 }
+                Rules::readonly | Rules::no_subscriptions => {
+                    // Modifiers always directly follow the name so they belong on the same line.
+                    let mut attr = ret_vec.pop().expect("Attribute name should always exist");
+                    attr += IndentedString::new(0, format!(" {}", child.get_string(self.source)));
+                    ret_vec.push(attr);
+                }
                 Rules::multiline_comment => {
                     let comment = self.multiline_comment(child);
                     for line in comment {
@@ -818,7 +1197,7 @@ impl<'a> Formatter<'a> {
 
     fn method(&self, node: &Node) -> Vec<IndentedString> {
         debug_assert!(node.rule == Rules::method);
-        let mut var_name: String;
+        let mut var_name: String = "".to_string();
         let mut input: Vec<IndentedString>;
         let mut output: Vec<IndentedString>;
         let mut ret_vec: Vec<IndentedString> = Vec::new();
@@ -848,6 +1227,14 @@ impl<'a> Formatter<'a> {
                     var_name = self.variable_name(child);
                     ret_vec.push(IndentedString::new(0, format!("method {} {{", var_name)));
                 }
+                Rules::fire_and_forget => {
+                    // Always directly follows the name so the last line is the header.
+                    ret_vec.pop().expect("Method name should always exist");
+                    ret_vec.push(IndentedString::new(
+                        0,
+                        format!("method {} fireAndForget {{", var_name),
+                    ));
+                }
                 Rules::input_params => {
                     input = self.input_params(child);
                     for mut line in input {
@@ -862,6 +1249,12 @@ impl<'a> Formatter<'a> {
                         ret_vec.push(line);
                     }
                 }
+                Rules::method_error => {
+                    for mut line in self.method_error(child) {
+                        line.indent();
+                        ret_vec.push(line);
+                    }
+                }
                 e => {
                     panic!("Rule: {:?} should not be the method child.", e)
                 }
@@ -870,6 +1263,60 @@ impl<'a> Formatter<'a> {
         ret_vec
     }
 
+    fn broadcast(&self, node: &Node) -> Vec<IndentedString> {
+        debug_assert!(node.rule == Rules::broadcast);
+        let mut var_name: String = "".to_string();
+        let mut output: Vec<IndentedString>;
+        let mut ret_vec: Vec<IndentedString> = Vec::new();
+        let mut open_bracket: bool = false;
+        let mut close_bracket: bool = false;
+        for child in node.get_children() {
+            let child = self.publisher.get_node(*child);
+            match child.rule {
+                Rules::open_bracket => open_bracket = true,
+                Rules::close_bracket => {
+                    close_bracket = true;
+                    self.after_bracket_helper(&mut ret_vec);
+                }
+                Rules::comment => {
+                    self.comment_helper(child, &mut ret_vec, open_bracket, close_bracket);
+                }
+                Rules::multiline_comment => {
+                    self.multiline_comment_helper(child, &mut ret_vec, open_bracket, close_bracket);
+                }
+
+                Rules::annotation_block => {
+                    for line in self.annotation_block(child) {
+                        ret_vec.push(line);
+                    }
+                }
+                Rules::variable_name => {
+                    var_name = self.variable_name(child);
+                    ret_vec.push(IndentedString::new(0, format!("broadcast {} {{", var_name)));
+                }
+                Rules::selective => {
+                    // Always directly follows the name so the last line is the header.
+                    ret_vec.pop().expect("Broadcast name should always exist");
+                    ret_vec.push(IndentedString::new(
+                        0,
+                        format!("broadcast {} selective {{", var_name),
+                    ));
+                }
+                Rules::output_params => {
+                    output = self.output_params(child);
+                    for mut line in output {
+                        line.indent();
+                        ret_vec.push(line);
+                    }
+                }
+                e => {
+                    panic!("Rule: {:?} should not be the broadcast child.", e)
+                }
+            }
+        }
+        ret_vec
+    }
+
     fn input_params(&self, node: &Node) -> Vec<IndentedString> {
         debug_assert!(node.rule == Rules::input_params);
         let mut ret_vec: Vec<IndentedString> = Vec::new();
@@ -994,6 +1441,53 @@ impl<'a> Formatter<'a> {
         ret_vec
     }
 
+    fn method_error(&self, node: &Node) -> Vec<IndentedString> {
+        debug_assert!(node.rule == Rules::method_error);
+        let mut ret_vec: Vec<IndentedString> = Vec::new();
+        let mut open_bracket: bool = false;
+        let mut close_bracket: bool = false;
+        for child in node.get_children() {
+            let child = self.publisher.get_node(*child);
+            match child.rule {
+                Rules::open_bracket => {
+                    open_bracket = true;
+                    ret_vec.push(IndentedString::new(0, "error {".to_owned()));
+                }
+                Rules::close_bracket => {
+                    close_bracket = true;
+                    self.after_bracket_helper(&mut ret_vec);
+                }
+                Rules::comment => {
+                    self.comment_helper(child, &mut ret_vec, open_bracket, close_bracket);
+                }
+                Rules::multiline_comment => {
+                    self.multiline_comment_helper(child, &mut ret_vec, open_bracket, close_bracket);
+                }
+                Rules::annotation_block => {
+                    for line in self.annotation_block(child) {
+                        ret_vec.push(line);
+                    }
+                }
+                Rules::type_ref => {
+                    let type_ref = self.type_ref(child);
+                    ret_vec.push(IndentedString::new(0, format!("error {}", type_ref)));
+                    // There are no brackets so treat it as closed so trailing comments stay on the same line.
+                    close_bracket = true;
+                }
+                Rules::enum_value => {
+                    for mut line in self.enum_value(child) {
+                        line.indent();
+                        ret_vec.push(line);
+                    }
+                }
+                e => {
+                    panic!("Rule: {:?} should not be the method_error child.", e)
+                }
+            }
+        }
+        ret_vec
+    }
+
     fn variable_declaration(&self, node: &Node) -> Vec<IndentedString> {
         debug_assert!(node.rule == Rules::variable_declaration);
         let mut type_ref: String = "".to_string();
@@ -1070,7 +1564,8 @@ impl<'a> Formatter<'a> {
         // type_ref is a terminal so we can just return the str slice
         debug_assert!(node.rule == Rules::type_ref);
         let str = node.get_string(self.source);
-        str.replace([' ', '\t', '\n', '\r'], "")
+        // Integer ranges are the only type refs with a comma, `Integer(0, 10)`.
+        str.replace([' ', '\t', '\n', '\r'], "").replace(',', ", ")
     }
     fn variable_name(&self, node: &Node) -> String {
         // type_ref is a terminal so we can just return the str slice
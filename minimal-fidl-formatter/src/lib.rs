@@ -784,4 +784,185 @@ mod tests {
         let output = fmt.format();
         println!("Formatted:\n\n{}", output.unwrap());
     }
+
+    #[test]
+    fn test_formatter_33() {
+        let src = "package testcases
+        typeCollection MyTypes {
+        union MyUnion01 { UInt8 e1
+        String e2 }
+        }
+        interface aInterface {
+        // A union extending another one
+        union MyUnion02   extends   MyTypes.MyUnion01 // Comment
+        {
+        Boolean[] e3
+        }
+        }";
+        let publisher = parse(src).unwrap();
+        publisher.print(Key(0), Some(true));
+        let fmt = formatter::Formatter::new(src, &publisher);
+        let output = fmt.format();
+        println!("Formatted:\n\n{}", output.unwrap());
+    }
+
+    #[test]
+    fn test_formatter_34() {
+        let src = "package testcases
+        typeCollection MyTypes {
+        map MyMap05 { UInt16   to String }
+        }
+        interface aInterface {
+        <** @description: Configuration table **>
+        map ConfigTable {
+        MyTypes.MyMap05 to UInt8 [ ] // Comment
+        }
+        }";
+        let publisher = parse(src).unwrap();
+        publisher.print(Key(0), Some(true));
+        let fmt = formatter::Formatter::new(src, &publisher);
+        let output = fmt.format();
+        println!("Formatted:\n\n{}", output.unwrap());
+    }
+
+    #[test]
+    fn test_formatter_35() {
+        let src = "package testcases
+        interface aInterface {
+        broadcast statusChanged    selective { out {
+        String status // Comment
+        }
+        }
+        <** @description: Fired when done **>
+        broadcast done {}
+        }";
+        let publisher = parse(src).unwrap();
+        publisher.print(Key(0), Some(true));
+        let fmt = formatter::Formatter::new(src, &publisher);
+        let output = fmt.format();
+        println!("Formatted:\n\n{}", output.unwrap());
+    }
+
+    #[test]
+    fn test_formatter_36() {
+        let src = "package testcases
+        interface aInterface {
+        method connect { in { String device }
+        out { UInt8 handle }
+        error { TIMEOUT
+        REFUSED = 4 // Comment
+        } }
+        method disconnect {
+        error   MyTypes.DisconnectError // Comment
+        }
+        }";
+        let publisher = parse(src).unwrap();
+        publisher.print(Key(0), Some(true));
+        let fmt = formatter::Formatter::new(src, &publisher);
+        let output = fmt.format();
+        println!("Formatted:\n\n{}", output.unwrap());
+    }
+
+    #[test]
+    fn test_formatter_37() {
+        let src = "package testcases
+        interface aInterface {
+        attribute UInt8 volume   readonly\tnoSubscriptions // Comment
+        attribute UInt8 balance noSubscriptions
+        method log    fireAndForget { in { String message } }
+        }";
+        let publisher = parse(src).unwrap();
+        publisher.print(Key(0), Some(true));
+        let fmt = formatter::Formatter::new(src, &publisher);
+        let output = fmt.format();
+        println!("Formatted:\n\n{}", output.unwrap());
+    }
+
+    #[test]
+    fn test_formatter_38() {
+        let src = "package testcases
+        typeCollection MyTypes {
+        struct Base { UInt8 b1 }
+        struct   Derived   extends  Base // Comment
+        { String d1 }
+        enumeration BaseEnum { A B }
+        enumeration DerivedEnum extends   MyTypes.BaseEnum { C }
+        }
+        interface aInterface   extends\tbaseInterface {
+        attribute UInt8 volume
+        }";
+        let publisher = parse(src).unwrap();
+        publisher.print(Key(0), Some(true));
+        let fmt = formatter::Formatter::new(src, &publisher);
+        let output = fmt.format();
+        println!("Formatted:\n\n{}", output.unwrap());
+    }
+
+    #[test]
+    fn test_formatter_39() {
+        let src = "package testcases
+        typeCollection MyTypes {
+        struct   Shape\tpolymorphic { UInt8 colour }
+        struct Circle extends Shape { Float radius }
+        }";
+        let publisher = parse(src).unwrap();
+        publisher.print(Key(0), Some(true));
+        let fmt = formatter::Formatter::new(src, &publisher);
+        let output = fmt.format();
+        println!("Formatted:\n\n{}", output.unwrap());
+    }
+
+    #[test]
+    fn test_formatter_40() {
+        let src = r#"package testcases
+        typeCollection {
+        const UInt32   MAX=( 2+ 3 )*- OTHER   // Comment
+        const Point ORIGIN = {x:0.0,
+            label :"a  b",tags:[ 1,2 ,] }
+        }
+        interface aInterface {
+        const Boolean ENABLED = true
+        const UInt8[] EMPTY = []
+        }"#;
+        let publisher = parse(src).unwrap();
+        publisher.print(Key(0), Some(true));
+        let fmt = formatter::Formatter::new(src, &publisher);
+        let output = fmt.format();
+        println!("Formatted:\n\n{}", output.unwrap());
+    }
+
+    #[test]
+    fn test_formatter_41() {
+        let src = r#"package testcases
+        typeCollection {
+        typedef Percent is Integer( 0,100 )
+        const Integer(- 5 , 5) SMALL = -5
+        struct Ranges {
+            Integer(minInt,0xFF) [] a
+        }
+        }"#;
+        let publisher = parse(src).unwrap();
+        publisher.print(Key(0), Some(true));
+        let fmt = formatter::Formatter::new(src, &publisher);
+        let output = fmt.format();
+        println!("Formatted:\n\n{}", output.unwrap());
+    }
+
+    #[test]
+    fn test_formatter_42() {
+        let src = r#"package testcases
+        typeCollection {
+        array   Names   of String // Comment
+        <** @length: 4 **>
+        array Matrix of UInt8 [ ]
+        }
+        interface aInterface {
+        array Percentages of Integer( 0,100 )
+        }"#;
+        let publisher = parse(src).unwrap();
+        publisher.print(Key(0), Some(true));
+        let fmt = formatter::Formatter::new(src, &publisher);
+        let output = fmt.format();
+        println!("Formatted:\n\n{}", output.unwrap());
+    }
 }
@@ -204,8 +204,8 @@ class u8(BaseIntegerPrimitive):
 class i8(BaseIntegerPrimitive):
     _struct_format: ClassVar[str] = "<b"
     _size: ClassVar[int] = 1
-    _lower_range_limit: ClassVar[int] = -127 
-    _upper_range_limit: ClassVar[int] = 128
+    _lower_range_limit: ClassVar[int] = -128
+    _upper_range_limit: ClassVar[int] = 127
 
 
 @dataclass(frozen=True)
@@ -307,6 +307,48 @@ class BinarySerdeStruct(ABC):
         else: 
             type(self)._size = None
 
+@dataclass(frozen=True)
+class PolymorphicBinarySerdeStruct(BinarySerdeStruct):
+    ''' A struct that can be sent wherever its polymorphic base is expected.
+    It is prefixed with a u32 type tag so the receiver can tell which struct of the family it got.
+    The tag is passed as a class keyword, e.g. `class Circle(Shape, type_tag=2)`,
+    because ClassVar annotations would show up as struct fields.
+    Derived structs subclass their base so reading a base only accepts the structs of its family.'''
+    _registry = {}
+
+    def __init_subclass__(cls, type_tag: int, **kwargs):
+        super().__init_subclass__(**kwargs)
+        registry = PolymorphicBinarySerdeStruct._registry
+        existing = registry.get(type_tag)
+        if existing is not None and existing.__qualname__ != cls.__qualname__:
+            raise ValueError(f"Struct: '{cls.__name__}' has the same type tag as '{existing.__name__}'")
+        registry[type_tag] = cls
+        cls._type_tag = type_tag
+
+    def __bytes__(self) -> bytes:
+        return bytes(u32(self._type_tag)) + super().__bytes__()
+
+    @classmethod
+    def dynamic_from_bytes(cls, input: bytes) -> tuple[Self, int]:
+        tag_size = u32.size()
+        tag = u32.from_bytes(input[0:tag_size]).value
+        actual_cls = PolymorphicBinarySerdeStruct._registry.get(tag)
+        if actual_cls is None:
+            raise ValueError(f"Unknown type tag {tag:#010x} for '{cls.__name__}'")
+        if not issubclass(actual_cls, cls):
+            raise ValueError(f"Type tag {tag:#010x} is a '{actual_cls.__name__}' which is not a '{cls.__name__}'")
+        new_obj = super(PolymorphicBinarySerdeStruct, actual_cls).from_bytes(input[tag_size:])
+        return (new_obj, len(bytes(new_obj)))
+
+    @classmethod
+    def from_bytes(cls, input: bytes):
+        return cls.dynamic_from_bytes(input)[0]
+
+    @classmethod
+    def size(cls):
+        size = None # The struct behind the tag is only known once the tag is read.
+        return size
+
 
 @dataclass(frozen=True)
 class String():
@@ -379,6 +421,40 @@ class Float(f32):
 class Double(f64):
     '''Type stub to match FIDL'''
 
+_ranged_integers: dict[tuple[type, int, int], type] = {}
+
+def RangedInteger(wire_type: type, lower_range_limit: int, upper_range_limit: int) -> type:
+    '''
+    Returns the type for a FIDL Integer(min, max), it serializes as wire_type 
+    but only accepts values in the range. The same arguments always return the same type.
+    '''
+    key = (wire_type, lower_range_limit, upper_range_limit)
+    if key not in _ranged_integers:
+        if lower_range_limit < wire_type._lower_range_limit or upper_range_limit > wire_type._upper_range_limit:
+            raise ValueError(f"Integer({lower_range_limit}, {upper_range_limit}) does not fit in {wire_type.__name__}")
+        name = f"Integer({lower_range_limit}, {upper_range_limit})"
+        namespace = {"_lower_range_limit": lower_range_limit, "_upper_range_limit": upper_range_limit}
+        _ranged_integers[key] = dataclass(frozen=True)(type(name, (wire_type,), namespace))
+    return _ranged_integers[key]
+
+_fixed_arrays: dict[tuple[object, int], type] = {}
+
+def FixedArray(element_type: object, length: int) -> type:
+    '''
+    Returns the type for a FIDL array with a `@length: N` annotation, it is a tuple 
+    that only accepts exactly length elements. The same arguments always return the same type.
+    '''
+    key = (element_type, length)
+    if key not in _fixed_arrays:
+        def __new__(cls, elements=()):
+            array = tuple.__new__(cls, elements)
+            if len(array) != length:
+                raise ValueError(f"{cls.__name__} must hold exactly {length} elements not {len(array)}")
+            return array
+        name = f"FixedArray({getattr(element_type, '__name__', element_type)}, {length})"
+        _fixed_arrays[key] = type(name, (tuple,), {"__new__": __new__})
+    return _fixed_arrays[key]
+
 
 
 
@@ -417,6 +493,19 @@ if __name__ == "__main__":
         some_value2: String
         some_value3: u32
 
+    @dataclass(frozen=True)
+    class Shape(PolymorphicBinarySerdeStruct, type_tag=0x1):
+        colour: u8
+
+    @dataclass(frozen=True)
+    class Circle(Shape, type_tag=0x2):
+        colour: u8
+        radius: f32
+
+    @dataclass(frozen=True)
+    class Square(PolymorphicBinarySerdeStruct, type_tag=0x3):
+        side: u8
+
     
 
     x = UInt8(20)
@@ -459,6 +548,17 @@ if __name__ == "__main__":
     print(z)
     print(f2.from_bytes(z))
 
+    c = Circle(u8(3), f32(2.5))
+    z = bytes(c)
+    print(z.hex(" ", 1))
+    print(Shape.from_bytes(z))
+    assert isinstance(Shape.from_bytes(z), Circle)
+    try:
+        Shape.from_bytes(bytes(Square(u8(1))))
+        assert False, "A Square is not a Shape"
+    except ValueError as err:
+        print(err)
+
     f = ThingStruct(u8(2), f32(0.5))
     z = bytes(f)
     print(z, len(z))
@@ -500,4 +600,12 @@ if __name__ == "__main__":
     f2 = bytes(f)
     print(f2, len(f2))
     f3 = ThingStruct4.from_bytes(f2)
-    print(f3)
\ No newline at end of file
+    print(f3)
+    Percent = RangedInteger(UInt8, 0, 100)
+    f = Percent(42)
+    f2 = bytes(f)
+    print(f, f2, len(f2), Percent.from_bytes(f2))
+    try:
+        Percent(101)
+    except ValueError as e:
+        print(e)
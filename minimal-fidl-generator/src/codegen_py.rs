@@ -1,21 +1,29 @@
 use std::collections::HashMap;
 use std::hash::Hash;
 use std::io::Write;
-use std::{fmt::format, path::PathBuf};
+use std::{
+    fmt::format,
+    path::{Path, PathBuf},
+};
 
-use crate::codegen_trait::{CodeGenerator, GeneratorError};
+use crate::codegen_trait::{local_base, target_path, CodeGenerator, GeneratorError, Selection};
 use crate::indented_string::IndentedString;
 use crate::FidlType;
 use minimal_fidl_collect::annotation::Annotation;
 use minimal_fidl_collect::enum_value::EnumValue;
 use minimal_fidl_collect::{annotation, enum_value, fidl_file, FidlProject};
 use minimal_fidl_collect::{
+    array_type::ArrayType,
     attribute::{self, Attribute},
+    broadcast::Broadcast,
+    constant::{Constant, ConstantValue},
     enumeration::Enumeration,
     fidl_file::FidlFileRs,
     interface::Interface,
-    method::Method,
+    map::Map,
+    method::{Method, MethodError},
     structure::Structure,
+    type_ref::IntegerRange,
     type_collection::{self, TypeCollection},
     type_def::TypeDef,
     variable_declaration::VariableDeclaration,
@@ -52,7 +60,7 @@ impl CodeGenerator for PythonCodeGen {
             _ => {}
         };
         for (path, content) in &self.python_code {
-            let new_path = target_dir.clone().join(path);
+            let new_path = target_path(&target_dir, path);
             println!("{:?}", new_path);
             let parent = new_path.parent();
             if parent.is_some() {
@@ -67,6 +75,15 @@ impl CodeGenerator for PythonCodeGen {
         Ok(())
     }
 
+    fn remove_generated(&self, target_dir: &Path, path: &Path) -> Result<(), GeneratorError> {
+        // Each file is generated into a package of its own.
+        let generated = target_path(target_dir, &path.with_extension(""));
+        match std::fs::remove_dir_all(generated) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+
     // fn generate_file(&mut self, path: PathBuf, fidl: FidlFileRs) -> Result<(), GeneratorError> {
     //     let file = self.file(path.clone(), &fidl);
     //     let mut str: String = "".to_string();
@@ -76,12 +93,21 @@ impl CodeGenerator for PythonCodeGen {
     //     self.python_code.insert(path, vec);
     //     Ok(())
     // }
-    fn generate_project(&mut self, dir: PathBuf) -> Result<(), GeneratorError> {
-        let dir_clone = dir.clone();
-        let paths = FidlProject::new(dir);
-        self.project(&dir_clone);
-        for path in paths.unwrap() {
-            let fidl = FidlProject::generate_file(path.clone())?;
+    fn generate_files(
+        &mut self,
+        dir: &Path,
+        project: &FidlProject,
+        paths: &[&Path],
+        selection: &Selection,
+    ) -> Result<(), GeneratorError> {
+        // The shared modules go next to a single file.
+        let root = if dir.is_file() {
+            dir.parent().unwrap_or(Path::new(""))
+        } else {
+            dir
+        };
+        self.project(&root.to_path_buf());
+        for (path, fidl) in project.files().filter(|(path, _)| paths.contains(path)) {
             // This needs to be modified because I want to get each interface and type collection as a
             // seperate file.
             // But it's not part of the trait anymore so that's fine.
@@ -91,9 +117,9 @@ impl CodeGenerator for PythonCodeGen {
             // Send through some function
             // Deserialize the returned value. Async/Sync as options. Maybe only async since we can always force sync using async
             // Also need to add annotation block details support.
-            let mut p = path.clone();
+            let mut p = path.to_path_buf();
             p.set_extension("");
-            self.file(p, &fidl);
+            self.file(p, project, fidl, selection)?;
         }
         Ok(())
     }
@@ -159,26 +185,43 @@ impl PythonCodeGen {
             .insert(dir.with_file_name(path), comm_handler);
     }
 
-    fn file(&mut self, path: PathBuf, file: &FidlFileRs) -> () {
+    fn file(
+        &mut self,
+        path: PathBuf,
+        project: &FidlProject,
+        file: &FidlFileRs,
+        selection: &Selection,
+    ) -> Result<(), GeneratorError> {
         let init_path = path.clone().join("__init__.py");
         self.python_code.insert(init_path, Vec::new());
 
+        // Inherited fields, values and interface members are flattened into each derived type.
         for type_collection in &file.type_collections {
+            if !selection.type_collection(&type_collection.name) {
+                continue;
+            }
             let type_collection_name = &type_collection.name;
-            let x = self.type_collection(&type_collection);
+            let scope = file.qualified_name(type_collection_name);
+            let flattened = project.flattened_type_collection(file, type_collection)?;
+            let x = self.type_collection(&flattened, &scope);
             let mut p = path.clone();
             p.push(type_collection_name);
             p.set_extension(".py");
             self.python_code.insert(p, x);
         }
         for interface in &file.interfaces {
+            if !selection.interface(&interface.name) {
+                continue;
+            }
             let interface_name = &interface.name;
-            let x = self.interface(&interface);
+            let scope = file.qualified_name(interface_name);
+            let x = self.interface(&project.flattened_interface(file, interface)?, &scope);
             let mut p = path.clone();
             p.push(interface_name);
             p.set_extension(".py");
             self.python_code.insert(p, x);
         }
+        Ok(())
     }
 
     fn version(&self, version: &Option<Version>) -> Vec<IndentedString> {
@@ -218,7 +261,7 @@ impl PythonCodeGen {
         res
     }
 
-    fn type_collection(&self, type_collection: &TypeCollection) -> Vec<IndentedString> {
+    fn type_collection(&self, type_collection: &TypeCollection, scope: &str) -> Vec<IndentedString> {
         let mut res: Vec<IndentedString> = Vec::new();
         let header: IndentedString;
         header = IndentedString::new(
@@ -238,6 +281,13 @@ impl PythonCodeGen {
         );
         res.push(header);
         let header: IndentedString;
+        header = IndentedString::new(
+            0,
+            FidlType::Enumeration,
+            format!("from typing import Final, List"),
+        );
+        res.push(header);
+        let header: IndentedString;
         header = IndentedString::new(
             0,
             FidlType::Enumeration,
@@ -256,18 +306,32 @@ impl PythonCodeGen {
             let typedef: Vec<IndentedString> = self.typedef(typedef);
             res.extend(typedef)
         }
-        for structure in &type_collection.structures {
-            let structure: Vec<IndentedString> = self.structure(structure);
+        for map in &type_collection.maps {
+            let map: Vec<IndentedString> = self.map(map);
+            res.extend(map)
+        }
+        for array in &type_collection.arrays {
+            let array: Vec<IndentedString> = self.array_type(array);
+            res.extend(array)
+        }
+        for structure in Self::bases_first(&type_collection.structures) {
+            let structure: Vec<IndentedString> =
+                self.structure(structure, scope, &type_collection.structures);
             res.extend(structure)
         }
         for enumeration in &type_collection.enumerations {
             let enumeration: Vec<IndentedString> = self.enumeration(enumeration);
             res.extend(enumeration)
         }
+        // Constants come last since their initializers can use any type in the module.
+        for constant in &type_collection.constants {
+            let constant: Vec<IndentedString> = self.constant(constant, &type_collection.structures);
+            res.extend(constant)
+        }
         res
     }
 
-    fn interface(&self, interface: &Interface) -> Vec<IndentedString> {
+    fn interface(&self, interface: &Interface, scope: &str) -> Vec<IndentedString> {
         let mut res: Vec<IndentedString> = Vec::new();
         let id = Self::method_and_interface_split_annotation_content(&interface.annotations);
         let header: IndentedString;
@@ -285,6 +349,13 @@ impl PythonCodeGen {
         );
         res.push(header);
         let header: IndentedString;
+        header = IndentedString::new(
+            0,
+            FidlType::Enumeration,
+            format!("from typing import Callable, Final, List"),
+        );
+        res.push(header);
+        let header: IndentedString;
         header = IndentedString::new(
             0,
             FidlType::Enumeration,
@@ -312,6 +383,14 @@ impl PythonCodeGen {
             let typedef: Vec<IndentedString> = self.typedef(typedef);
             res.extend(typedef)
         }
+        for map in &interface.maps {
+            let map: Vec<IndentedString> = self.map(map);
+            res.extend(map)
+        }
+        for array in &interface.arrays {
+            let array: Vec<IndentedString> = self.array_type(array);
+            res.extend(array)
+        }
         for attribute in &interface.attributes {
             let attr: Vec<IndentedString> = self.attribute(attribute);
             res.extend(attr);
@@ -320,28 +399,56 @@ impl PythonCodeGen {
             let method: Vec<IndentedString> = self.method(method);
             res.extend(method)
         }
-        for structure in &interface.structures {
-            let structure: Vec<IndentedString> = self.structure(structure);
+        for broadcast in &interface.broadcasts {
+            let broadcast: Vec<IndentedString> = self.broadcast(broadcast);
+            res.extend(broadcast)
+        }
+        for structure in Self::bases_first(&interface.structures) {
+            let structure: Vec<IndentedString> =
+                self.structure(structure, scope, &interface.structures);
             res.extend(structure)
         }
         for enumeration in &interface.enumerations {
             let enumeration: Vec<IndentedString> = self.enumeration(enumeration);
             res.extend(enumeration)
         }
+        for constant in &interface.constants {
+            let constant: Vec<IndentedString> = self.constant(constant, &interface.structures);
+            res.extend(constant)
+        }
         res
     }
     fn attribute(&self, attribute: &Attribute) -> Vec<IndentedString> {
         // An attribute is some data the interface holds on the provider side, since we must communicate with a binary protocol
         // This gets converted into a get and set method for the attribute.
         let mut res: Vec<IndentedString> = Vec::new();
+        if !attribute.readonly {
+            let header = IndentedString::new(
+                0,
+                FidlType::Structure,
+                format!(
+                    "def set_{}(ctx: Comms, {}: {}):",
+                    attribute.name,
+                    attribute.name.to_lowercase(),
+                    Self::type_name(&attribute.type_n, &attribute.range)
+                ),
+            );
+            res.push(header);
+            res.push(IndentedString::new(
+                1,
+                FidlType::Attribute,
+                "pass".to_string(),
+            ));
+            res.push(IndentedString::new(1, FidlType::Attribute, "".to_string()));
+        }
+
         let header = IndentedString::new(
             0,
             FidlType::Structure,
             format!(
-                "def set_{}(ctx: Comms, {}: {}):",
+                "def get_{}() -> {}: ",
                 attribute.name,
-                attribute.name.to_lowercase(),
-                attribute.type_n
+                Self::type_name(&attribute.type_n, &attribute.range)
             ),
         );
         res.push(header);
@@ -350,45 +457,87 @@ impl PythonCodeGen {
             FidlType::Attribute,
             "pass".to_string(),
         ));
-        res.push(IndentedString::new(1, FidlType::Attribute, "".to_string()));
-
-        let header = IndentedString::new(
-            0,
-            FidlType::Structure,
-            format!("def get_{}() -> {}: ", attribute.name, attribute.type_n),
-        );
-        res.push(header);
-        res.push(IndentedString::new(
-            1,
-            FidlType::Attribute,
-            "pass".to_string(),
-        ));
         res.push(IndentedString::new(0, FidlType::Attribute, "".to_string()));
 
+        if !attribute.no_subscriptions {
+            let header = IndentedString::new(
+                0,
+                FidlType::Attribute,
+                format!(
+                    "def subscribe_{}_changed(ctx: Comms, callback: Callable[[{}], None]) -> None:",
+                    attribute.name,
+                    Self::type_name(&attribute.type_n, &attribute.range)
+                ),
+            );
+            res.push(header);
+            res.push(IndentedString::new(
+                1,
+                FidlType::Attribute,
+                "pass".to_string(),
+            ));
+            res.push(IndentedString::new(0, FidlType::Attribute, "".to_string()));
+        }
+
         res
     }
 
-    fn structure(&self, structure: &Structure) -> Vec<IndentedString> {
+    /// `structures` are the structs declared next to it, a derived polymorphic struct subclasses
+    /// its base when the base is one of them.
+    fn structure(
+        &self,
+        structure: &Structure,
+        scope: &str,
+        structures: &[Structure],
+    ) -> Vec<IndentedString> {
         let mut res: Vec<IndentedString> = Vec::new();
         let header: IndentedString;
         header = IndentedString::new(0, FidlType::Structure, format!("@dataclass(frozen=True)"));
 
         res.push(header);
         let header: IndentedString;
-        header = IndentedString::new(
-            0,
-            FidlType::Structure,
-            format!("class {}():", structure.name),
-        );
+        if structure.polymorphic {
+            // Polymorphic structs are prefixed with their type tag when serialized.
+            let base = match local_base(structure, structures) {
+                Some(base) if base.polymorphic => base.name.as_str(),
+                _ => "PolymorphicBinarySerdeStruct",
+            };
+            header = IndentedString::new(
+                0,
+                FidlType::Structure,
+                format!(
+                    "class {}({}, type_tag={:#010x}):",
+                    structure.name,
+                    base,
+                    structure.type_tag(scope)
+                ),
+            );
+        } else {
+            header = IndentedString::new(
+                0,
+                FidlType::Structure,
+                format!("class {}():", structure.name),
+            );
+        }
 
         res.push(header);
 
         for var_dec in &structure.contents {
             if var_dec.is_array {
-                let var_dec = format!("{}: List[{}]", var_dec.name, var_dec.type_n);
+                let var_dec = format!(
+                    "{}: {}",
+                    var_dec.name,
+                    Self::array_of(
+                        Self::type_name(&var_dec.type_n, &var_dec.range),
+                        var_dec.length
+                    )
+                );
                 res.push(IndentedString::new(1, FidlType::Structure, var_dec));
             } else {
-                let var_dec = format!("{}: {}", var_dec.name, var_dec.type_n);
+                let var_dec = format!(
+                    "{}: {}",
+                    var_dec.name,
+                    Self::type_name(&var_dec.type_n, &var_dec.range)
+                );
                 res.push(IndentedString::new(1, FidlType::Structure, var_dec));
             }
         }
@@ -396,7 +545,40 @@ impl PythonCodeGen {
         res
     }
 
+    /// Orders `structures` so a base comes before the structs derived from it,
+    /// a Python class can only subclass a class that already exists.
+    fn bases_first(structures: &[Structure]) -> Vec<&Structure> {
+        let mut ordered: Vec<&Structure> = Vec::new();
+        let mut remaining: Vec<&Structure> = structures.iter().collect();
+        while !remaining.is_empty() {
+            let before = remaining.len();
+            remaining.retain(|structure| match local_base(structure, structures) {
+                Some(base) if !ordered.iter().any(|s| std::ptr::eq(*s, base)) => true,
+                _ => {
+                    ordered.push(structure);
+                    false
+                }
+            });
+            // Cyclic bases are rejected when the file is collected, this only guards the loop.
+            if remaining.len() == before {
+                ordered.append(&mut remaining);
+            }
+        }
+        ordered
+    }
+
     fn typedef(&self, typedef: &TypeDef) -> Vec<IndentedString> {
+        if typedef.range.is_some() {
+            return vec![IndentedString::new(
+                0,
+                FidlType::File,
+                format!(
+                    "{} = {}\n",
+                    typedef.name,
+                    Self::type_name(&typedef.type_n, &typedef.range)
+                ),
+            )];
+        }
         vec![
             IndentedString::new(
                 0,
@@ -411,13 +593,134 @@ impl PythonCodeGen {
         ]
     }
 
+    fn constant(&self, constant: &Constant, structures: &[Structure]) -> Vec<IndentedString> {
+        let element_type = Self::type_name(&constant.type_n, &constant.range);
+        let mut type_n = element_type.clone();
+        if constant.is_array {
+            type_n = format!("List[{}]", type_n);
+        }
+        let value = Self::constant_value(
+            &constant.value,
+            Some(&element_type),
+            constant.is_array,
+            structures,
+        );
+        vec![IndentedString::new(
+            0,
+            FidlType::Constant,
+            format!("{}: Final[{}] = {}\n", constant.name, type_n, value),
+        )]
+    }
+
+    /// `Integer(min, max)` becomes a range checked subclass of the smallest integer type that fits.
+    fn type_name(type_n: &str, range: &Option<IntegerRange>) -> String {
+        match range {
+            Some(range) => format!(
+                "RangedInteger({}, {}, {})",
+                range.wire_type(),
+                range.min,
+                range.max
+            ),
+            None => type_n.to_string(),
+        }
+    }
+
+    fn constant_value(
+        value: &ConstantValue,
+        type_n: Option<&str>,
+        is_array: bool,
+        structures: &[Structure],
+    ) -> String {
+        // Values are wrapped in their FIDL type when it is known. Fields of structs declared
+        // in another module have no known type so they are left as plain Python values.
+        let raw = match value {
+            ConstantValue::Integer(i) => i.to_string(),
+            ConstantValue::Float(f) => format!("{:?}", f),
+            ConstantValue::Boolean(true) => "True".to_string(),
+            ConstantValue::Boolean(false) => "False".to_string(),
+            ConstantValue::String(s) => format!("{:?}", s),
+            ConstantValue::Array(values) => {
+                let element_type = if is_array { type_n } else { None };
+                let values: Vec<String> = values
+                    .iter()
+                    .map(|v| Self::constant_value(v, element_type, false, structures))
+                    .collect();
+                return format!("[{}]", values.join(", "));
+            }
+            ConstantValue::Struct(fields) => {
+                let structure = structures.iter().find(|s| Some(s.name.as_str()) == type_n);
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|(name, value)| {
+                        let field = structure
+                            .and_then(|s| s.contents.iter().find(|f| f.name == *name));
+                        let value = match field {
+                            Some(field) => Self::constant_value(
+                                value,
+                                Some(&Self::type_name(&field.type_n, &field.range)),
+                                field.is_array,
+                                structures,
+                            ),
+                            None => Self::constant_value(value, None, false, structures),
+                        };
+                        format!("{}={}", name, value)
+                    })
+                    .collect();
+                return format!("{}({})", type_n.unwrap_or("dict"), fields.join(", "));
+            }
+        };
+        match type_n {
+            Some(type_n) => format!("{}({})", type_n, raw),
+            None => raw,
+        }
+    }
+
+    fn map(&self, map: &Map) -> Vec<IndentedString> {
+        // A map is just a type alias for a dict, declared before structures so they can use it.
+        let key_type = Self::type_name(&map.key_type, &map.key_range);
+        let mut value_type = Self::type_name(&map.value_type, &map.value_range);
+        if map.value_is_array {
+            value_type = format!("List[{}]", value_type);
+        }
+        vec![IndentedString::new(
+            0,
+            FidlType::Map,
+            format!("{} = dict[{}, {}]\n", map.name, key_type, value_type),
+        )]
+    }
+
+    /// Arrays with a `@length: N` annotation are a length checked FixedArray, all others are a List.
+    fn array_of(element_type: String, length: Option<u64>) -> String {
+        match length {
+            Some(length) => format!("FixedArray({}, {})", element_type, length),
+            None => format!("List[{}]", element_type),
+        }
+    }
+
+    fn array_type(&self, array: &ArrayType) -> Vec<IndentedString> {
+        // Like maps a named array is a type alias, declared before structures so they can use it.
+        let mut element_type = Self::type_name(&array.type_n, &array.range);
+        if array.is_array {
+            element_type = Self::array_of(element_type, None);
+        }
+        vec![IndentedString::new(
+            0,
+            FidlType::Array,
+            format!(
+                "{} = {}\n",
+                array.name,
+                Self::array_of(element_type, array.length)
+            ),
+        )]
+    }
+
     fn method(&self, method: &Method) -> Vec<IndentedString> {
         let mut input_params = "".to_string();
         let id = Self::method_and_interface_split_annotation_content(&method.annotations);
         for param in &method.input_parameters {
             input_params += &param.name;
             input_params += ": ";
-            input_params += &param.type_n;
+            input_params += &Self::type_name(&param.type_n, &param.range);
             input_params += ", "
         }
         if input_params.len() != 0 {
@@ -426,17 +729,20 @@ impl PythonCodeGen {
 
         let mut output_params = "".to_string();
         match method.output_parameters.len() {
+            0 if method.fire_and_forget => {
+                output_params += "None";
+            }
             0 => {
                 output_params += "()";
             }
             1 => {
                 let single_param = &method.output_parameters[0];
-                output_params = format!("{}", single_param.type_n);
+                output_params = Self::type_name(&single_param.type_n, &single_param.range);
             }
             e => {
                 output_params.push('(');
                 for param in &method.output_parameters {
-                    output_params += &param.type_n;
+                    output_params += &Self::type_name(&param.type_n, &param.range);
                     output_params += ", "
                 }
                 output_params = output_params[0..output_params.len() - 2].to_string();
@@ -444,6 +750,9 @@ impl PythonCodeGen {
             }
         }
         let mut res: Vec<IndentedString> = Vec::new();
+        if method.errors.is_some() {
+            res.extend(self.method_error(method));
+        }
         res.push(IndentedString::new(
             0,
             FidlType::Method,
@@ -453,6 +762,20 @@ impl PythonCodeGen {
             )
             .to_string(),
         ));
+        if method.errors.is_some() {
+            res.push(IndentedString::new(
+                1,
+                FidlType::Method,
+                format!("'''Raises {}Exception if the call fails.'''", method.name),
+            ));
+        }
+        if method.fire_and_forget {
+            res.push(IndentedString::new(
+                1,
+                FidlType::Method,
+                "'''Fire and forget, returns once sent without waiting for a response.'''".to_string(),
+            ));
+        }
         if id.is_some() {
             let id = id.unwrap();
             res.push(IndentedString::new(1, FidlType::Method, format!("Id: int = {:?}", id)));
@@ -461,6 +784,105 @@ impl PythonCodeGen {
         res
     }
 
+    fn method_error(&self, method: &Method) -> Vec<IndentedString> {
+        // Errors are raised as a typed exception that carries the error enumeration value.
+        let mut res: Vec<IndentedString> = Vec::new();
+        let error_type = match &method.errors {
+            None => return res,
+            Some(MethodError::Inline(enumeration)) => {
+                res.extend(self.enumeration(enumeration));
+                res.push(IndentedString::new(0, FidlType::Method, "".to_string()));
+                enumeration.name.clone()
+            }
+            Some(MethodError::Reference(type_n)) => type_n.clone(),
+        };
+        res.push(IndentedString::new(
+            0,
+            FidlType::Method,
+            format!("class {}Exception(Exception):", method.name),
+        ));
+        res.push(IndentedString::new(
+            1,
+            FidlType::Method,
+            format!("def __init__(self, error: {}):", error_type),
+        ));
+        res.push(IndentedString::new(
+            2,
+            FidlType::Method,
+            "super().__init__(error)".to_string(),
+        ));
+        res.push(IndentedString::new(
+            2,
+            FidlType::Method,
+            "self.error = error\n".to_string(),
+        ));
+        res
+    }
+
+    fn broadcast(&self, broadcast: &Broadcast) -> Vec<IndentedString> {
+        let id = Self::method_and_interface_split_annotation_content(&broadcast.annotations);
+        let payload_name = format!("{}Payload", broadcast.name);
+        let mut res: Vec<IndentedString> = Vec::new();
+        res.push(IndentedString::new(
+            0,
+            FidlType::Broadcast,
+            format!("@dataclass(frozen=True)"),
+        ));
+        res.push(IndentedString::new(
+            0,
+            FidlType::Broadcast,
+            format!("class {}():", payload_name),
+        ));
+        for var_dec in &broadcast.output_parameters {
+            if var_dec.is_array {
+                let var_dec = format!(
+                    "{}: {}",
+                    var_dec.name,
+                    Self::array_of(
+                        Self::type_name(&var_dec.type_n, &var_dec.range),
+                        var_dec.length
+                    )
+                );
+                res.push(IndentedString::new(1, FidlType::Broadcast, var_dec));
+            } else {
+                let var_dec = format!(
+                    "{}: {}",
+                    var_dec.name,
+                    Self::type_name(&var_dec.type_n, &var_dec.range)
+                );
+                res.push(IndentedString::new(1, FidlType::Broadcast, var_dec));
+            }
+        }
+        if broadcast.output_parameters.len() == 0 {
+            res.push(IndentedString::new(1, FidlType::Broadcast, format!("pass")));
+        }
+        res.push(IndentedString::new(0, FidlType::Broadcast, "".to_string()));
+
+        for action in ["subscribe", "unsubscribe"] {
+            res.push(IndentedString::new(
+                0,
+                FidlType::Broadcast,
+                format!(
+                    "def {}_{}(ctx: Comms, callback: Callable[[{}], None]) -> None:",
+                    action, broadcast.name, payload_name
+                ),
+            ));
+            if broadcast.selective {
+                res.push(IndentedString::new(
+                    1,
+                    FidlType::Broadcast,
+                    format!("'''Selective broadcast, the provider decides which subscribers receive it.'''"),
+                ));
+            }
+            if id.is_some() {
+                let id = id.unwrap();
+                res.push(IndentedString::new(1, FidlType::Broadcast, format!("Id: int = {:?}", id)));
+            }
+            res.push(IndentedString::new(1, FidlType::Broadcast, format!("pass\n")));
+        }
+        res
+    }
+
     fn enumeration_value_gatherer(&self, enumeration: &Enumeration) -> (u64, HashMap<String, u64>) {
         // The goal is to have as compact a representation as possible
         // So we need to do some work to allow for hardcoded enum values and autovalued enum values
@@ -1,22 +1,93 @@
-use std::{fmt::format, path::PathBuf};
+use std::collections::HashMap;
+use std::io::Write;
+use std::{
+    fmt::format,
+    path::{Path, PathBuf},
+};
 
-use crate::codegen_trait::CodeGenerator;
+use crate::codegen_trait::{local_base, target_path, CodeGenerator, GeneratorError, Selection};
 use crate::indented_string::IndentedString;
 use crate::FidlType;
 use minimal_fidl_collect::{
+    array_type::ArrayType,
     attribute::{self, Attribute},
+    broadcast::Broadcast,
+    constant::{Constant, ConstantValue},
     enumeration::Enumeration,
     fidl_file::FidlFileRs,
     interface::Interface,
-    method::Method,
+    map::Map,
+    method::{Method, MethodError},
     structure::Structure,
     type_collection::{self, TypeCollection},
     type_def::TypeDef,
+    type_ref::IntegerRange,
     variable_declaration::VariableDeclaration,
     version::Version,
+    FidlProject,
 };
 
-pub struct RustCodeGen();
+pub struct RustCodeGen {
+    // Each fidl file becomes one Rust source file with a module per interface and type collection.
+    pub rust_code: HashMap<PathBuf, Vec<IndentedString>>,
+}
+impl std::fmt::Debug for RustCodeGen {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (path, vec) in &self.rust_code {
+            write!(f, "\n\n{:?}\n", path)?;
+            for src in vec {
+                write!(f, "{}", src)?;
+            }
+        }
+        Ok(())
+    }
+}
+impl CodeGenerator for RustCodeGen {
+    fn new() -> Self {
+        Self {
+            rust_code: HashMap::new(),
+        }
+    }
+
+    fn emit_project(&self, target_dir: PathBuf) -> Result<(), GeneratorError> {
+        std::fs::create_dir_all(&target_dir)?;
+        for (path, content) in &self.rust_code {
+            let new_path = target_path(&target_dir, path);
+            if let Some(parent) = new_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let mut file = std::fs::File::create(new_path)?;
+            for line in content {
+                file.write_all(line.to_string().as_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn remove_generated(&self, target_dir: &Path, path: &Path) -> Result<(), GeneratorError> {
+        let generated = target_path(target_dir, &path.with_extension("rs"));
+        match std::fs::remove_file(generated) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn generate_files(
+        &mut self,
+        _dir: &Path,
+        project: &FidlProject,
+        paths: &[&Path],
+        selection: &Selection,
+    ) -> Result<(), GeneratorError> {
+        for (path, fidl) in project.files().filter(|(path, _)| paths.contains(path)) {
+            let mut p = path.to_path_buf();
+            p.set_extension("rs");
+            let code = self.file(project, fidl, selection)?;
+            self.rust_code.insert(p, code);
+        }
+        Ok(())
+    }
+}
 
 impl RustCodeGen {
     fn built_in_types(&self) -> Vec<IndentedString> {
@@ -92,6 +163,62 @@ impl RustCodeGen {
             FidlType::File,
             format!("pub use f64 as Double;"),
         ));
+        // Integer(min, max) types are wrapped so out of range values cannot be constructed,
+        // not even by deserializing them.
+        let ranged_integer = [
+            "#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]",
+            "pub struct RangedInteger<T, const MIN: i64, const MAX: i64>(T);",
+            "impl<T: Copy + Into<i128>, const MIN: i64, const MAX: i64> RangedInteger<T, MIN, MAX> {",
+            "    pub fn new(value: T) -> Option<Self> {",
+            "        let wide: i128 = value.into();",
+            "        if wide < MIN as i128 || wide > MAX as i128 {",
+            "            return None;",
+            "        }",
+            "        Some(Self(value))",
+            "    }",
+            "    pub fn get(&self) -> T {",
+            "        self.0",
+            "    }",
+            "}",
+            "impl<T: serde::Serialize, const MIN: i64, const MAX: i64> serde::Serialize for RangedInteger<T, MIN, MAX> {",
+            "    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {",
+            "        serde::Serialize::serialize(&self.0, serializer)",
+            "    }",
+            "}",
+            "impl<'de, T: serde::Deserialize<'de> + Copy + Into<i128>, const MIN: i64, const MAX: i64> serde::Deserialize<'de>",
+            "    for RangedInteger<T, MIN, MAX>",
+            "{",
+            "    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {",
+            "        let value = T::deserialize(deserializer)?;",
+            "        Self::new(value).ok_or_else(|| {",
+            "            let wide: i128 = value.into();",
+            "            serde::de::Error::custom(format!(\"{} is outside of {}..={}\", wide, MIN, MAX))",
+            "        })",
+            "    }",
+            "}",
+            "impl<T: binary_serde::BinarySerde + Copy + Into<i128>, const MIN: i64, const MAX: i64> binary_serde::BinarySerde",
+            "    for RangedInteger<T, MIN, MAX>",
+            "{",
+            "    const SERIALIZED_SIZE: usize = T::SERIALIZED_SIZE;",
+            "    type RecursiveArray = T::RecursiveArray;",
+            "    fn binary_serialize(&self, buf: &mut [u8], endianness: binary_serde::Endianness) {",
+            "        binary_serde::BinarySerde::binary_serialize(&self.0, buf, endianness)",
+            "    }",
+            "    fn binary_deserialize(",
+            "        buf: &[u8],",
+            "        endianness: binary_serde::Endianness,",
+            "    ) -> Result<Self, binary_serde::DeserializeError> {",
+            "        let value = T::binary_deserialize(buf, endianness)?;",
+            "        // binary_serde can only report invalid enum values, an out of range integer is the closest.",
+            "        Self::new(value).ok_or(binary_serde::DeserializeError::InvalidEnumValue {",
+            "            enum_name: \"RangedInteger\",",
+            "        })",
+            "    }",
+            "}",
+        ];
+        for line in ranged_integer {
+            res.push(IndentedString::new(1, FidlType::File, line.to_string()));
+        }
         res.push(IndentedString::new(0, FidlType::File, "}".to_string()));
 
         res
@@ -108,6 +235,31 @@ impl RustCodeGen {
         res
     }
 
+    fn polymorphic_trait(&self) -> Vec<IndentedString> {
+        // Polymorphic structs are written with their type tag first so the receiver
+        // can tell which struct of the family it got before reading the rest.
+        let lines = [
+            "pub trait PolymorphicStruct: BinarySerde {",
+            "    const TYPE_TAG: u32;",
+            "    fn binary_serialize_tagged(&self, buf: &mut [u8], endianness: Endianness) {",
+            "        Self::TYPE_TAG.binary_serialize(&mut buf[..4], endianness);",
+            "        self.binary_serialize(&mut buf[4..], endianness);",
+            "    }",
+            "    fn binary_deserialize_tagged(buf: &[u8], endianness: Endianness) -> Option<Self> {",
+            "        let tag = u32::binary_deserialize(&buf[..4], endianness).ok()?;",
+            "        if tag != Self::TYPE_TAG {",
+            "            return None;",
+            "        }",
+            "        Self::binary_deserialize(&buf[4..], endianness).ok()",
+            "    }",
+            "}",
+        ];
+        lines
+            .iter()
+            .map(|line| IndentedString::new(0, FidlType::File, line.to_string()))
+            .collect()
+    }
+
     fn project(&self, dir: &PathBuf) -> Vec<IndentedString> {
         let mut res: Vec<IndentedString> = Vec::new();
         res.push(IndentedString::new(
@@ -115,6 +267,11 @@ impl RustCodeGen {
             FidlType::File,
             "use serde::{Serialize, Deserialize};".to_string(),
         ));
+        res.push(IndentedString::new(
+            0,
+            FidlType::File,
+            "use std::collections::HashMap;".to_string(),
+        ));
         res.push(IndentedString::new(
             0,
             FidlType::File,
@@ -123,11 +280,17 @@ impl RustCodeGen {
 
         res.extend(self.built_in_types());
         res.extend(self.context_trait());
+        res.extend(self.polymorphic_trait());
 
         res
     }
 
-    fn file(&self, file: &FidlFileRs) -> Vec<IndentedString> {
+    fn file(
+        &self,
+        project: &FidlProject,
+        file: &FidlFileRs,
+        selection: &Selection,
+    ) -> Result<Vec<IndentedString>, GeneratorError> {
         let mut res: Vec<IndentedString> = Vec::new();
 
         // Below is temporary, file should really be called by and from project not this way around.
@@ -135,16 +298,26 @@ impl RustCodeGen {
         res.extend(self.project(&dir_path));
         // End temporary
 
+        // Inherited fields, values and interface members are flattened into each derived type.
         for type_collection in &file.type_collections {
-            let x = self.type_collection(&type_collection);
+            if !selection.type_collection(&type_collection.name) {
+                continue;
+            }
+            let scope = file.qualified_name(&type_collection.name);
+            let flattened = project.flattened_type_collection(file, type_collection)?;
+            let x = self.type_collection(&flattened, &scope);
             res.extend(x);
         }
         for interface in &file.interfaces {
-            let x = self.interface(&interface);
+            if !selection.interface(&interface.name) {
+                continue;
+            }
+            let scope = file.qualified_name(&interface.name);
+            let x = self.interface(&project.flattened_interface(file, interface)?, &scope);
             res.extend(x);
         }
 
-        res
+        Ok(res)
     }
 
     fn version(&self, version: &Option<Version>) -> Vec<IndentedString> {
@@ -184,7 +357,7 @@ impl RustCodeGen {
         res
     }
 
-    fn type_collection(&self, type_collection: &TypeCollection) -> Vec<IndentedString> {
+    fn type_collection(&self, type_collection: &TypeCollection, scope: &str) -> Vec<IndentedString> {
         let mut res: Vec<IndentedString> = Vec::new();
         // An interface is equivalent to a Rust Module
         let module = IndentedString::new(
@@ -218,14 +391,36 @@ impl RustCodeGen {
                 .collect();
             res.extend(typedef)
         }
+        for map in &type_collection.maps {
+            let map: Vec<IndentedString> = self
+                .map(map, true)
+                .into_iter()
+                .map(|e| e.indent())
+                .collect();
+            res.extend(map)
+        }
+        for array in &type_collection.arrays {
+            let array: Vec<IndentedString> = self
+                .array_type(array)
+                .into_iter()
+                .map(|e| e.indent())
+                .collect();
+            res.extend(array)
+        }
         for structure in &type_collection.structures {
             let structure: Vec<IndentedString> = self
-                .structure(structure, true)
+                .structure(structure, scope, true)
                 .into_iter()
                 .map(|e| e.indent())
                 .collect();
             res.extend(structure)
         }
+        let families: Vec<IndentedString> = self
+            .polymorphic_families(&type_collection.structures)
+            .into_iter()
+            .map(|e| e.indent())
+            .collect();
+        res.extend(families);
         for enumeration in &type_collection.enumerations {
             let enumeration: Vec<IndentedString> = self
                 .enumeration(enumeration, true)
@@ -234,13 +429,21 @@ impl RustCodeGen {
                 .collect();
             res.extend(enumeration)
         }
+        for constant in &type_collection.constants {
+            let constant: Vec<IndentedString> = self
+                .constant(constant, &type_collection.structures)
+                .into_iter()
+                .map(|e| e.indent())
+                .collect();
+            res.extend(constant)
+        }
         let end_bracket = IndentedString::new(0, FidlType::Interface, format!("}}"));
         res.push(end_bracket);
 
         res
     }
 
-    fn interface(&self, interface: &Interface) -> Vec<IndentedString> {
+    fn interface(&self, interface: &Interface, scope: &str) -> Vec<IndentedString> {
         let mut res: Vec<IndentedString> = Vec::new();
         // An interface is equivalent to a Rust Module
         let module = IndentedString::new(
@@ -274,6 +477,14 @@ impl RustCodeGen {
                 .collect();
             res.extend(typedef)
         }
+        for constant in &interface.constants {
+            let constant: Vec<IndentedString> = self
+                .constant(constant, &interface.structures)
+                .into_iter()
+                .map(|e| e.indent())
+                .collect();
+            res.extend(constant)
+        }
         for attribute in &interface.attributes {
             let attr: Vec<IndentedString> = self
                 .attribute(attribute)
@@ -290,14 +501,44 @@ impl RustCodeGen {
                 .collect();
             res.extend(method)
         }
+        for broadcast in &interface.broadcasts {
+            let broadcast: Vec<IndentedString> = self
+                .broadcast(broadcast)
+                .into_iter()
+                .map(|e| e.indent())
+                .collect();
+            res.extend(broadcast)
+        }
+        for map in &interface.maps {
+            let map: Vec<IndentedString> = self
+                .map(map, false)
+                .into_iter()
+                .map(|e| e.indent())
+                .collect();
+            res.extend(map)
+        }
+        for array in &interface.arrays {
+            let array: Vec<IndentedString> = self
+                .array_type(array)
+                .into_iter()
+                .map(|e| e.indent())
+                .collect();
+            res.extend(array)
+        }
         for structure in &interface.structures {
             let structure: Vec<IndentedString> = self
-                .structure(structure, false)
+                .structure(structure, scope, false)
                 .into_iter()
                 .map(|e| e.indent())
                 .collect();
             res.extend(structure)
         }
+        let families: Vec<IndentedString> = self
+            .polymorphic_families(&interface.structures)
+            .into_iter()
+            .map(|e| e.indent())
+            .collect();
+        res.extend(families);
         for enumeration in &interface.enumerations {
             let enumeration: Vec<IndentedString> = self
                 .enumeration(enumeration, false)
@@ -314,19 +555,21 @@ impl RustCodeGen {
         // An attribute is some data the interface holds on the provider side, since we must communicate with a binary protocol
         // This gets converted into a get and set method for the attribute.
         let mut res: Vec<IndentedString> = Vec::new();
-        let header = IndentedString::new(
-            0,
-            FidlType::Structure,
-            format!(
-                "fn set_{}(ctx: impl FidlContext, {}: {}) {{ ",
-                attribute.name,
-                attribute.name.to_lowercase(),
-                attribute.type_n
-            ),
-        );
-        res.push(header);
-        let header = IndentedString::new(0, FidlType::Structure, format!("}}"));
-        res.push(header);
+        if !attribute.readonly {
+            let header = IndentedString::new(
+                0,
+                FidlType::Structure,
+                format!(
+                    "fn set_{}(ctx: impl FidlContext, {}: {}) {{ ",
+                    attribute.name,
+                    attribute.name.to_lowercase(),
+                    Self::type_name(&attribute.type_n, &attribute.range)
+                ),
+            );
+            res.push(header);
+            let header = IndentedString::new(0, FidlType::Structure, format!("}}"));
+            res.push(header);
+        }
         let header = IndentedString::new(
             0,
             FidlType::Structure,
@@ -335,11 +578,25 @@ impl RustCodeGen {
         res.push(header);
         let header = IndentedString::new(0, FidlType::Structure, format!("}}"));
         res.push(header);
+        if !attribute.no_subscriptions {
+            let header = IndentedString::new(
+                0,
+                FidlType::Attribute,
+                format!(
+                    "pub fn subscribe_{}_changed(ctx: impl FidlContext, callback: impl Fn({}) + 'static) {{",
+                    attribute.name,
+                    Self::type_name(&attribute.type_n, &attribute.range)
+                ),
+            );
+            res.push(header);
+            let header = IndentedString::new(0, FidlType::Attribute, format!("}}"));
+            res.push(header);
+        }
 
         res
     }
 
-    fn structure(&self, structure: &Structure, public: bool) -> Vec<IndentedString> {
+    fn structure(&self, structure: &Structure, scope: &str, public: bool) -> Vec<IndentedString> {
         let mut res: Vec<IndentedString> = Vec::new();
         res.push(IndentedString::new(
             0,
@@ -370,19 +627,152 @@ impl RustCodeGen {
         res.push(header);
         for var_dec in &structure.contents {
             if var_dec.is_array {
-                let var_dec = format!("pub {}: [{}; 0],", var_dec.name, var_dec.type_n);
+                let var_dec = format!(
+                    "pub {}: {},",
+                    var_dec.name,
+                    Self::array_of(
+                        Self::type_name(&var_dec.type_n, &var_dec.range),
+                        var_dec.length
+                    )
+                );
                 res.push(IndentedString::new(1, FidlType::Structure, var_dec));
             } else {
-                let var_dec = format!("pub {}: {},", var_dec.name, var_dec.type_n);
+                let var_dec = format!(
+                    "pub {}: {},",
+                    var_dec.name,
+                    Self::type_name(&var_dec.type_n, &var_dec.range)
+                );
                 res.push(IndentedString::new(1, FidlType::Structure, var_dec));
             }
         }
         let header = IndentedString::new(0, FidlType::Structure, format!("}}"));
         res.push(header);
+        if structure.polymorphic {
+            res.push(IndentedString::new(
+                0,
+                FidlType::Structure,
+                format!("impl PolymorphicStruct for {} {{", structure.name),
+            ));
+            res.push(IndentedString::new(
+                1,
+                FidlType::Structure,
+                format!("const TYPE_TAG: u32 = {:#010x};", structure.type_tag(scope)),
+            ));
+            res.push(IndentedString::new(0, FidlType::Structure, "}".to_string()));
+        }
+        res
+    }
+
+    fn polymorphic_families(&self, structures: &[Structure]) -> Vec<IndentedString> {
+        // Each family of polymorphic structs gets an enum of its members, a receiver that expects
+        // the root reads the type tag and gets whichever member was sent.
+        let mut res: Vec<IndentedString> = Vec::new();
+        let root_of = |structure: &Structure| -> Option<String> {
+            if !structure.polymorphic {
+                return None;
+            }
+            let mut current = structure;
+            for _ in 0..structures.len() {
+                match local_base(current, structures) {
+                    Some(base) if base.polymorphic => current = base,
+                    _ => break,
+                }
+            }
+            Some(current.name.clone())
+        };
+        for root in structures {
+            if root_of(root).as_ref() != Some(&root.name) {
+                continue;
+            }
+            let members: Vec<&Structure> = structures
+                .iter()
+                .filter(|s| root_of(s).as_ref() == Some(&root.name))
+                .collect();
+            let family = format!("Any{}", root.name);
+            res.push(IndentedString::new(
+                0,
+                FidlType::Structure,
+                "#[derive(Debug, PartialEq)]".to_string(),
+            ));
+            res.push(IndentedString::new(
+                0,
+                FidlType::Structure,
+                format!("pub enum {} {{", family),
+            ));
+            for member in &members {
+                res.push(IndentedString::new(
+                    1,
+                    FidlType::Structure,
+                    format!("{}({}),", member.name, member.name),
+                ));
+            }
+            res.push(IndentedString::new(0, FidlType::Structure, "}".to_string()));
+            res.push(IndentedString::new(
+                0,
+                FidlType::Structure,
+                format!("impl {} {{", family),
+            ));
+            res.push(IndentedString::new(
+                1,
+                FidlType::Structure,
+                "pub fn binary_serialize_tagged(&self, buf: &mut [u8], endianness: Endianness) {"
+                    .to_string(),
+            ));
+            res.push(IndentedString::new(2, FidlType::Structure, "match self {".to_string()));
+            for member in &members {
+                res.push(IndentedString::new(
+                    3,
+                    FidlType::Structure,
+                    format!(
+                        "Self::{}(value) => value.binary_serialize_tagged(buf, endianness),",
+                        member.name
+                    ),
+                ));
+            }
+            res.push(IndentedString::new(2, FidlType::Structure, "}".to_string()));
+            res.push(IndentedString::new(1, FidlType::Structure, "}".to_string()));
+            res.push(IndentedString::new(
+                1,
+                FidlType::Structure,
+                "pub fn binary_deserialize_tagged(buf: &[u8], endianness: Endianness) -> Option<Self> {"
+                    .to_string(),
+            ));
+            res.push(IndentedString::new(
+                2,
+                FidlType::Structure,
+                "let tag = u32::binary_deserialize(&buf[..4], endianness).ok()?;".to_string(),
+            ));
+            res.push(IndentedString::new(2, FidlType::Structure, "match tag {".to_string()));
+            for member in &members {
+                res.push(IndentedString::new(
+                    3,
+                    FidlType::Structure,
+                    format!(
+                        "{0}::TYPE_TAG => {0}::binary_deserialize_tagged(buf, endianness).map(Self::{0}),",
+                        member.name
+                    ),
+                ));
+            }
+            res.push(IndentedString::new(3, FidlType::Structure, "_ => None,".to_string()));
+            res.push(IndentedString::new(2, FidlType::Structure, "}".to_string()));
+            res.push(IndentedString::new(1, FidlType::Structure, "}".to_string()));
+            res.push(IndentedString::new(0, FidlType::Structure, "}".to_string()));
+        }
         res
     }
 
     fn typedef(&self, typedef: &TypeDef, public: bool) -> Vec<IndentedString> {
+        if typedef.range.is_some() {
+            return vec![IndentedString::new(
+                0,
+                FidlType::File,
+                format!(
+                    "pub type {} = {};",
+                    typedef.name,
+                    Self::type_name(&typedef.type_n, &typedef.range)
+                ),
+            )];
+        }
         vec![IndentedString::new(
             0,
             FidlType::File,
@@ -390,12 +780,130 @@ impl RustCodeGen {
         )]
     }
 
+    fn constant(&self, constant: &Constant, structures: &[Structure]) -> Vec<IndentedString> {
+        // Ranged constants are checked when collected so they can use the plain wire type.
+        // Built in types without a Primitives alias map to the Rust type they are stored as.
+        let mut type_n = match (constant.type_n.as_str(), constant.range) {
+            ("String", _) => "&str".to_string(),
+            (_, Some(range)) => range.wire_type().to_string(),
+            ("Integer", None) => "i64".to_string(),
+            ("Boolean", _) => "bool".to_string(),
+            ("ByteBuffer", _) => "u8".to_string(),
+            _ => constant.type_n.clone(),
+        };
+        // A ByteBuffer is collected as an array of bytes.
+        let array_of_bytes = constant.type_n == "ByteBuffer" && !constant.is_array;
+        if constant.is_array || array_of_bytes {
+            let len = match &constant.value {
+                ConstantValue::Array(values) => values.len(),
+                _ => 0,
+            };
+            type_n = format!("[{}; {}]", type_n, len);
+        }
+        vec![IndentedString::new(
+            0,
+            FidlType::Constant,
+            format!(
+                "pub const {}: {} = {};",
+                constant.name,
+                type_n,
+                Self::constant_value(&constant.value, &constant.type_n, structures)
+            ),
+        )]
+    }
+
+    /// `Integer(min, max)` becomes a range checked wrapper of the smallest integer type that fits.
+    fn type_name(type_n: &str, range: &Option<IntegerRange>) -> String {
+        match range {
+            Some(range) => format!(
+                "RangedInteger<{}, {}, {}>",
+                range.wire_type(),
+                range.min,
+                range.max
+            ),
+            None => type_n.to_string(),
+        }
+    }
+
+    fn constant_value(value: &ConstantValue, type_n: &str, structures: &[Structure]) -> String {
+        match value {
+            ConstantValue::Integer(i) => i.to_string(),
+            ConstantValue::Float(f) => format!("{:?}", f),
+            ConstantValue::Boolean(b) => b.to_string(),
+            ConstantValue::String(s) => format!("{:?}", s),
+            ConstantValue::Array(values) => {
+                let values: Vec<String> = values
+                    .iter()
+                    .map(|v| Self::constant_value(v, type_n, structures))
+                    .collect();
+                format!("[{}]", values.join(", "))
+            }
+            ConstantValue::Struct(fields) => {
+                let structure = structures.iter().find(|s| s.name == type_n);
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|(name, value)| {
+                        let field_type = structure
+                            .and_then(|s| s.contents.iter().find(|f| f.name == *name))
+                            .map_or(name.as_str(), |f| f.type_n.as_str());
+                        format!(
+                            "{}: {}",
+                            name,
+                            Self::constant_value(value, field_type, structures)
+                        )
+                    })
+                    .collect();
+                format!("{} {{ {} }}", type_n, fields.join(", "))
+            }
+        }
+    }
+
+    /// Arrays with a `@length: N` annotation are fixed size, all others are a Vec.
+    fn array_of(element_type: String, length: Option<u64>) -> String {
+        match length {
+            Some(length) => format!("[{}; {}]", element_type, length),
+            None => format!("Vec<{}>", element_type),
+        }
+    }
+
+    fn array_type(&self, array: &ArrayType) -> Vec<IndentedString> {
+        let mut element_type = Self::type_name(&array.type_n, &array.range);
+        if array.is_array {
+            element_type = Self::array_of(element_type, None);
+        }
+        vec![IndentedString::new(
+            0,
+            FidlType::Array,
+            format!(
+                "pub type {} = {};",
+                array.name,
+                Self::array_of(element_type, array.length)
+            ),
+        )]
+    }
+
+    fn map(&self, map: &Map, public: bool) -> Vec<IndentedString> {
+        let key_type = Self::type_name(&map.key_type, &map.key_range);
+        let mut value_type = Self::type_name(&map.value_type, &map.value_range);
+        if map.value_is_array {
+            value_type = Self::array_of(value_type, None);
+        }
+        vec![IndentedString::new(
+            0,
+            FidlType::Map,
+            format!(
+                "pub type {} = HashMap<{}, {}>;",
+                map.name, key_type, value_type
+            ),
+        )]
+    }
+
     fn method(&self, method: &Method) -> Vec<IndentedString> {
         let mut input_params = "".to_string();
         for param in &method.input_parameters {
             input_params += &param.name;
             input_params += ": ";
-            input_params += &param.type_n;
+            input_params += &Self::type_name(&param.type_n, &param.range);
             input_params += ", "
         }
         if input_params.len() != 0 {
@@ -409,12 +917,12 @@ impl RustCodeGen {
             }
             1 => {
                 let single_param = &method.output_parameters[0];
-                output_params = format!("{}", single_param.type_n);
+                output_params = Self::type_name(&single_param.type_n, &single_param.range);
             }
             e => {
                 output_params.push('(');
                 for param in &method.output_parameters {
-                    output_params += &param.type_n;
+                    output_params += &Self::type_name(&param.type_n, &param.range);
                     output_params += ", "
                 }
                 output_params = output_params[0..output_params.len() - 2].to_string();
@@ -422,6 +930,24 @@ impl RustCodeGen {
             }
         }
         let mut res: Vec<IndentedString> = Vec::new();
+        if method.fire_and_forget {
+            res.push(IndentedString::new(
+                0,
+                FidlType::Method,
+                "/// Fire and forget, returns once sent without waiting for a response."
+                    .to_string(),
+            ));
+        }
+        match &method.errors {
+            None => {}
+            Some(MethodError::Inline(enumeration)) => {
+                res.extend(self.enumeration(enumeration, true));
+                output_params = format!("Result<{}, {}>", output_params, enumeration.name);
+            }
+            Some(MethodError::Reference(type_n)) => {
+                output_params = format!("Result<{}, {}>", output_params, type_n);
+            }
+        }
         res.push(IndentedString::new(
             0,
             FidlType::Method,
@@ -435,6 +961,74 @@ impl RustCodeGen {
         res.push(IndentedString::new(0, FidlType::Method, format!("}}")));
         res
     }
+    fn broadcast(&self, broadcast: &Broadcast) -> Vec<IndentedString> {
+        let payload_name = format!("{}Payload", broadcast.name);
+        let mut res: Vec<IndentedString> = Vec::new();
+        res.push(IndentedString::new(
+            0,
+            FidlType::Broadcast,
+            "#[derive(Debug, Serialize, Deserialize, BinarySerde, PartialEq)]".to_string(),
+        ));
+        res.push(IndentedString::new(
+            0,
+            FidlType::Broadcast,
+            "#[repr(C)]".to_string(),
+        ));
+        res.push(IndentedString::new(
+            0,
+            FidlType::Broadcast,
+            format!("pub struct {} {{ ", payload_name),
+        ));
+        for var_dec in &broadcast.output_parameters {
+            if var_dec.is_array {
+                let var_dec = format!(
+                    "pub {}: {},",
+                    var_dec.name,
+                    Self::array_of(
+                        Self::type_name(&var_dec.type_n, &var_dec.range),
+                        var_dec.length
+                    )
+                );
+                res.push(IndentedString::new(1, FidlType::Broadcast, var_dec));
+            } else {
+                let var_dec = format!(
+                    "pub {}: {},",
+                    var_dec.name,
+                    Self::type_name(&var_dec.type_n, &var_dec.range)
+                );
+                res.push(IndentedString::new(1, FidlType::Broadcast, var_dec));
+            }
+        }
+        res.push(IndentedString::new(0, FidlType::Broadcast, format!("}}")));
+        if broadcast.selective {
+            res.push(IndentedString::new(
+                0,
+                FidlType::Broadcast,
+                "/// Selective broadcast, the provider decides which subscribers receive it."
+                    .to_string(),
+            ));
+        }
+        res.push(IndentedString::new(
+            0,
+            FidlType::Broadcast,
+            format!(
+                "pub fn subscribe_{}(ctx: impl FidlContext, callback: impl Fn({}) + 'static) {{",
+                broadcast.name, payload_name
+            ),
+        ));
+        res.push(IndentedString::new(0, FidlType::Broadcast, format!("}}")));
+        res.push(IndentedString::new(
+            0,
+            FidlType::Broadcast,
+            format!(
+                "pub fn unsubscribe_{}(ctx: impl FidlContext, callback: impl Fn({}) + 'static) {{",
+                broadcast.name, payload_name
+            ),
+        ));
+        res.push(IndentedString::new(0, FidlType::Broadcast, format!("}}")));
+        res
+    }
+
     fn enumeration(&self, enumeration: &Enumeration, public: bool) -> Vec<IndentedString> {
         let mut res: Vec<IndentedString> = Vec::new();
         res.push(IndentedString::new(
@@ -1,4 +1,7 @@
-use std::{fmt::Debug, path::PathBuf};
+use std::{
+    fmt::Debug,
+    path::{Component, Path, PathBuf},
+};
 
 use crate::indented_string::IndentedString;
 use minimal_fidl_collect::{
@@ -20,17 +23,85 @@ pub enum GeneratorError {
     #[error["This error means the program has a bug: {0}"]]
     InternalLogicError(String),
     #[error["Could not generate code. {:?}", 0]]
-    CouldNotGeneratCodeForFile(FidlFileRs),
+    CouldNotGeneratCodeForFile(Box<FidlFileRs>),
     #[error["{:?}", 0]]
     FidlFileError(#[from] FileError),
     #[error["{:?}", 0]]
     IoError(#[from] std::io::Error),
 }
+
+/// The interfaces and type collections to generate code for, by name.
+/// Everything is generated when nothing is selected.
+#[derive(Debug, Clone, Default)]
+pub struct Selection {
+    pub interfaces: Vec<String>,
+    pub type_collections: Vec<String>,
+}
+impl Selection {
+    pub fn is_empty(&self) -> bool {
+        self.interfaces.is_empty() && self.type_collections.is_empty()
+    }
+
+    pub fn interface(&self, name: &str) -> bool {
+        self.is_empty() || self.interfaces.iter().any(|n| n == name)
+    }
+
+    pub fn type_collection(&self, name: &str) -> bool {
+        self.is_empty() || self.type_collections.iter().any(|n| n == name)
+    }
+}
+
 pub trait CodeGenerator {
     fn new() -> Self;
     // fn generate_file(&mut self, path: PathBuf, fidl: FidlFile) -> Result<(), GeneratorError>;
-    /// Convenience function if you don't want to filter the files at all. Otherwise use FidlProject to generate each file manually then call
-    /// CodeGenerator::generate_file instead for each you want to use.
-    fn generate_project(&mut self, dir: PathBuf) -> Result<(), GeneratorError>;
+    /// Convenience function if you don't want to filter the files at all. Otherwise use FidlProject to load the files
+    /// then call CodeGenerator::generate_selection with the interfaces and type collections you want to use.
+    fn generate_project(&mut self, dir: PathBuf) -> Result<(), GeneratorError> {
+        let project = FidlProject::new(dir.clone())?;
+        self.generate_selection(&dir, &project, &Selection::default())
+    }
+    /// Generates code for the selected parts of every file in `project`, `dir` is where the project was loaded from.
+    fn generate_selection(
+        &mut self,
+        dir: &Path,
+        project: &FidlProject,
+        selection: &Selection,
+    ) -> Result<(), GeneratorError> {
+        let paths: Vec<&Path> = project.files().map(|(path, _)| path).collect();
+        self.generate_files(dir, project, &paths, selection)
+    }
+    /// Like generate_selection but only for the files of `project` at `paths`, i.e the ones affected by a change.
+    fn generate_files(
+        &mut self,
+        dir: &Path,
+        project: &FidlProject,
+        paths: &[&Path],
+        selection: &Selection,
+    ) -> Result<(), GeneratorError>;
     fn emit_project(&self, target_dir: PathBuf) -> Result<(), GeneratorError>;
+    /// Removes what `emit_project` wrote into `target_dir` for the file at `path`, i.e after it was deleted.
+    fn remove_generated(&self, target_dir: &Path, path: &Path) -> Result<(), GeneratorError>;
+}
+
+/// The struct `structure` extends, when it is declared among `structures` too, i.e in the same
+/// interface or type collection.
+pub(crate) fn local_base<'a>(
+    structure: &Structure,
+    structures: &'a [Structure],
+) -> Option<&'a Structure> {
+    let base = structure.extends.as_ref()?;
+    let name = base.name.rsplit('.').next().unwrap_or(&base.name);
+    structures.iter().find(|s| s.name == name)
+}
+
+/// Where the code generated for `path` goes under `target_dir`.
+/// Generated paths follow the input paths, so roots and `..` are dropped to stay inside `target_dir`.
+pub fn target_path(target_dir: &Path, path: &Path) -> PathBuf {
+    let mut target = target_dir.to_path_buf();
+    for component in path.components() {
+        if let Component::Normal(component) = component {
+            target.push(component);
+        }
+    }
+    target
 }
@@ -10,9 +10,13 @@ pub enum FidlType {
     Enumeration,
     EnumValue,
     Method,
+    Broadcast,
     Attribute,
     Structure,
+    Map,
+    Array,
     TypeCollection,
+    Constant,
 }
 
 #[derive(PartialEq, Debug)]
@@ -10,8 +10,9 @@ mod codegen_py;
 mod codegen_rust;
 mod codegen_trait;
 mod indented_string;
-use codegen_rust::RustCodeGen;
-use codegen_trait::CodeGenerator;
+pub use codegen_py::PythonCodeGen;
+pub use codegen_rust::RustCodeGen;
+pub use codegen_trait::{CodeGenerator, GeneratorError, Selection};
 use indented_string::FidlType;
 use indented_string::IndentedString;
 
@@ -26,9 +27,11 @@ mod tests {
         fs::remove_dir_all,
         io::{stdout, Write},
         path::{Path, PathBuf},
+        process::Command,
     };
 
-    use crate::{codegen_py::PythonCodeGen, CodeGenerator, RustCodeGen};
+    use crate::codegen_trait::target_path;
+    use crate::{codegen_py::PythonCodeGen, CodeGenerator, RustCodeGen, Selection};
 
     // pub fn parse(input: &str) -> Option<BasicPublisher> {
     //     let string = input.to_string();
@@ -367,4 +370,232 @@ mod tests {
         codegen.emit_project(path).unwrap();
         Ok(())
     }
+
+    #[test]
+    fn test_generator_8() {
+        let src = Path::new("tests/test_fidls/test1.fidl");
+        let project = FidlProject::new(src).unwrap();
+        let selection = Selection {
+            interfaces: vec!["MyDerivedInterface".to_string()],
+            type_collections: Vec::new(),
+        };
+        let mut codegen = RustCodeGen::new();
+        codegen
+            .generate_selection(src, &project, &selection)
+            .unwrap();
+        let target = std::env::temp_dir().join("minimal_fidl_generator_8");
+        let _ = remove_dir_all(&target);
+        codegen.emit_project(target.clone()).unwrap();
+        let code =
+            std::fs::read_to_string(target.join("tests/test_fidls/test1.rs")).unwrap();
+        assert!(code.contains("pub mod MyDerivedInterface {"));
+        // The base interface is only flattened into the selected one.
+        assert!(!code.contains("pub mod MyInterface {"));
+        assert!(!code.contains("pub mod MyTypeCollection10 {"));
+    }
+
+    /// The code `G` generates for a project made of the single file `src`.
+    fn generated<G: CodeGenerator + std::fmt::Debug>(name: &str, src: &str) -> String {
+        let dir = std::env::temp_dir().join(name);
+        let _ = remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("test.fidl");
+        std::fs::write(&path, src).unwrap();
+        let project = FidlProject::new(&path).unwrap();
+        let mut codegen = G::new();
+        codegen
+            .generate_selection(&path, &project, &Selection::default())
+            .unwrap();
+        format!("{:?}", codegen)
+    }
+
+    /// Runs `script` with Python, after generating `src` and loading the names of its type collection `name`.
+    fn run_python(dir_name: &str, src: &str, name: &str, script: &str) -> std::process::Output {
+        let dir = std::env::temp_dir().join(dir_name);
+        let _ = remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("test.fidl");
+        std::fs::write(&path, src).unwrap();
+        let project = FidlProject::new(&path).unwrap();
+        let mut codegen = PythonCodeGen::new();
+        codegen
+            .generate_selection(&path, &project, &Selection::default())
+            .unwrap();
+        let out = dir.join("out");
+        codegen.emit_project(out.clone()).unwrap();
+        let root = target_path(&out, &dir);
+        let module = std::fs::read_dir(root.join("test"))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .find(|path| path.file_name().unwrap().to_string_lossy().starts_with(&format!("{}.", name)))
+            .unwrap();
+        let program = format!(
+            "import runpy, sys\nsys.path.insert(0, {:?})\nglobals().update(runpy.run_path({:?}))\n{}",
+            root, module, script
+        );
+        Command::new("python3").arg("-c").arg(program).output().unwrap()
+    }
+
+    #[test]
+    fn test_generator_9() {
+        let src = "package org.example\ntypeCollection MyTypes {\n    map Ranged { Integer(0, 10) to Integer(-5, 5)[] }\n}\n";
+        let python = generated::<PythonCodeGen>("minimal_fidl_generator_9_py", src);
+        assert!(python.contains(
+            "Ranged = dict[RangedInteger(UInt8, 0, 10), List[RangedInteger(Int8, -5, 5)]]"
+        ));
+        let rust = generated::<RustCodeGen>("minimal_fidl_generator_9_rs", src);
+        assert!(rust.contains(
+            "pub type Ranged = HashMap<RangedInteger<UInt8, 0, 10>, Vec<RangedInteger<Int8, -5, 5>>>;"
+        ));
+    }
+
+    #[test]
+    fn test_generator_12() {
+        let src = "package org.example\ntypeCollection MyTypes {\n    struct Reading {\n        Integer(-40, 125) celsius\n        UInt8 sensor\n    }\n}\n";
+        let rust = generated::<RustCodeGen>("minimal_fidl_generator_12_rs", src);
+        let structure: Vec<&str> = rust
+            .lines()
+            .map(|line| line.trim())
+            .skip_while(|line| *line != "pub struct Reading {")
+            .take(4)
+            .collect();
+        assert_eq!(
+            structure,
+            [
+                "pub struct Reading {",
+                "pub celsius: RangedInteger<Int8, -40, 125>,",
+                "pub sensor: UInt8,",
+                "}"
+            ]
+        );
+        // The derives of the struct need every serde trait on the ranged field, reading one checks the range.
+        assert!(rust.contains("#[derive(Debug, Serialize, Deserialize, BinarySerde, PartialEq)]"));
+        assert!(rust.contains("serde::Serialize for RangedInteger<T, MIN, MAX>"));
+        assert!(rust.contains("serde::Deserialize<'de>"));
+        assert!(rust.contains("binary_serde::BinarySerde"));
+        assert!(rust.contains("serde::de::Error::custom"));
+        assert!(rust.contains("Self::new(value).ok_or(binary_serde::DeserializeError::InvalidEnumValue {"));
+    }
+
+    #[test]
+    fn test_generator_10() {
+        let src = "package org.example\ntypeCollection MyTypes {\n    struct Circle extends Shape {\n        Float radius\n    }\n    struct Shape polymorphic {\n        UInt8 colour\n    }\n    struct Point {\n        Float x\n    }\n}\n";
+        let file = FidlProject::generate_file_from_string(src.to_string()).unwrap();
+        let structures = &file.type_collections[0].structures;
+        let circle_tag = structures[0].type_tag("org.example.MyTypes");
+        let shape_tag = structures[1].type_tag("org.example.MyTypes");
+
+        // The base is declared first so the derived class can subclass it.
+        let python = generated::<PythonCodeGen>("minimal_fidl_generator_10_py", src);
+        let shape_class = format!("class Shape(PolymorphicBinarySerdeStruct, type_tag={:#010x}):", shape_tag);
+        let circle_class = format!("class Circle(Shape, type_tag={:#010x}):", circle_tag);
+        assert!(python.find(&shape_class).unwrap() < python.find(&circle_class).unwrap());
+        assert!(python.contains("class Point():"));
+
+        let rust = generated::<RustCodeGen>("minimal_fidl_generator_10_rs", src);
+        assert!(rust.contains(&format!("const TYPE_TAG: u32 = {:#010x};", circle_tag)));
+        assert!(rust.contains("pub enum AnyShape {"));
+        assert!(rust.contains("Circle(Circle),"));
+        assert!(rust.contains("Shape::TYPE_TAG => Shape::binary_deserialize_tagged(buf, endianness).map(Self::Shape),"));
+        assert!(rust.contains("Circle::TYPE_TAG => Circle::binary_deserialize_tagged(buf, endianness).map(Self::Circle),"));
+        assert!(!rust.contains("Point(Point)"));
+    }
+
+    #[test]
+    fn test_generator_11() {
+        let src = "package org.example\ntypeCollection MyTypes {\n    const Integer COUNT = 3\n    const ByteBuffer MAGIC = [0xca, 0xfe]\n    const Boolean ENABLED = true\n    const Point ORIGIN = { x: 1, y: 2 }\n    struct Point {\n        Float x\n        UInt8 y\n    }\n}\n";
+        let rust = generated::<RustCodeGen>("minimal_fidl_generator_11_rs", src);
+        assert!(rust.contains("pub const COUNT: i64 = 3;"));
+        assert!(rust.contains("pub const MAGIC: [u8; 2] = [202, 254];"));
+        assert!(rust.contains("pub const ENABLED: bool = true;"));
+        assert!(rust.contains("pub const ORIGIN: Point = Point { x: 1.0, y: 2 };"));
+    }
+
+    #[test]
+    fn test_generator_13() {
+        let src = "package org.example\ntypeCollection MyTypes {\n    struct Reading {\n        Integer(-128, 127) value\n    }\n}\n";
+        let script = "
+field = Reading.__dataclass_fields__['value'].type
+assert field.__mro__[1] is Int8
+assert Reading(field(-128)).value.value == -128
+assert Reading(field(127)).value.value == 127
+for value in (-129, 128):
+    for wire_type in (field, Int8):
+        try:
+            wire_type(value)
+        except ValueError:
+            continue
+        raise AssertionError(f'{wire_type.__name__}({value}) was accepted')
+";
+        let output = run_python("minimal_fidl_generator_13_py", src, "MyTypes", script);
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    }
+
+    #[test]
+    fn test_generator_14() {
+        let src = "package org.example\ntypeCollection Limits {\n    const Int8 MIN8 = -128\n    const Int8 MAX8 = 127\n    const Int16 MIN16 = -32768\n    const Int16 MAX16 = 32767\n    const Int32 MIN32 = -2147483648\n    const Int32 MAX32 = 2147483647\n    const Int64 MIN64 = -9223372036854775808\n    const Int64 MAX64 = 9223372036854775807\n}\n";
+        let script = "
+for width in (8, 16, 32, 64):
+    assert globals()[f'MIN{width}'].value == -2 ** (width - 1)
+    assert globals()[f'MAX{width}'].value == 2 ** (width - 1) - 1
+";
+        let output = run_python("minimal_fidl_generator_14_py", src, "Limits", script);
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+        let rust = generated::<RustCodeGen>("minimal_fidl_generator_14_rs", src);
+        assert!(rust.contains("pub const MIN8: Int8 = -128;"));
+        assert!(rust.contains("pub const MAX16: Int16 = 32767;"));
+        assert!(rust.contains("pub const MIN64: Int64 = -9223372036854775808;"));
+    }
+
+    #[test]
+    fn test_generator_15() {
+        // Both bindings subscribe and unsubscribe a callback the same way.
+        let src = "package org.example\ninterface Tracker {\n    broadcast moved selective {\n        out {\n            UInt8 x\n        }\n    }\n}\n";
+        let rust = generated::<RustCodeGen>("minimal_fidl_generator_15_rs", src);
+        assert!(rust.contains(
+            "pub fn subscribe_moved(ctx: impl FidlContext, callback: impl Fn(movedPayload) + 'static) {"
+        ));
+        assert!(rust.contains(
+            "pub fn unsubscribe_moved(ctx: impl FidlContext, callback: impl Fn(movedPayload) + 'static) {"
+        ));
+
+        let script = "
+import inspect
+for function in (subscribe_moved, unsubscribe_moved):
+    assert list(inspect.signature(function).parameters) == ['ctx', 'callback']
+    function(None, print)
+";
+        let output = run_python("minimal_fidl_generator_15_py", src, "Tracker", script);
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    }
+
+    #[test]
+    fn test_generator_16() {
+        // A `@length: N` array is fixed size in Rust and length checked in Python.
+        let src = "package org.example\ntypeCollection MyTypes {\n    <** @length: 4 **>\n    array Quad of UInt8\n    struct Colour {\n        <** @length: 3 **>\n        UInt16[] rgb\n        UInt8[] rest\n    }\n}\n";
+        let rust = generated::<RustCodeGen>("minimal_fidl_generator_16_rs", src);
+        assert!(rust.contains("pub type Quad = [UInt8; 4];"));
+        assert!(rust.contains("pub rgb: [UInt16; 3],"));
+        assert!(rust.contains("pub rest: Vec<UInt8>,"));
+
+        let python = generated::<PythonCodeGen>("minimal_fidl_generator_16_py", src);
+        assert!(python.contains("Quad = FixedArray(UInt8, 4)"));
+        assert!(python.contains("rgb: FixedArray(UInt16, 3)"));
+        assert!(python.contains("rest: List[UInt8]"));
+
+        let script = "
+rgb = Colour.__dataclass_fields__['rgb'].type
+assert len(Colour(rgb([UInt16(1), UInt16(2), UInt16(3)]), []).rgb) == 3
+assert len(Quad([UInt8(0)] * 4)) == 4
+for array, elements in ((rgb, [UInt16(1)] * 2), (rgb, [UInt16(1)] * 4), (Quad, [])):
+    try:
+        array(elements)
+    except ValueError:
+        continue
+    raise AssertionError(f'{array.__name__} accepted {len(elements)} elements')
+";
+        let output = run_python("minimal_fidl_generator_16_run", src, "MyTypes", script);
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    }
 }
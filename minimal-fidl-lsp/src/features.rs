@@ -0,0 +1,446 @@
+use std::path::Path;
+
+use lsp_types::{
+    Diagnostic, DiagnosticSeverity, DocumentSymbol, Hover, HoverContents, Location, MarkupContent,
+    MarkupKind, Position, Range, SymbolKind, TextEdit, Url,
+};
+use minimal_fidl_collect::resolver::name_at;
+use minimal_fidl_collect::{
+    Annotation, ArrayType, Constant, Enumeration, FidlFileRs, FidlProject, FileError, Map,
+    Resolver, Span, Structure, TypeDef, Union, VariableDeclaration,
+};
+use minimal_fidl_formatter::Formatter;
+use minimal_fidl_parser::parse;
+
+use crate::position::{position, range};
+
+/// Parse errors of the declarations that had to be skipped, the first error found while collecting
+/// the file and type references that resolve to nothing.
+/// `path` is where the document is saved, for a document without one or that was never saved
+/// references into imported files are not checked.
+pub fn diagnostics(path: Option<&Path>, source: &str) -> Vec<Diagnostic> {
+    let (file, errors) =
+        match FidlProject::generate_file_from_string_with_recovery(source.to_string()) {
+            Ok(recovered) => recovered,
+            Err(err) => return vec![diagnostic(source, &err)],
+        };
+    let mut diagnostics: Vec<Diagnostic> =
+        errors.iter().map(|err| diagnostic(source, err)).collect();
+    let path = path.filter(|path| path.exists());
+    let mut resolver = Resolver::default();
+    let loaded = path.map(|path| resolver.load_source(path, source.to_string()));
+    let unresolved = match (path, loaded) {
+        (Some(path), Some(Ok(_))) => resolver
+            .check()
+            .into_iter()
+            .filter(|err| match err {
+                FileError::UnresolvedTypeRef(file, ..) | FileError::AmbiguousTypeRef(file, ..) => {
+                    file == path
+                }
+                _ => false,
+            })
+            .collect(),
+        (_, Some(Err(err))) => {
+            // Once the document is loaded the error is in one of its imports, its span would be meaningless here.
+            if !resolver.files.is_empty() {
+                diagnostics.push(Diagnostic {
+                    range: Range::default(),
+                    ..diagnostic(source, &err)
+                });
+            }
+            file.undefined_types()
+        }
+        _ => file.undefined_types(),
+    };
+    diagnostics.extend(unresolved.iter().map(|err| diagnostic(source, err)));
+    diagnostics
+}
+
+fn diagnostic(source: &str, error: &FileError) -> Diagnostic {
+    let range = match error.span() {
+        Some(span) => range(source, span.trim(source)),
+        None => Range::default(),
+    };
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::ERROR),
+        source: Some("fidl".to_string()),
+        message: error.to_string(),
+        ..Default::default()
+    }
+}
+
+/// Replaces the whole document with its formatted text, nothing if it does not parse.
+pub fn formatting(source: &str) -> Option<Vec<TextEdit>> {
+    let (parsed, parsed_to, publisher, _) = parse(source);
+    if !parsed || parsed_to != source.len() as u32 {
+        return None;
+    }
+    let formatted = Formatter::new(source, &publisher).format().ok()?;
+    let whole = Range::new(Position::new(0, 0), position(source, source.len() as u32));
+    Some(vec![TextEdit::new(whole, formatted)])
+}
+
+/// Where the type named at `offset` is declared, possibly in an imported file.
+pub fn definition(path: &Path, source: &str, offset: u32) -> Option<Location> {
+    let mut resolver = Resolver::default();
+    resolver.load_source(path, source.to_string()).ok()?;
+    let symbol = resolver.definition(path, offset)?;
+    let declared_in = &resolver.file(&symbol.file)?.source;
+    let uri = Url::from_file_path(symbol.file.canonicalize().ok()?).ok()?;
+    Some(Location::new(
+        uri,
+        range(declared_in, symbol.span.trim(declared_in)),
+    ))
+}
+
+/// The annotations of the type named at `offset`, or of the declaration whose name is at `offset`.
+pub fn hover(path: Option<&Path>, source: &str, offset: u32) -> Option<Hover> {
+    let definition = path.and_then(|path| {
+        let mut resolver = Resolver::default();
+        resolver.load_source(path, source.to_string()).ok()?;
+        let symbol = resolver.definition(path, offset)?;
+        Some((symbol.qualified_name(), symbol.annotations.clone()))
+    });
+    let (title, annotations) = match definition {
+        Some(definition) => definition,
+        None => {
+            let (file, _) =
+                FidlProject::generate_file_from_string_with_recovery(source.to_string()).ok()?;
+            let name = name_at(source, offset)?;
+            let declarations = declarations(&file);
+            let declaration = find(&declarations, offset, name)?;
+            (
+                format!("{} {}", declaration.keyword, declaration.name),
+                declaration.annotations.to_vec(),
+            )
+        }
+    };
+    let mut value = format!("```fidl\n{}\n```", title);
+    for annotation in &annotations {
+        value.push_str(&format!(
+            "\n\n**@{}**: {}",
+            annotation.name,
+            annotation.contents.trim()
+        ));
+    }
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value,
+        }),
+        range: None,
+    })
+}
+
+/// The interfaces and type collections of the document with everything declared in them.
+pub fn document_symbols(source: &str) -> Option<Vec<DocumentSymbol>> {
+    let (file, _) =
+        FidlProject::generate_file_from_string_with_recovery(source.to_string()).ok()?;
+    Some(
+        declarations(&file)
+            .into_iter()
+            .map(|declaration| declaration.into_symbol(source))
+            .collect(),
+    )
+}
+
+/// A named part of a file such as an interface, a method, a struct or one of its fields.
+struct Declaration<'a> {
+    keyword: &'static str,
+    kind: SymbolKind,
+    name: &'a str,
+    span: Span,
+    annotations: &'a [Annotation],
+    children: Vec<Declaration<'a>>,
+}
+impl<'a> Declaration<'a> {
+    fn new(
+        keyword: &'static str,
+        kind: SymbolKind,
+        name: &'a str,
+        span: Span,
+        annotations: &'a [Annotation],
+    ) -> Self {
+        Self {
+            keyword,
+            kind,
+            name,
+            span,
+            annotations,
+            children: Vec::new(),
+        }
+    }
+
+    fn with_children(mut self, mut children: Vec<Declaration<'a>>) -> Self {
+        children.sort_by_key(|child| child.span.start);
+        self.children = children;
+        self
+    }
+
+    fn field(field: &'a VariableDeclaration) -> Self {
+        Self::new(
+            "field",
+            SymbolKind::FIELD,
+            &field.name,
+            field.span(),
+            &field.annotations,
+        )
+    }
+
+    #[allow(deprecated)] // `deprecated` has to be set even though `tags` replaced it.
+    fn into_symbol(self, source: &str) -> DocumentSymbol {
+        let span = self.span.trim(source);
+        let selection = name_span(source, span, self.name).unwrap_or(span);
+        let children: Vec<DocumentSymbol> = self
+            .children
+            .into_iter()
+            .map(|child| child.into_symbol(source))
+            .collect();
+        DocumentSymbol {
+            // Names can not be empty, i.e for an unnamed type collection.
+            name: match self.name.is_empty() {
+                true => self.keyword.to_string(),
+                false => self.name.to_string(),
+            },
+            detail: Some(self.keyword.to_string()),
+            kind: self.kind,
+            tags: None,
+            deprecated: None,
+            range: range(source, span),
+            selection_range: range(source, selection),
+            children: (!children.is_empty()).then_some(children),
+        }
+    }
+}
+
+fn declarations(file: &FidlFileRs) -> Vec<Declaration<'_>> {
+    let mut declarations: Vec<Declaration> = Vec::new();
+    for interface in &file.interfaces {
+        let mut children: Vec<Declaration> = Vec::new();
+        for attribute in &interface.attributes {
+            children.push(Declaration::new(
+                "attribute",
+                SymbolKind::PROPERTY,
+                &attribute.name,
+                attribute.span(),
+                &attribute.annotations,
+            ));
+        }
+        for method in &interface.methods {
+            let parameters = method
+                .input_parameters
+                .iter()
+                .chain(&method.output_parameters)
+                .map(Declaration::field)
+                .collect();
+            children.push(
+                Declaration::new(
+                    "method",
+                    SymbolKind::METHOD,
+                    &method.name,
+                    method.span(),
+                    &method.annotations,
+                )
+                .with_children(parameters),
+            );
+        }
+        for broadcast in &interface.broadcasts {
+            let parameters = broadcast
+                .output_parameters
+                .iter()
+                .map(Declaration::field)
+                .collect();
+            children.push(
+                Declaration::new(
+                    "broadcast",
+                    SymbolKind::EVENT,
+                    &broadcast.name,
+                    broadcast.span(),
+                    &broadcast.annotations,
+                )
+                .with_children(parameters),
+            );
+        }
+        types(
+            &mut children,
+            &interface.typedefs,
+            &interface.structures,
+            &interface.unions,
+            &interface.enumerations,
+            &interface.maps,
+            &interface.arrays,
+            &interface.constants,
+        );
+        declarations.push(
+            Declaration::new(
+                "interface",
+                SymbolKind::INTERFACE,
+                &interface.name,
+                interface.span(),
+                &interface.annotations,
+            )
+            .with_children(children),
+        );
+    }
+    for type_collection in &file.type_collections {
+        let mut children: Vec<Declaration> = Vec::new();
+        types(
+            &mut children,
+            &type_collection.typedefs,
+            &type_collection.structures,
+            &type_collection.unions,
+            &type_collection.enumerations,
+            &type_collection.maps,
+            &type_collection.arrays,
+            &type_collection.constants,
+        );
+        declarations.push(
+            Declaration::new(
+                "typeCollection",
+                SymbolKind::NAMESPACE,
+                &type_collection.name,
+                type_collection.span(),
+                &type_collection.annotations,
+            )
+            .with_children(children),
+        );
+    }
+    declarations.sort_by_key(|declaration| declaration.span.start);
+    declarations
+}
+
+#[allow(clippy::too_many_arguments)]
+fn types<'a>(
+    declarations: &mut Vec<Declaration<'a>>,
+    typedefs: &'a [TypeDef],
+    structures: &'a [Structure],
+    unions: &'a [Union],
+    enumerations: &'a [Enumeration],
+    maps: &'a [Map],
+    arrays: &'a [ArrayType],
+    constants: &'a [Constant],
+) {
+    for typedef in typedefs {
+        declarations.push(Declaration::new(
+            "typedef",
+            SymbolKind::TYPE_PARAMETER,
+            &typedef.name,
+            typedef.span(),
+            &typedef.annotations,
+        ));
+    }
+    for structure in structures {
+        let fields = structure.contents.iter().map(Declaration::field).collect();
+        declarations.push(
+            Declaration::new(
+                "struct",
+                SymbolKind::STRUCT,
+                &structure.name,
+                structure.span(),
+                &structure.annotations,
+            )
+            .with_children(fields),
+        );
+    }
+    for union in unions {
+        let fields = union.contents.iter().map(Declaration::field).collect();
+        declarations.push(
+            Declaration::new(
+                "union",
+                SymbolKind::STRUCT,
+                &union.name,
+                union.span(),
+                &union.annotations,
+            )
+            .with_children(fields),
+        );
+    }
+    for enumeration in enumerations {
+        let values = enumeration
+            .values
+            .iter()
+            .map(|value| {
+                Declaration::new(
+                    "enumerator",
+                    SymbolKind::ENUM_MEMBER,
+                    &value.name,
+                    value.span(),
+                    &value.annotations,
+                )
+            })
+            .collect();
+        declarations.push(
+            Declaration::new(
+                "enumeration",
+                SymbolKind::ENUM,
+                &enumeration.name,
+                enumeration.span(),
+                &enumeration.annotations,
+            )
+            .with_children(values),
+        );
+    }
+    for map in maps {
+        declarations.push(Declaration::new(
+            "map",
+            SymbolKind::OBJECT,
+            &map.name,
+            map.span(),
+            &map.annotations,
+        ));
+    }
+    for array in arrays {
+        declarations.push(Declaration::new(
+            "array",
+            SymbolKind::ARRAY,
+            &array.name,
+            array.span(),
+            &array.annotations,
+        ));
+    }
+    for constant in constants {
+        declarations.push(Declaration::new(
+            "const",
+            SymbolKind::CONSTANT,
+            &constant.name,
+            constant.span(),
+            &constant.annotations,
+        ));
+    }
+}
+
+/// The innermost declaration around `offset` that is named `name`.
+fn find<'a, 'b>(
+    declarations: &'b [Declaration<'a>],
+    offset: u32,
+    name: &str,
+) -> Option<&'b Declaration<'a>> {
+    declarations
+        .iter()
+        .filter(|d| d.span.start <= offset && offset <= d.span.end)
+        .find_map(|d| find(&d.children, offset, name).or((d.name == name).then_some(d)))
+}
+
+/// Where `name` appears as a whole word in the declaration, after any annotation block.
+fn name_span(source: &str, declaration: Span, name: &str) -> Option<Span> {
+    if name.is_empty() {
+        return None;
+    }
+    let text = &source[declaration.start as usize..declaration.end as usize];
+    let skip = match text.starts_with("<**") {
+        true => text.find("**>")? + 3,
+        false => 0,
+    };
+    let is_name = |c: char| c.is_alphanumeric() || c == '_';
+    text[skip..]
+        .match_indices(name)
+        .map(|(index, _)| skip + index)
+        .find(|index| {
+            !text[..*index].ends_with(is_name) && !text[index + name.len()..].starts_with(is_name)
+        })
+        .map(|index| {
+            let start = declaration.start + index as u32;
+            Span::new(start, start + name.len() as u32)
+        })
+}
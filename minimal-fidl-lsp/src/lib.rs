@@ -0,0 +1,4 @@
+mod features;
+mod position;
+mod server;
+pub use server::{capabilities, run, Server};
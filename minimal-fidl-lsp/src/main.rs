@@ -0,0 +1,11 @@
+use lsp_server::Connection;
+use std::error::Error;
+
+fn main() -> Result<(), Box<dyn Error + Sync + Send>> {
+    // The protocol runs over stdin and stdout so nothing else may print to stdout.
+    let (connection, io_threads) = Connection::stdio();
+    minimal_fidl_lsp::run(&connection)?;
+    drop(connection);
+    io_threads.join()?;
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use lsp_types::{Position, Range};
+use minimal_fidl_collect::Span;
+
+/// The LSP position of a byte offset, LSP counts columns in UTF-16 code units.
+pub fn position(source: &str, offset: u32) -> Position {
+    let mut offset = (offset as usize).min(source.len());
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let line_start = source[..offset].rfind('\n').map_or(0, |index| index + 1);
+    let line = source[..line_start].matches('\n').count();
+    let character = source[line_start..offset].encode_utf16().count();
+    Position::new(line as u32, character as u32)
+}
+
+/// The byte offset of an LSP position, positions past the end of a line are moved back to its end.
+pub fn offset(source: &str, position: Position) -> u32 {
+    let line_start = match position.line {
+        0 => 0,
+        line => match source.match_indices('\n').nth(line as usize - 1) {
+            Some((index, _)) => index + 1,
+            None => return source.len() as u32,
+        },
+    };
+    let mut units: u32 = 0;
+    for (index, c) in source[line_start..].char_indices() {
+        if c == '\n' || units >= position.character {
+            return (line_start + index) as u32;
+        }
+        units += c.len_utf16() as u32;
+    }
+    source.len() as u32
+}
+
+pub fn range(source: &str, span: Span) -> Range {
+    Range::new(position(source, span.start), position(source, span.end))
+}
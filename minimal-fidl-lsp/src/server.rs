@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+    Notification as NotificationTrait, PublishDiagnostics,
+};
+use lsp_types::request::{
+    DocumentSymbolRequest, Formatting, GotoDefinition, HoverRequest, Request as RequestTrait,
+};
+use lsp_types::{
+    DocumentSymbolResponse, GotoDefinitionResponse, OneOf, PublishDiagnosticsParams,
+    ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+};
+
+use crate::features;
+use crate::position::offset;
+
+pub fn capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        document_formatting_provider: Some(OneOf::Left(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        hover_provider: Some(true.into()),
+        document_symbol_provider: Some(OneOf::Left(true)),
+        ..Default::default()
+    }
+}
+
+/// Serves requests on `connection` until the client shuts the server down.
+pub fn run(connection: &Connection) -> Result<(), Box<dyn Error + Sync + Send>> {
+    connection.initialize(serde_json::to_value(capabilities())?)?;
+    let mut server = Server::default();
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    return Ok(());
+                }
+                let response = server.handle_request(request);
+                connection.sender.send(Message::Response(response))?;
+            }
+            Message::Notification(notification) => {
+                if let Some(notification) = server.handle_notification(notification) {
+                    connection
+                        .sender
+                        .send(Message::Notification(notification))?;
+                }
+            }
+            Message::Response(_) => {}
+        }
+    }
+    Ok(())
+}
+
+/// The text of every open document, everything else is worked out from it on each request.
+#[derive(Debug, Default)]
+pub struct Server {
+    documents: HashMap<Url, String>,
+}
+impl Server {
+    pub fn handle_request(&self, request: Request) -> Response {
+        match request.method.as_str() {
+            Formatting::METHOD => respond::<Formatting>(request, |params| {
+                let source = self.documents.get(&params.text_document.uri)?;
+                features::formatting(source)
+            }),
+            GotoDefinition::METHOD => respond::<GotoDefinition>(request, |params| {
+                let document = params.text_document_position_params;
+                let source = self.documents.get(&document.text_document.uri)?;
+                let path = document.text_document.uri.to_file_path().ok()?;
+                let offset = offset(source, document.position);
+                let location = features::definition(&path, source, offset)?;
+                Some(GotoDefinitionResponse::Scalar(location))
+            }),
+            HoverRequest::METHOD => respond::<HoverRequest>(request, |params| {
+                let document = params.text_document_position_params;
+                let source = self.documents.get(&document.text_document.uri)?;
+                let path = document.text_document.uri.to_file_path().ok();
+                let offset = offset(source, document.position);
+                features::hover(path.as_deref(), source, offset)
+            }),
+            DocumentSymbolRequest::METHOD => respond::<DocumentSymbolRequest>(request, |params| {
+                let source = self.documents.get(&params.text_document.uri)?;
+                let symbols = features::document_symbols(source)?;
+                Some(DocumentSymbolResponse::Nested(symbols))
+            }),
+            method => Response::new_err(
+                request.id,
+                ErrorCode::MethodNotFound as i32,
+                format!("Unsupported request: {}", method),
+            ),
+        }
+    }
+
+    /// Keeps track of open documents, returns the diagnostics to publish after one changed.
+    pub fn handle_notification(&mut self, notification: Notification) -> Option<Notification> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params = params::<DidOpenTextDocument>(notification)?;
+                let document = params.text_document;
+                self.documents.insert(document.uri.clone(), document.text);
+                Some(self.publish_diagnostics(document.uri, Some(document.version)))
+            }
+            DidChangeTextDocument::METHOD => {
+                let params = params::<DidChangeTextDocument>(notification)?;
+                // Only full document syncing is offered so the last change is the whole text.
+                let text = params.content_changes.into_iter().last()?.text;
+                let document = params.text_document;
+                self.documents.insert(document.uri.clone(), text);
+                Some(self.publish_diagnostics(document.uri, Some(document.version)))
+            }
+            DidCloseTextDocument::METHOD => {
+                let params = params::<DidCloseTextDocument>(notification)?;
+                self.documents.remove(&params.text_document.uri);
+                // Clears the diagnostics of the closed document.
+                let params =
+                    PublishDiagnosticsParams::new(params.text_document.uri, Vec::new(), None);
+                Some(Notification::new(
+                    PublishDiagnostics::METHOD.to_string(),
+                    params,
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    fn publish_diagnostics(&self, uri: Url, version: Option<i32>) -> Notification {
+        let source = &self.documents[&uri];
+        let path = uri.to_file_path().ok();
+        let diagnostics = features::diagnostics(path.as_deref(), source);
+        let params = PublishDiagnosticsParams::new(uri, diagnostics, version);
+        Notification::new(PublishDiagnostics::METHOD.to_string(), params)
+    }
+}
+
+fn params<N: NotificationTrait>(notification: Notification) -> Option<N::Params> {
+    serde_json::from_value(notification.params).ok()
+}
+
+fn respond<R: RequestTrait>(
+    request: Request,
+    handler: impl FnOnce(R::Params) -> R::Result,
+) -> Response {
+    match serde_json::from_value::<R::Params>(request.params) {
+        Ok(params) => Response::new_ok(request.id, handler(params)),
+        Err(err) => Response::new_err(request.id, ErrorCode::InvalidParams as i32, err.to_string()),
+    }
+}
@@ -0,0 +1,280 @@
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+/// Talks to the server binary over stdio the same way an editor would.
+struct Client {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: i64,
+    /// Notifications received while waiting for a response.
+    notifications: Vec<Value>,
+}
+impl Client {
+    fn start() -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_minimal-fidl-lsp"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let stdin = child.stdin.take().unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        Self {
+            child,
+            stdin,
+            stdout,
+            next_id: 1,
+            notifications: Vec::new(),
+        }
+    }
+
+    fn send(&mut self, message: Value) {
+        let body = message.to_string();
+        write!(self.stdin, "Content-Length: {}\r\n\r\n{}", body.len(), body).unwrap();
+        self.stdin.flush().unwrap();
+    }
+
+    fn receive(&mut self) -> Value {
+        let mut length: usize = 0;
+        loop {
+            let mut header = String::new();
+            self.stdout.read_line(&mut header).unwrap();
+            let header = header.trim();
+            if header.is_empty() {
+                break;
+            }
+            if let Some(value) = header.strip_prefix("Content-Length: ") {
+                length = value.parse().unwrap();
+            }
+        }
+        let mut body = vec![0; length];
+        self.stdout.read_exact(&mut body).unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.send(json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params}));
+        loop {
+            let message = self.receive();
+            if message["id"] == id {
+                assert!(message.get("error").is_none(), "{}", message);
+                return message["result"].clone();
+            }
+            self.notifications.push(message);
+        }
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        self.send(json!({"jsonrpc": "2.0", "method": method, "params": params}));
+    }
+
+    fn notification(&mut self, method: &str) -> Value {
+        if let Some(index) = self
+            .notifications
+            .iter()
+            .position(|n| n["method"] == method)
+        {
+            return self.notifications.remove(index)["params"].clone();
+        }
+        loop {
+            let message = self.receive();
+            if message["method"] == method {
+                return message["params"].clone();
+            }
+        }
+    }
+
+    fn shutdown(mut self) {
+        self.request("shutdown", Value::Null);
+        self.notify("exit", Value::Null);
+        assert!(self.child.wait().unwrap().success());
+    }
+}
+
+fn uri(path: &Path) -> String {
+    format!("file://{}", path.canonicalize().unwrap().display())
+}
+
+fn at(uri: &str, line: u32, character: u32) -> Value {
+    json!({"textDocument": {"uri": uri}, "position": {"line": line, "character": character}})
+}
+
+const TYPES: &str = "package org.example.types
+
+typeCollection Common {
+    <** @description: A point on the screen **>
+    struct Point {
+        Int32 x
+        Int32 y
+    }
+}
+";
+
+const MAIN: &str = "package org.example.app
+
+import org.example.types.Common.* from \"types.fidl\"
+
+interface App {
+    <** @description: Moves the cursor **>
+    method move {
+        in {
+            Point to
+        }
+    }
+    metod broken {
+    }
+    method jump {
+        in {
+            Missing to
+        }
+    }
+}
+";
+
+#[test]
+fn test_stdio_1() {
+    let dir = std::env::temp_dir().join("minimal_fidl_lsp_stdio_1");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("types.fidl"), TYPES).unwrap();
+    std::fs::write(dir.join("main.fidl"), MAIN).unwrap();
+    let main = uri(&dir.join("main.fidl"));
+
+    let mut client = Client::start();
+    let initialized = client.request("initialize", json!({"capabilities": {}}));
+    let capabilities = &initialized["capabilities"];
+    assert_eq!(capabilities["hoverProvider"], true);
+    assert_eq!(capabilities["definitionProvider"], true);
+    assert_eq!(capabilities["documentFormattingProvider"], true);
+    assert_eq!(capabilities["documentSymbolProvider"], true);
+    client.notify("initialized", json!({}));
+
+    client.notify(
+        "textDocument/didOpen",
+        json!({"textDocument": {"uri": main, "languageId": "fidl", "version": 1, "text": MAIN}}),
+    );
+    let published = client.notification("textDocument/publishDiagnostics");
+    assert_eq!(published["uri"], main);
+    let diagnostics = published["diagnostics"].as_array().unwrap();
+    let lines: Vec<&Value> = diagnostics
+        .iter()
+        .map(|d| &d["range"]["start"]["line"])
+        .collect();
+    assert_eq!(lines, vec![11, 15]);
+    assert!(diagnostics[0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("found `metod`"));
+    assert!(diagnostics[1]["message"]
+        .as_str()
+        .unwrap()
+        .contains("Missing"));
+
+    // `Point` in `Point to`.
+    let definition = client.request("textDocument/definition", at(&main, 8, 13));
+    assert_eq!(definition["uri"], uri(&dir.join("types.fidl")));
+    assert_eq!(
+        definition["range"]["start"],
+        json!({"line": 3, "character": 4})
+    );
+
+    let hover = client.request("textDocument/hover", at(&main, 8, 13));
+    let hover = hover["contents"]["value"].as_str().unwrap();
+    assert!(hover.contains("org.example.types.Common.Point"));
+    assert!(hover.contains("A point on the screen"));
+    // The name of a method.
+    let hover = client.request("textDocument/hover", at(&main, 6, 12));
+    let hover = hover["contents"]["value"].as_str().unwrap();
+    assert!(hover.contains("method move"));
+    assert!(hover.contains("Moves the cursor"));
+
+    let symbols = client.request(
+        "textDocument/documentSymbol",
+        json!({"textDocument": {"uri": main}}),
+    );
+    assert_eq!(symbols[0]["name"], "App");
+    let methods: Vec<&Value> = symbols[0]["children"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|child| &child["name"])
+        .collect();
+    assert_eq!(methods, vec!["move", "jump"]);
+    assert_eq!(
+        symbols[0]["children"][0]["selectionRange"]["start"],
+        json!({"line": 6, "character": 11})
+    );
+
+    // A fixed and badly indented version of the document.
+    let text = "package org.example.app\ninterface App {\nmethod move {\n}\n}\n";
+    client.notify(
+        "textDocument/didChange",
+        json!({"textDocument": {"uri": main, "version": 2}, "contentChanges": [{"text": text}]}),
+    );
+    let published = client.notification("textDocument/publishDiagnostics");
+    assert_eq!(published["version"], 2);
+    assert_eq!(published["diagnostics"], json!([]));
+    let edits = client.request(
+        "textDocument/formatting",
+        json!({"textDocument": {"uri": main}, "options": {"tabSize": 4, "insertSpaces": true}}),
+    );
+    assert_eq!(edits[0]["range"]["end"], json!({"line": 5, "character": 0}));
+    assert!(edits[0]["newText"]
+        .as_str()
+        .unwrap()
+        .contains("\n    method move {"));
+
+    client.shutdown();
+}
+
+#[test]
+fn test_stdio_2() {
+    let dir = std::env::temp_dir().join("minimal_fidl_lsp_stdio_2");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("types.fidl"), TYPES).unwrap();
+    let text = MAIN.replace("types.fidl", "missing.fidl");
+    std::fs::write(dir.join("main.fidl"), &text).unwrap();
+    // Never saved, it only exists in the editor.
+    let unsaved = format!("{}/unsaved.fidl", uri(&dir));
+
+    let mut client = Client::start();
+    client.request("initialize", json!({"capabilities": {}}));
+    client.notify("initialized", json!({}));
+
+    client.notify(
+        "textDocument/didOpen",
+        json!({"textDocument": {"uri": unsaved, "languageId": "fidl", "version": 1, "text": MAIN}}),
+    );
+    let published = client.notification("textDocument/publishDiagnostics");
+    assert_eq!(published["uri"], unsaved);
+    let lines: Vec<&Value> = published["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| &d["range"]["start"]["line"])
+        .collect();
+    // Only the parse error, nothing about a file that could not be read.
+    assert_eq!(lines, vec![11]);
+
+    let main = uri(&dir.join("main.fidl"));
+    client.notify(
+        "textDocument/didOpen",
+        json!({"textDocument": {"uri": main, "languageId": "fidl", "version": 1, "text": text}}),
+    );
+    let published = client.notification("textDocument/publishDiagnostics");
+    assert_eq!(published["uri"], main);
+    let diagnostics = published["diagnostics"].as_array().unwrap();
+    let import = diagnostics
+        .iter()
+        .find(|d| d["range"]["start"] == json!({"line": 0, "character": 0}))
+        .unwrap();
+    assert!(import["message"]
+        .as_str()
+        .unwrap()
+        .contains("\"missing.fidl\" which could not be read"));
+
+    client.shutdown();
+}
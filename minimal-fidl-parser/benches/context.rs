@@ -0,0 +1,103 @@
+use criterion::{criterion_group, BenchmarkId, Criterion};
+use minimal_fidl_parser::{parse_with_context, BasicContext, Context, SparseContext, RULES_SIZE};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Tracks the most memory allocated at once so the contexts can be compared on it.
+struct PeakAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let allocated = ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(allocated, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: PeakAllocator = PeakAllocator;
+
+/// A file with `declarations` copies of the coverage interface, in the range of the largest FIDL files seen.
+fn source(declarations: usize) -> String {
+    let declaration = std::fs::read_to_string("tests/grammar_test_files/05-CoverageInterface.fidl")
+        .unwrap()
+        .replacen("package", "//", 1);
+    format!("package org.example\n{}", declaration.repeat(declarations))
+}
+
+/// Peak bytes allocated while parsing `src` with a fresh context.
+fn peak_memory<T: Context<P = minimal_fidl_parser::BasicPublisher>>(
+    src: &str,
+    context: impl FnOnce() -> T,
+) -> usize {
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    PEAK.store(before, Ordering::Relaxed);
+    let context = RefCell::new(context());
+    let (result, position, _, _) = parse_with_context(src, &context);
+    assert!(result && position == src.len() as u32);
+    drop(context);
+    PEAK.load(Ordering::Relaxed) - before
+}
+
+fn memory() {
+    println!("Peak memory while parsing");
+    for declarations in [1, 10, 100] {
+        let src = source(declarations);
+        let basic = peak_memory(&src, || BasicContext::new(src.len(), RULES_SIZE as usize));
+        let sparse = peak_memory(&src, || SparseContext::new(src.len(), RULES_SIZE as usize));
+        println!(
+            "{:>9} bytes of source: BasicContext {:>11} bytes, SparseContext {:>11} bytes",
+            src.len(),
+            basic,
+            sparse
+        );
+    }
+}
+
+fn time(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for declarations in [1, 10, 100] {
+        let src = source(declarations);
+        group.bench_with_input(
+            BenchmarkId::new("BasicContext", src.len()),
+            &src,
+            |b, src| {
+                let context = RefCell::new(BasicContext::new(src.len(), RULES_SIZE as usize));
+                b.iter(|| parse_with_context(src, &context))
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("SparseContext", src.len()),
+            &src,
+            |b, src| {
+                let context = RefCell::new(SparseContext::new(src.len(), RULES_SIZE as usize));
+                b.iter(|| parse_with_context(src, &context))
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = time
+}
+
+fn main() {
+    memory();
+    benches();
+    Criterion::default().configure_from_args().final_summary();
+}
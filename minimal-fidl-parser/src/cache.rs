@@ -33,6 +33,14 @@ impl BasicCache {
         }
     }
 
+    /// Forgets every result so the cache can be used for another source, the maps keep their capacity.
+    pub fn reset(&mut self) {
+        self.cache.clear();
+        self.left_recursion_cache.clear();
+        self.heads.clear();
+        self.current_active_left_recursion = None;
+    }
+
     pub fn get_current_active_lr_position(&self) -> Option<(Rules, u32)> {
         println!(
             "Getting Current Active LR Position: {:?}",
@@ -210,6 +218,156 @@ impl BasicCache {
     }
 }
 
+/// What a context needs from the cache it memoizes results in, the rest is done through `BasicCache`.
+pub trait ContextCache: core::fmt::Debug {
+    fn new() -> Self;
+    fn reset(&mut self);
+    fn insert(
+        &mut self,
+        rule: Rules,
+        is_true: bool,
+        start_position: u32,
+        end_position: u32,
+        key: Key,
+    );
+    /// How many bytes of source to reserve tree nodes for up front.
+    fn reserved_source_size(size_of_source: usize) -> usize;
+    /// Forgets the successful results of rules whose node is `key` or after it, i.e the ones parsed
+    /// inside a rule that failed, so those nodes can be dropped from the tree.
+    /// Returns false if this cache keeps every result, the nodes then have to stay.
+    fn forget_from(&mut self, key: Key) -> bool;
+    fn results(&self) -> &BasicCache;
+    fn results_mut(&mut self) -> &mut BasicCache;
+}
+impl ContextCache for BasicCache {
+    fn new() -> Self {
+        BasicCache::new()
+    }
+    fn reset(&mut self) {
+        BasicCache::reset(self);
+    }
+    fn insert(
+        &mut self,
+        rule: Rules,
+        is_true: bool,
+        start_position: u32,
+        end_position: u32,
+        key: Key,
+    ) {
+        BasicCache::insert(self, rule, is_true, start_position, end_position, key);
+    }
+    fn reserved_source_size(size_of_source: usize) -> usize {
+        size_of_source
+    }
+    fn forget_from(&mut self, _key: Key) -> bool {
+        false
+    }
+    fn results(&self) -> &BasicCache {
+        self
+    }
+    fn results_mut(&mut self) -> &mut BasicCache {
+        self
+    }
+}
+
+/// A cache that only keeps results that start within `window` bytes of the furthest position
+/// parsed so far, so its size does not grow with the length of the source.
+/// Backtracking further than that parses the rule again, the result is the same only slower.
+#[derive(Debug)]
+pub struct SparseCache {
+    cache: BasicCache,
+    window: u32,
+    /// Results that start before this have been evicted.
+    evicted_before: u32,
+    /// The successful results still cached in the order they were inserted, failed results point at no
+    /// node so they are never forgotten.
+    inserted: Vec<(Rules, u32, Key)>,
+}
+
+impl SparseCache {
+    /// How far back in bytes results are kept by `ContextCache::new`.
+    pub const DEFAULT_WINDOW: u32 = 4096;
+
+    pub fn with_window(window: u32) -> Self {
+        SparseCache {
+            cache: BasicCache::new(),
+            window,
+            evicted_before: 0,
+            inserted: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.cache.len()
+    }
+}
+impl ContextCache for SparseCache {
+    fn new() -> Self {
+        Self::with_window(Self::DEFAULT_WINDOW)
+    }
+
+    fn reset(&mut self) {
+        self.cache.reset();
+        self.evicted_before = 0;
+        self.inserted.clear();
+    }
+
+    fn insert(
+        &mut self,
+        rule: Rules,
+        is_true: bool,
+        start_position: u32,
+        end_position: u32,
+        key: Key,
+    ) {
+        self.cache
+            .insert(rule, is_true, start_position, end_position, key);
+        if is_true {
+            self.inserted.push((rule, start_position, key));
+        }
+        // Evicting once the parse is two windows ahead keeps the cost linear in the number of results.
+        if start_position
+            >= self
+                .evicted_before
+                .saturating_add(self.window.saturating_mul(2))
+        {
+            let evicted_before = start_position - self.window;
+            self.cache
+                .cache
+                .retain(|(_, position), _| *position >= evicted_before);
+            self.inserted
+                .retain(|(_, position, _)| *position >= evicted_before);
+            self.evicted_before = evicted_before;
+        }
+    }
+
+    fn reserved_source_size(_size_of_source: usize) -> usize {
+        0
+    }
+
+    fn forget_from(&mut self, key: Key) -> bool {
+        // Nodes are reserved when a rule starts and cached when it ends, so everything cached after
+        // the rule at `key` started has a node after it.
+        let key = usize::from(key);
+        while let Some(&(rule, start_position, inserted)) = self.inserted.last() {
+            if usize::from(inserted) < key {
+                break;
+            }
+            self.cache.cache.remove(&(rule, start_position));
+            self.inserted.pop();
+        }
+        true
+    }
+
+    // Left recursion results are rare and needed until the recursion finishes so they are never evicted.
+    fn results(&self) -> &BasicCache {
+        &self.cache
+    }
+    fn results_mut(&mut self) -> &mut BasicCache {
+        &mut self.cache
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 
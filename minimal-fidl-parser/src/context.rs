@@ -1,7 +1,9 @@
 #[allow(unused_imports)] // So that I don't need to keep adding or removing whilst testing
 use super::Key;
 use crate::{
-    cache::{DirectLeftRecursionCache, Head, IndirectLeftRecursionCache, LR},
+    cache::{
+        ContextCache, DirectLeftRecursionCache, Head, IndirectLeftRecursionCache, SparseCache, LR,
+    },
     publisher::{DirectLeftRecursionPublisher, IndirectLeftRecursionPublisher},
     Rules,
 };
@@ -22,6 +24,9 @@ where
     #[allow(dead_code)]
 
     fn new(size_of_source: usize, number_of_rules: usize) -> Self;
+    /// Readies the context to parse another source without giving up what it has allocated,
+    /// parsing afterwards gives the same tree as a new context would.
+    fn reset(&mut self, size_of_source: usize, number_of_rules: usize);
     #[allow(dead_code)]
     fn print_cache(&self);
     #[allow(dead_code)]
@@ -68,6 +73,8 @@ where
     fn remove_from_eval_set(&mut self, head_index: (Rules, u32), rule: Rules);
     fn reinitialize_eval_set(&mut self, rule: Rules, start_position: u32);
     fn get_publisher(self) -> Self::P;
+    /// The tree of the last parse, for when the context is going to be reset and used again.
+    fn publisher(&self) -> &Self::P;
     fn clear_node_of_children(&mut self, node: Key);
     fn eval_set_is_empty(&self, start_position: u32, rule: Rules) -> bool;
     fn reset_head(&mut self, rule: Rules, start_position: u32);
@@ -75,12 +82,38 @@ where
     fn print_node(&self, node: Key);
     fn get_current_active_lr_position(&self) -> Option<(Rules, u32)>;
     fn set_current_active_lr_position(&mut self, position: Option<(Rules, u32)>);
+    /// Drops the node at `key` and everything parsed inside it after its rule failed.
+    /// Returns false if the context keeps failed nodes, the node is then updated and connected as usual.
+    fn discard_failed(&mut self, key: Key) -> bool;
 }
 
-pub struct BasicContext {
-    cache: BasicCache,
+/// Memoizes results in the cache `C` and builds the tree in a `BasicPublisher`.
+pub struct CachedContext<C> {
+    cache: C,
     publisher: BasicPublisher,
 }
+/// Keeps every result and reserves the tree up front from the length of the source.
+pub type BasicContext = CachedContext<BasicCache>;
+/// Like `BasicContext` but for large sources, the cache only keeps results near where the parse is.
+/// Failed rules are dropped from the tree as soon as they fail, so it only holds the rules that matched
+/// and the ones still being parsed, and it grows as it is needed rather than being reserved up front.
+pub type SparseContext = CachedContext<SparseCache>;
+impl SparseContext {
+    /// How far back in bytes results are kept by `SparseContext::new`.
+    pub const DEFAULT_WINDOW: u32 = SparseCache::DEFAULT_WINDOW;
+
+    pub fn with_window(window: u32, number_of_rules: usize) -> Self {
+        CachedContext {
+            cache: SparseCache::with_window(window),
+            publisher: BasicPublisher::new(0, number_of_rules),
+        }
+    }
+
+    /// The number of results currently cached.
+    pub fn cache_len(&self) -> usize {
+        self.cache.len()
+    }
+}
 #[allow(dead_code)]
 
 pub struct DirectLeftRecursionContext {
@@ -94,32 +127,41 @@ pub struct IndirectLeftRecursionContext {
     publisher: IndirectLeftRecursionPublisher,
 }
 
-impl Context for BasicContext {
-    type C = BasicCache;
+impl<C: ContextCache> Context for CachedContext<C> {
+    type C = C;
     type P = BasicPublisher;
 
     fn new(size_of_source: usize, number_of_rules: usize) -> Self {
-        BasicContext {
-            cache: Self::C::new(),
-            publisher: Self::P::new(size_of_source, number_of_rules),
+        CachedContext {
+            cache: C::new(),
+            publisher: Self::P::new(C::reserved_source_size(size_of_source), number_of_rules),
         }
     }
+    fn reset(&mut self, size_of_source: usize, number_of_rules: usize) {
+        self.cache.reset();
+        self.publisher
+            .reset(C::reserved_source_size(size_of_source), number_of_rules);
+    }
     fn get_current_active_lr_position(&self) -> Option<(Rules, u32)> {
-        self.cache.get_current_active_lr_position()
+        self.cache.results().get_current_active_lr_position()
     }
     fn set_current_active_lr_position(&mut self, position: Option<(Rules, u32)>) {
-        self.cache.set_current_active_lr_position(position);
+        self.cache
+            .results_mut()
+            .set_current_active_lr_position(position);
     }
 
     fn reinitialize_eval_set(&mut self, rule: Rules, start_position: u32) {
-        self.cache.reinitialize_eval_set(rule, start_position);
+        self.cache
+            .results_mut()
+            .reinitialize_eval_set(rule, start_position);
     }
     #[allow(dead_code)]
     fn print_cache(&self) {
         println!("{:?}", &self.cache)
     }
     fn eval_set_is_empty(&self, start_position: u32, rule: Rules) -> bool {
-        self.cache.eval_set_is_empty(rule, start_position)
+        self.cache.results().eval_set_is_empty(rule, start_position)
     }
     fn clear_node_of_children(&mut self, node: Key) {
         self.publisher.clear_node_of_children(node);
@@ -174,8 +216,14 @@ impl Context for BasicContext {
         key: Key,
         lr: LR,
     ) {
-        self.cache
-            .insert_direct_lr(rule, is_true, start_position, end_position, key, lr);
+        self.cache.results_mut().insert_direct_lr(
+            rule,
+            is_true,
+            start_position,
+            end_position,
+            key,
+            lr,
+        );
     }
 
     fn update_publisher_entry(
@@ -190,13 +238,13 @@ impl Context for BasicContext {
         self.publisher.set_node_end_position(key, end_position);
     }
     fn check(&self, rule: Rules, start_position: u32) -> Option<(bool, u32, Key)> {
-        self.cache.check(rule, start_position)
+        self.cache.results().check(rule, start_position)
     }
     fn check_lr(&self, rule: Rules, start_position: u32) -> Option<(bool, u32, Key, LR)> {
-        self.cache.check_direct_lr(rule, start_position)
+        self.cache.results().check_direct_lr(rule, start_position)
     }
     fn check_head(&self, rule: Rules, start_position: u32) -> Option<&Head> {
-        self.cache.check_head(rule, start_position)
+        self.cache.results().check_head(rule, start_position)
     }
     // fn remove_head(&mut self, start_position: u32) {
     //     println!("REMOVE HEAD! {:?}\x1b[0m", (start_position));
@@ -204,7 +252,7 @@ impl Context for BasicContext {
     //     self.cache.remove_head(start_position);
     // }
     fn rule_in_involved_set(&self, head: (Rules, u32), rule: Rules) -> bool {
-        self.cache.rule_in_involved_set(head, rule)
+        self.cache.results().rule_in_involved_set(head, rule)
     }
     fn connect_if_not_connected(&mut self, parent_index: Key, child_index: Key) {
         self.publisher
@@ -213,22 +261,37 @@ impl Context for BasicContext {
 
     fn reset_head(&mut self, rule: Rules, start_position: u32) {
         println!("RESETTING HEAD!");
-        self.cache.reset_head(rule, start_position);
+        self.cache.results_mut().reset_head(rule, start_position);
     }
     fn set_head(&mut self, start_position: u32, head_rule: Rules, involved_set: BTreeSet<Rules>) {
         println!("SETTING HEAD");
-        self.cache.set_head(start_position, head_rule, involved_set);
+        self.cache
+            .results_mut()
+            .set_head(start_position, head_rule, involved_set);
     }
     fn rule_in_eval_set(&self, head_index: (Rules, u32), rule: Rules) -> bool {
-        self.cache.rule_in_eval_set(head_index, rule)
+        self.cache.results().rule_in_eval_set(head_index, rule)
     }
     fn remove_from_eval_set(&mut self, head_index: (Rules, u32), rule: Rules) {
-        self.cache.remove_from_eval_set(head_index, rule);
+        self.cache
+            .results_mut()
+            .remove_from_eval_set(head_index, rule);
+    }
+
+    fn discard_failed(&mut self, key: Key) -> bool {
+        let discarded = self.cache.forget_from(key);
+        if discarded {
+            self.publisher.truncate(key);
+        }
+        discarded
     }
 
     fn get_publisher(self) -> Self::P {
         self.publisher
     }
+    fn publisher(&self) -> &Self::P {
+        &self.publisher
+    }
 }
 
 #[cfg(test)]
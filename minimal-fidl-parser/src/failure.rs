@@ -0,0 +1,164 @@
+use crate::Rules;
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// Something the parser tried to match at a position.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Expected {
+    /// A literal such as `}` or `method`.
+    Terminal(&'static [u8]),
+    /// Any character in an inclusive range.
+    Range(u32, u32),
+    /// A rule that matches a token such as a name or a number, see `token_description`.
+    Rule(Rules),
+}
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Terminal(terminal) => {
+                write!(f, "`{}`", String::from_utf8_lossy(terminal).escape_debug())
+            }
+            Expected::Range(start, end) => {
+                let char = |c: &u32| char::from_u32(*c).unwrap_or(char::REPLACEMENT_CHARACTER);
+                write!(f, "a character from `{}` to `{}`", char(start), char(end))
+            }
+            Expected::Rule(rule) => match token_description(*rule) {
+                Some(description) => write!(f, "{}", description),
+                None => write!(f, "{:?}", rule),
+            },
+        }
+    }
+}
+
+/// Rules that are reported as a whole rather than by the characters inside them.
+fn token_description(rule: Rules) -> Option<&'static str> {
+    match rule {
+        Rules::variable_name => Some("a name"),
+        Rules::type_dec => Some("a type name"),
+        Rules::type_ref => Some("a type"),
+        Rules::annotation_name => Some("an annotation name"),
+        Rules::file_path => Some("a file path"),
+        Rules::string_literal => Some("a string"),
+        Rules::constant_ref => Some("a constant"),
+        Rules::number
+        | Rules::integer
+        | Rules::float
+        | Rules::hex
+        | Rules::binary
+        | Rules::digits => Some("a number"),
+        _ => None,
+    }
+}
+
+/// Rules that can appear almost anywhere, reporting them would only add noise.
+fn is_silent(rule: Rules) -> bool {
+    matches!(rule, Rules::comment | Rules::multiline_comment)
+}
+
+/// Tracks the furthest position any terminal or token failed to match at while parsing.
+/// Everything the parser attempted at that position is what it expected to find there.
+#[derive(Debug, Default)]
+pub(crate) struct FailureTracker {
+    position: u32,
+    expected: BTreeSet<Expected>,
+    /// While above zero failures are not recorded, i.e inside lookaheads and tokens.
+    silenced: u32,
+}
+impl FailureTracker {
+    pub(crate) fn record(&mut self, position: u32, expected: Expected) {
+        if self.silenced > 0 || position < self.position {
+            return;
+        }
+        if position > self.position {
+            self.position = position;
+            self.expected.clear();
+        }
+        self.expected.insert(expected);
+    }
+
+    /// Tokens are reported as a whole so nothing inside them or inside silent rules is recorded.
+    pub(crate) fn enter_rule(&mut self, rule: Rules) {
+        if is_silent(rule) || token_description(rule).is_some() {
+            self.silenced += 1;
+        }
+    }
+
+    pub(crate) fn exit_rule(&mut self, rule: Rules, is_true: bool, start_position: u32) {
+        if is_silent(rule) || token_description(rule).is_some() {
+            self.silenced -= 1;
+        }
+        if !is_true && token_description(rule).is_some() {
+            self.record(start_position, Expected::Rule(rule));
+        }
+    }
+
+    pub(crate) fn silence(&mut self) {
+        self.silenced += 1;
+    }
+
+    pub(crate) fn unsilence(&mut self) {
+        self.silenced -= 1;
+    }
+
+    pub(crate) fn to_failure(&self, source: &str) -> ParseFailure {
+        let position = (self.position as usize).min(source.len());
+        let before = &source[..position];
+        let line = before.matches('\n').count() + 1;
+        let column = before[before.rfind('\n').map_or(0, |i| i + 1)..].chars().count() + 1;
+        let rest = &source[position..];
+        let word: String = rest
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect();
+        let found = match (word.is_empty(), rest.chars().next()) {
+            (false, _) => Some(word),
+            (true, Some(c)) => Some(c.to_string()),
+            (true, None) => None,
+        };
+        let expected = self
+            .expected
+            .iter()
+            .filter(|e| {
+                !matches!(e, Expected::Terminal(t) if t.iter().all(u8::is_ascii_whitespace))
+            })
+            .cloned()
+            .collect();
+        ParseFailure {
+            position: self.position,
+            line,
+            column,
+            expected,
+            found,
+        }
+    }
+}
+
+/// Where parsing stopped making progress and what would have let it continue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFailure {
+    /// Byte offset into the source.
+    pub position: u32,
+    /// 1 based line of `position`.
+    pub line: usize,
+    /// 1 based column of `position` in characters.
+    pub column: usize,
+    /// Whitespace is never listed since it is allowed nearly everywhere.
+    pub expected: Vec<Expected>,
+    /// The word or character at `position`, `None` at the end of the source.
+    pub found: Option<String>,
+}
+impl fmt::Display for ParseFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {} col {}: ", self.line, self.column)?;
+        let expected: Vec<String> = self.expected.iter().map(|e| e.to_string()).collect();
+        match expected.split_last() {
+            None => write!(f, "unexpected input")?,
+            Some((last, [])) => write!(f, "expected {}", last)?,
+            Some((last, rest)) => write!(f, "expected {} or {}", rest.join(", "), last)?,
+        }
+        match &self.found {
+            Some(found) => write!(f, ", found `{}`", found.escape_debug()),
+            None => write!(f, ", found the end of the file"),
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use crate::parse::parse;
+use crate::parser::{interface, type_collection, wsn};
+use crate::parser_core::_ordered_choice;
+use crate::var_name::_var_name;
+use crate::{BasicContext, BasicPublisher, Context, Key, ParseFailure, Rules, Source, RULES_SIZE};
+use std::cell::RefCell;
+use std::ops::Range;
+
+/// How far past its end a package, import or declaration looks, their trailing comment rule checks
+/// for `//` after the last character it consumed.
+pub(crate) const LOOKAHEAD: u32 = 2;
+
+/// Parses `old_source` with `edit` replaced by `text`, reusing the interfaces and type collections
+/// of `previous`, the parse of `old_source`, that the edit does not touch.
+///
+/// The package, imports and declarations that end before the edit are kept as they are and the
+/// declarations after it are kept shifted by the change in length, only the region between them is
+/// parsed again. Parsing the region stops as soon as it reaches the start of a declaration after the
+/// edit, everything from there on parses the same as before. The result is the same as `parse` of the
+/// edited source.
+/// Falls back to parsing the whole source when the edit is in the package or imports, when `previous`
+/// was not a complete parse, or when the edited source does not parse so the failure is exact.
+///
+/// `edit` is in bytes and must be on character boundaries of `old_source`.
+pub fn reparse(
+    previous: &BasicPublisher,
+    old_source: &str,
+    edit: Range<usize>,
+    text: &str,
+) -> (bool, u32, BasicPublisher, ParseFailure) {
+    let mut new_source = old_source.to_string();
+    new_source.replace_range(edit.clone(), text);
+    match reparse_declarations(previous, old_source, &new_source, edit, text) {
+        Some(reparsed) => reparsed,
+        None => parse(&new_source),
+    }
+}
+
+fn reparse_declarations(
+    previous: &BasicPublisher,
+    old_source: &str,
+    new_source: &str,
+    edit: Range<usize>,
+    text: &str,
+) -> Option<(bool, u32, BasicPublisher, ParseFailure)> {
+    let root = previous.get_node(Key(0));
+    let grammar = previous.get_node(*root.get_children().first()?);
+    if grammar.rule != Rules::Grammar || grammar.end_position != old_source.len() as u32 {
+        return None;
+    }
+    let delta = new_source.len() as i64 - old_source.len() as i64;
+    let shift = |position: u32| (position as i64 + delta) as u32;
+    let edit_start = edit.start as u32;
+    let edit_end = (edit.start + text.len()) as u32;
+    let is_declaration = |key: &Key| {
+        matches!(
+            previous.get_node(*key).rule,
+            Rules::interface | Rules::type_collection
+        )
+    };
+
+    // Parsing starts again after the last package, import or declaration that can not have seen
+    // the edit. The region is parsed as declarations only, so an import in it falls back.
+    let region_start = grammar
+        .get_children()
+        .iter()
+        .filter(|key| {
+            is_declaration(key)
+                || matches!(
+                    previous.get_node(**key).rule,
+                    Rules::package | Rules::import_model | Rules::import_namespace
+                )
+        })
+        .map(|key| previous.get_node(*key).end_position)
+        .take_while(|end| end + LOOKAHEAD <= edit_start)
+        .last()?;
+    // Declarations whose source is unchanged from their start to the end of the file.
+    let after: Vec<Key> = grammar
+        .get_children()
+        .iter()
+        .filter(|key| is_declaration(key))
+        .filter(|key| previous.get_node(**key).start_position >= edit.end as u32)
+        .copied()
+        .collect();
+
+    let source = Source::new(new_source);
+    let context = RefCell::new(BasicContext::new(0, RULES_SIZE as usize));
+    let interface = _var_name(Rules::interface, &context, interface);
+    let type_collection = _var_name(Rules::type_collection, &context, type_collection);
+    let declaration = _ordered_choice(&interface, &type_collection);
+    // The same as the loop over declarations in `grammar`, each one is followed by whitespace.
+    let mut position = wsn(Key(0), &context, &source, region_start).1;
+    let mut resume: Option<Key> = None;
+    loop {
+        if position >= edit_end {
+            resume = after
+                .iter()
+                .find(|key| shift(previous.get_node(**key).start_position) == position)
+                .copied();
+            if resume.is_some() {
+                break;
+            }
+        }
+        let (result, end) = declaration(Key(0), &source, position);
+        if !result {
+            break;
+        }
+        position = wsn(Key(0), &context, &source, end).1;
+    }
+    let end_position = match resume {
+        Some(_) => shift(grammar.end_position),
+        None => position,
+    };
+    if end_position != new_source.len() as u32 {
+        return None;
+    }
+
+    let region = context.borrow().publisher().clear_false();
+    let mut publisher = BasicPublisher::new(previous.len() / 4, 1);
+    let grammar_key = publisher.add_node(Rules::Grammar, 0, end_position, true);
+    publisher.connect(Key(0), grammar_key);
+    for key in grammar.get_children() {
+        if previous.get_node(*key).end_position <= region_start {
+            copy_subtree(&mut publisher, grammar_key, previous, *key, &|p| p);
+        }
+    }
+    for key in region.get_node(Key(0)).get_children() {
+        copy_subtree(&mut publisher, grammar_key, &region, *key, &|p| p);
+    }
+    if let Some(resume) = resume {
+        let resume_start = previous.get_node(resume).start_position;
+        for key in grammar.get_children() {
+            if previous.get_node(*key).start_position >= resume_start {
+                copy_subtree(&mut publisher, grammar_key, previous, *key, &shift);
+            }
+        }
+    }
+    Some((true, end_position, publisher, source.furthest_failure()))
+}
+
+/// Adds the node at `key` in `from` and everything under it to `parent`, moving each position with `shift`.
+pub(crate) fn copy_subtree(
+    publisher: &mut BasicPublisher,
+    parent: Key,
+    from: &BasicPublisher,
+    key: Key,
+    shift: &dyn Fn(u32) -> u32,
+) {
+    let node = from.get_node(key);
+    let copy = publisher.add_node(
+        node.rule,
+        shift(node.start_position),
+        shift(node.end_position),
+        node.result,
+    );
+    publisher.connect(parent, copy);
+    for child in node.get_children() {
+        copy_subtree(publisher, copy, from, *child, shift);
+    }
+}
@@ -1,20 +1,26 @@
 #![allow(unused_imports)]
 mod cache;
 mod context;
+mod failure;
+mod incremental;
 mod keys;
 pub mod parse;
 pub mod parser;
 mod parser_core;
 mod publisher;
+mod recovery;
 mod rules;
 mod source;
 mod var_name;
-use cache::{BasicCache, DirectLeftRecursionCache, IndirectLeftRecursionCache};
-pub use context::{BasicContext, Context};
+use cache::{BasicCache, DirectLeftRecursionCache, IndirectLeftRecursionCache, SparseCache};
+pub use context::{BasicContext, CachedContext, Context, SparseContext};
+pub use failure::{Expected, ParseFailure};
+pub use incremental::reparse;
 pub use keys::Key;
-pub use parse::parse;
+pub use parse::{parse, parse_with_context};
 pub use parser::*;
 use parser_core::*;
+pub use recovery::{parse_with_recovery, RecoveredParse};
 pub use publisher::{
     BasicPublisher, DirectLeftRecursionPublisher, IndirectLeftRecursionPublisher, Node,
 };
@@ -42,6 +42,7 @@ fn main() {
                             "Failed to parse to end of file. Parsed {:?} chars from {:?} chars",
                             result.1, src_len
                         );
+                        println!("{}", result.3);
                     }
                 }
             };
@@ -7,16 +7,29 @@ use core::cell::RefCell;
 use std::env;
 use std::fs::{canonicalize, read_to_string};
 
-pub fn parse(source: &String) -> (bool, u32, BasicPublisher) {
+/// Parses a whole file, the `ParseFailure` is only meaningful if the source was not fully consumed.
+pub fn parse(source: &str) -> (bool, u32, BasicPublisher, ParseFailure) {
+    let context = RefCell::new(BasicContext::new(source.len(), RULES_SIZE as usize));
+    parse_with_context(source, &context)
+}
+
+/// Like `parse` but with a context that is reset first and can be used again afterwards,
+/// so parsing many sources does not allocate a new cache and tree for each.
+pub fn parse_with_context<T: Context<P = BasicPublisher>>(
+    source: &str,
+    context: &RefCell<T>,
+) -> (bool, u32, BasicPublisher, ParseFailure) {
     let src_len = source.len() as u32;
-    let source = Source::new(&source);
+    let source = Source::new(source);
     let position: u32 = 0;
-    let context = RefCell::new(BasicContext::new(src_len as usize, RULES_SIZE as usize));
+    context
+        .borrow_mut()
+        .reset(src_len as usize, RULES_SIZE as usize);
     let result: (bool, u32);
     {
-        let executor = _var_name(Rules::Grammar, &context, grammar);
+        let executor = _var_name(Rules::Grammar, context, grammar);
         result = executor(Key(0), &source, position);
     }
-    let gen_code = context.into_inner().get_publisher().clear_false();
-    (result.0, result.1, gen_code)
+    let gen_code = context.borrow().publisher().clear_false();
+    (result.0, result.1, gen_code, source.furthest_failure())
 }
@@ -485,6 +485,79 @@ pub fn array<T: Context>(
     closure_7(parent, source, position)
 }
 #[allow(dead_code)]
+pub fn min_int<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = _string_terminal_opt_ascii(&[b'm', b'i', b'n', b'I', b'n', b't']);
+    closure_1(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn max_int<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = _string_terminal_opt_ascii(&[b'm', b'a', b'x', b'I', b'n', b't']);
+    closure_1(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn range_bound<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = _var_name(Rules::hex, context, hex);
+    let closure_2 = _var_name(Rules::binary, context, binary);
+    let closure_3 = _ordered_choice(&closure_1, &closure_2);
+    let closure_4 = _var_name(Rules::integer, context, integer);
+    let closure_5 = _ordered_choice(&closure_3, &closure_4);
+    let closure_6 = _var_name(Rules::min_int, context, min_int);
+    let closure_7 = _ordered_choice(&closure_5, &closure_6);
+    let closure_8 = _var_name(Rules::max_int, context, max_int);
+    let closure_9 = _ordered_choice(&closure_7, &closure_8);
+    closure_9(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn integer_range<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    //  Only valid on Integer, which is checked when collecting
+    let closure_1 = _terminal(b'(');
+    let closure_2 =
+        move |parent: Key, source: &Source, position: u32| ws(parent, context, source, position);
+    let closure_3 = _sequence(&closure_1, &closure_2);
+    let closure_4 = move |parent: Key, source: &Source, position: u32| {
+        range_bound(parent, context, source, position)
+    };
+    let closure_5 = _sequence(&closure_3, &closure_4);
+    let closure_6 =
+        move |parent: Key, source: &Source, position: u32| ws(parent, context, source, position);
+    let closure_7 = _sequence(&closure_5, &closure_6);
+    let closure_8 = _terminal(b',');
+    let closure_9 = _sequence(&closure_7, &closure_8);
+    let closure_10 =
+        move |parent: Key, source: &Source, position: u32| ws(parent, context, source, position);
+    let closure_11 = _sequence(&closure_9, &closure_10);
+    let closure_12 = move |parent: Key, source: &Source, position: u32| {
+        range_bound(parent, context, source, position)
+    };
+    let closure_13 = _sequence(&closure_11, &closure_12);
+    let closure_14 =
+        move |parent: Key, source: &Source, position: u32| ws(parent, context, source, position);
+    let closure_15 = _sequence(&closure_13, &closure_14);
+    let closure_16 = _terminal(b')');
+    let closure_17 = _sequence(&closure_15, &closure_16);
+    closure_17(parent, source, position)
+}
+#[allow(dead_code)]
 pub fn type_ref<T: Context>(
     parent: Key,
     context: &RefCell<T>,
@@ -502,10 +575,13 @@ pub fn type_ref<T: Context>(
     let closure_5 = _subexpression(&closure_4);
     let closure_6 = _zero_or_more(&closure_5);
     let closure_7 = _sequence(&closure_1, &closure_6);
-    let closure_8 = _var_name(Rules::array, context, array);
+    let closure_8 = _var_name(Rules::integer_range, context, integer_range);
     let closure_9 = _optional(&closure_8);
     let closure_10 = _sequence(&closure_7, &closure_9);
-    closure_10(parent, source, position)
+    let closure_11 = _var_name(Rules::array, context, array);
+    let closure_12 = _optional(&closure_11);
+    let closure_13 = _sequence(&closure_10, &closure_12);
+    closure_13(parent, source, position)
 }
 #[allow(dead_code)]
 pub fn variable_name<T: Context>(
@@ -611,48 +687,774 @@ pub fn import_model<T: Context>(
     source: &Source,
     position: u32,
 ) -> (bool, u32) {
-    let closure_1 = _string_terminal_opt_ascii(&[b'i', b'm', b'p', b'o', b'r', b't']);
+    let closure_1 = _string_terminal_opt_ascii(&[b'i', b'm', b'p', b'o', b'r', b't']);
+    let closure_2 = move |parent: Key, source: &Source, position: u32| {
+        ws_atlone(parent, context, source, position)
+    };
+    let closure_3 = _sequence(&closure_1, &closure_2);
+    let closure_4 = _string_terminal_opt_ascii(&[b'm', b'o', b'd', b'e', b'l']);
+    let closure_5 = _sequence(&closure_3, &closure_4);
+    let closure_6 = move |parent: Key, source: &Source, position: u32| {
+        ws_atlone(parent, context, source, position)
+    };
+    let closure_7 = _sequence(&closure_5, &closure_6);
+    let closure_8 = _var_name(Rules::file_path, context, file_path);
+    let closure_9 = _sequence(&closure_7, &closure_8);
+    let closure_10 = move |parent: Key, source: &Source, position: u32| {
+        ws_only_regular_comment(parent, context, source, position)
+    };
+    let closure_11 = _sequence(&closure_9, &closure_10);
+    closure_11(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn open_bracket<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    //  These two are relevant for formatting and can be inlined for anything else just like comments
+    let closure_1 = _terminal(b'{');
+    closure_1(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn close_bracket<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = _terminal(b'}');
+    closure_1(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn readonly<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = _string_terminal_opt_ascii(&[b'r', b'e', b'a', b'd', b'o', b'n', b'l', b'y']);
+    closure_1(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn no_subscriptions<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = _string_terminal_opt_ascii(&[
+        b'n', b'o', b'S', b'u', b'b', b's', b'c', b'r', b'i', b'p', b't', b'i', b'o', b'n', b's',
+    ]);
+    closure_1(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn attribute<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = _var_name(Rules::annotation_block, context, annotation_block);
+    let closure_2 = _optional(&closure_1);
+    let closure_3 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_4 = _sequence(&closure_2, &closure_3);
+    let closure_5 =
+        _string_terminal_opt_ascii(&[b'a', b't', b't', b'r', b'i', b'b', b'u', b't', b'e']);
+    let closure_6 = _sequence(&closure_4, &closure_5);
+    let closure_7 = move |parent: Key, source: &Source, position: u32| {
+        ws_atlone(parent, context, source, position)
+    };
+    let closure_8 = _sequence(&closure_6, &closure_7);
+    let closure_9 = _var_name(Rules::type_ref, context, type_ref);
+    let closure_10 = _sequence(&closure_8, &closure_9);
+    let closure_11 = move |parent: Key, source: &Source, position: u32| {
+        ws_atlone(parent, context, source, position)
+    };
+    let closure_12 = _sequence(&closure_10, &closure_11);
+    let closure_13 = _var_name(Rules::variable_name, context, variable_name);
+    let closure_14 = _sequence(&closure_12, &closure_13);
+    let closure_15 = move |parent: Key, source: &Source, position: u32| {
+        ws_atlone(parent, context, source, position)
+    };
+    let closure_16 = _var_name(Rules::readonly, context, readonly);
+    let closure_17 = _sequence(&closure_15, &closure_16);
+    let closure_18 = _subexpression(&closure_17);
+    let closure_19 = _optional(&closure_18);
+    let closure_20 = _sequence(&closure_14, &closure_19);
+    let closure_21 = move |parent: Key, source: &Source, position: u32| {
+        ws_atlone(parent, context, source, position)
+    };
+    let closure_22 = _var_name(Rules::no_subscriptions, context, no_subscriptions);
+    let closure_23 = _sequence(&closure_21, &closure_22);
+    let closure_24 = _subexpression(&closure_23);
+    let closure_25 = _optional(&closure_24);
+    let closure_26 = _sequence(&closure_20, &closure_25);
+    let closure_27 = move |parent: Key, source: &Source, position: u32| {
+        ws_only_regular_comment(parent, context, source, position)
+    };
+    let closure_28 = _sequence(&closure_26, &closure_27);
+    closure_28(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn variable_declaration<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = _var_name(Rules::annotation_block, context, annotation_block);
+    let closure_2 = _optional(&closure_1);
+    let closure_3 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_4 = _sequence(&closure_2, &closure_3);
+    let closure_5 = _var_name(Rules::type_ref, context, type_ref);
+    let closure_6 = _sequence(&closure_4, &closure_5);
+    let closure_7 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_8 = _sequence(&closure_6, &closure_7);
+    let closure_9 = _var_name(Rules::variable_name, context, variable_name);
+    let closure_10 = _sequence(&closure_8, &closure_9);
+    let closure_11 = move |parent: Key, source: &Source, position: u32| {
+        ws_only_regular_comment(parent, context, source, position)
+    };
+    let closure_12 = _sequence(&closure_10, &closure_11);
+    closure_12(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn input_params<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = _var_name(Rules::annotation_block, context, annotation_block);
+    let closure_2 = _optional(&closure_1);
+    let closure_3 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_4 = _sequence(&closure_2, &closure_3);
+    let closure_5 = _string_terminal_opt_ascii(&[b'i', b'n']);
+    let closure_6 = _sequence(&closure_4, &closure_5);
+    let closure_7 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_8 = _sequence(&closure_6, &closure_7);
+    let closure_9 = _var_name(Rules::open_bracket, context, open_bracket);
+    let closure_10 = _sequence(&closure_8, &closure_9);
+    let closure_11 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_12 = _sequence(&closure_10, &closure_11);
+    let closure_13 = _var_name(Rules::variable_declaration, context, variable_declaration);
+    let closure_14 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_15 = _sequence(&closure_13, &closure_14);
+    let closure_16 = _subexpression(&closure_15);
+    let closure_17 = _zero_or_more(&closure_16);
+    let closure_18 = _sequence(&closure_12, &closure_17);
+    let closure_19 = _var_name(Rules::close_bracket, context, close_bracket);
+    let closure_20 = _sequence(&closure_18, &closure_19);
+    let closure_21 = move |parent: Key, source: &Source, position: u32| {
+        ws_only_regular_comment(parent, context, source, position)
+    };
+    let closure_22 = _sequence(&closure_20, &closure_21);
+    closure_22(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn output_params<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = _var_name(Rules::annotation_block, context, annotation_block);
+    let closure_2 = _optional(&closure_1);
+    let closure_3 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_4 = _sequence(&closure_2, &closure_3);
+    let closure_5 = _string_terminal_opt_ascii(&[b'o', b'u', b't']);
+    let closure_6 = _sequence(&closure_4, &closure_5);
+    let closure_7 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_8 = _sequence(&closure_6, &closure_7);
+    let closure_9 = _var_name(Rules::open_bracket, context, open_bracket);
+    let closure_10 = _sequence(&closure_8, &closure_9);
+    let closure_11 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_12 = _sequence(&closure_10, &closure_11);
+    let closure_13 = _var_name(Rules::variable_declaration, context, variable_declaration);
+    let closure_14 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_15 = _sequence(&closure_13, &closure_14);
+    let closure_16 = _subexpression(&closure_15);
+    let closure_17 = _zero_or_more(&closure_16);
+    let closure_18 = _sequence(&closure_12, &closure_17);
+    let closure_19 = _var_name(Rules::close_bracket, context, close_bracket);
+    let closure_20 = _sequence(&closure_18, &closure_19);
+    let closure_21 = move |parent: Key, source: &Source, position: u32| {
+        ws_only_regular_comment(parent, context, source, position)
+    };
+    let closure_22 = _sequence(&closure_20, &closure_21);
+    closure_22(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn method_error<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = _var_name(Rules::annotation_block, context, annotation_block);
+    let closure_2 = _optional(&closure_1);
+    let closure_3 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_4 = _sequence(&closure_2, &closure_3);
+    let closure_5 = _string_terminal_opt_ascii(&[b'e', b'r', b'r', b'o', b'r']);
+    let closure_6 = _sequence(&closure_4, &closure_5);
+    let closure_7 = move |parent: Key, source: &Source, position: u32| {
+        ws_atlone(parent, context, source, position)
+    };
+    let closure_8 = _var_name(Rules::type_ref, context, type_ref);
+    let closure_9 = _sequence(&closure_7, &closure_8);
+    let closure_10 = _subexpression(&closure_9);
+    let closure_11 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_12 = _var_name(Rules::open_bracket, context, open_bracket);
+    let closure_13 = _sequence(&closure_11, &closure_12);
+    let closure_14 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_15 = _sequence(&closure_13, &closure_14);
+    let closure_16 = _var_name(Rules::enum_value, context, enum_value);
+    let closure_17 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_18 = _sequence(&closure_16, &closure_17);
+    let closure_19 = _subexpression(&closure_18);
+    let closure_20 = _zero_or_more(&closure_19);
+    let closure_21 = _sequence(&closure_15, &closure_20);
+    let closure_22 = _var_name(Rules::close_bracket, context, close_bracket);
+    let closure_23 = _sequence(&closure_21, &closure_22);
+    let closure_24 = _subexpression(&closure_23);
+    let closure_25 = _ordered_choice(&closure_10, &closure_24);
+    let closure_26 = _subexpression(&closure_25);
+    let closure_27 = _sequence(&closure_6, &closure_26);
+    let closure_28 = move |parent: Key, source: &Source, position: u32| {
+        ws_only_regular_comment(parent, context, source, position)
+    };
+    let closure_29 = _sequence(&closure_27, &closure_28);
+    closure_29(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn fire_and_forget<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = _string_terminal_opt_ascii(&[
+        b'f', b'i', b'r', b'e', b'A', b'n', b'd', b'F', b'o', b'r', b'g', b'e', b't',
+    ]);
+    closure_1(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn method<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = _var_name(Rules::annotation_block, context, annotation_block);
+    let closure_2 = _optional(&closure_1);
+    let closure_3 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_4 = _sequence(&closure_2, &closure_3);
+    let closure_5 = _string_terminal_opt_ascii(&[b'm', b'e', b't', b'h', b'o', b'd']);
+    let closure_6 = _sequence(&closure_4, &closure_5);
+    let closure_7 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_8 = _sequence(&closure_6, &closure_7);
+    let closure_9 = _var_name(Rules::variable_name, context, variable_name);
+    let closure_10 = _sequence(&closure_8, &closure_9);
+    let closure_11 = move |parent: Key, source: &Source, position: u32| {
+        ws_atlone(parent, context, source, position)
+    };
+    let closure_12 = _var_name(Rules::fire_and_forget, context, fire_and_forget);
+    let closure_13 = _sequence(&closure_11, &closure_12);
+    let closure_14 = _subexpression(&closure_13);
+    let closure_15 = _optional(&closure_14);
+    let closure_16 = _sequence(&closure_10, &closure_15);
+    let closure_17 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_18 = _sequence(&closure_16, &closure_17);
+    let closure_19 = _var_name(Rules::open_bracket, context, open_bracket);
+    let closure_20 = _sequence(&closure_18, &closure_19);
+    let closure_21 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_22 = _sequence(&closure_20, &closure_21);
+    let closure_23 = _var_name(Rules::input_params, context, input_params);
+    let closure_24 = _optional(&closure_23);
+    let closure_25 = _sequence(&closure_22, &closure_24);
+    let closure_26 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_27 = _sequence(&closure_25, &closure_26);
+    let closure_28 = _var_name(Rules::output_params, context, output_params);
+    let closure_29 = _optional(&closure_28);
+    let closure_30 = _sequence(&closure_27, &closure_29);
+    let closure_31 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_32 = _sequence(&closure_30, &closure_31);
+    let closure_33 = _var_name(Rules::method_error, context, method_error);
+    let closure_34 = _optional(&closure_33);
+    let closure_35 = _sequence(&closure_32, &closure_34);
+    let closure_36 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_37 = _sequence(&closure_35, &closure_36);
+    let closure_38 = _var_name(Rules::close_bracket, context, close_bracket);
+    let closure_39 = _sequence(&closure_37, &closure_38);
+    let closure_40 = move |parent: Key, source: &Source, position: u32| {
+        ws_only_regular_comment(parent, context, source, position)
+    };
+    let closure_41 = _sequence(&closure_39, &closure_40);
+    closure_41(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn selective<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 =
+        _string_terminal_opt_ascii(&[b's', b'e', b'l', b'e', b'c', b't', b'i', b'v', b'e']);
+    closure_1(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn broadcast<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = _var_name(Rules::annotation_block, context, annotation_block);
+    let closure_2 = _optional(&closure_1);
+    let closure_3 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_4 = _sequence(&closure_2, &closure_3);
+    let closure_5 =
+        _string_terminal_opt_ascii(&[b'b', b'r', b'o', b'a', b'd', b'c', b'a', b's', b't']);
+    let closure_6 = _sequence(&closure_4, &closure_5);
+    let closure_7 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_8 = _sequence(&closure_6, &closure_7);
+    let closure_9 = _var_name(Rules::variable_name, context, variable_name);
+    let closure_10 = _sequence(&closure_8, &closure_9);
+    let closure_11 = move |parent: Key, source: &Source, position: u32| {
+        ws_atlone(parent, context, source, position)
+    };
+    let closure_12 = _var_name(Rules::selective, context, selective);
+    let closure_13 = _sequence(&closure_11, &closure_12);
+    let closure_14 = _subexpression(&closure_13);
+    let closure_15 = _optional(&closure_14);
+    let closure_16 = _sequence(&closure_10, &closure_15);
+    let closure_17 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_18 = _sequence(&closure_16, &closure_17);
+    let closure_19 = _var_name(Rules::open_bracket, context, open_bracket);
+    let closure_20 = _sequence(&closure_18, &closure_19);
+    let closure_21 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_22 = _sequence(&closure_20, &closure_21);
+    let closure_23 = _var_name(Rules::output_params, context, output_params);
+    let closure_24 = _optional(&closure_23);
+    let closure_25 = _sequence(&closure_22, &closure_24);
+    let closure_26 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_27 = _sequence(&closure_25, &closure_26);
+    let closure_28 = _var_name(Rules::close_bracket, context, close_bracket);
+    let closure_29 = _sequence(&closure_27, &closure_28);
+    let closure_30 = move |parent: Key, source: &Source, position: u32| {
+        ws_only_regular_comment(parent, context, source, position)
+    };
+    let closure_31 = _sequence(&closure_29, &closure_30);
+    closure_31(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn typedef<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = _var_name(Rules::annotation_block, context, annotation_block);
+    let closure_2 = _optional(&closure_1);
+    let closure_3 = move |parent: Key, source: &Source, position: u32| {
+        wsn_nocomment(parent, context, source, position)
+    };
+    let closure_4 = _sequence(&closure_2, &closure_3);
+    let closure_5 = _string_terminal_opt_ascii(&[b't', b'y', b'p', b'e', b'd', b'e', b'f']);
+    let closure_6 = _sequence(&closure_4, &closure_5);
+    let closure_7 = move |parent: Key, source: &Source, position: u32| {
+        ws_atlone(parent, context, source, position)
+    };
+    let closure_8 = _sequence(&closure_6, &closure_7);
+    let closure_9 = _var_name(Rules::type_dec, context, type_dec);
+    let closure_10 = _sequence(&closure_8, &closure_9);
+    let closure_11 = move |parent: Key, source: &Source, position: u32| {
+        ws_atlone(parent, context, source, position)
+    };
+    let closure_12 = _sequence(&closure_10, &closure_11);
+    let closure_13 = _string_terminal_opt_ascii(&[b'i', b's']);
+    let closure_14 = _sequence(&closure_12, &closure_13);
+    let closure_15 = move |parent: Key, source: &Source, position: u32| {
+        ws_atlone(parent, context, source, position)
+    };
+    let closure_16 = _sequence(&closure_14, &closure_15);
+    let closure_17 = _var_name(Rules::type_ref, context, type_ref);
+    let closure_18 = _sequence(&closure_16, &closure_17);
+    let closure_19 = move |parent: Key, source: &Source, position: u32| {
+        ws_only_regular_comment(parent, context, source, position)
+    };
+    let closure_20 = _sequence(&closure_18, &closure_19);
+    closure_20(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn string_literal<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = _terminal(b'"');
+    let closure_2 = _terminal(b'"');
+    let closure_3 = _not_predicate(&closure_2);
+    let closure_4 =
+        move |parent: Key, source: &Source, position: u32| ascii(parent, context, source, position);
+    let closure_5 = _sequence(&closure_3, &closure_4);
+    let closure_6 = _subexpression(&closure_5);
+    let closure_7 = _zero_or_more(&closure_6);
+    let closure_8 = _sequence(&closure_1, &closure_7);
+    let closure_9 = _terminal(b'"');
+    let closure_10 = _sequence(&closure_8, &closure_9);
+    closure_10(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn boolean<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = _string_terminal_opt_ascii(&[b't', b'r', b'u', b'e']);
+    let closure_2 = _string_terminal_opt_ascii(&[b'f', b'a', b'l', b's', b'e']);
+    let closure_3 = _ordered_choice(&closure_1, &closure_2);
+    let closure_4 = _subexpression(&closure_3);
+    let closure_5 = move |parent: Key, source: &Source, position: u32| {
+        type_char_with_num(parent, context, source, position)
+    };
+    let closure_6 = _not_predicate(&closure_5);
+    let closure_7 = _sequence(&closure_4, &closure_6);
+    closure_7(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn constant_ref<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = move |parent: Key, source: &Source, position: u32| {
+        type_name(parent, context, source, position)
+    };
+    let closure_2 = _terminal(b'.');
+    let closure_3 = move |parent: Key, source: &Source, position: u32| {
+        type_name(parent, context, source, position)
+    };
+    let closure_4 = _sequence(&closure_2, &closure_3);
+    let closure_5 = _subexpression(&closure_4);
+    let closure_6 = _zero_or_more(&closure_5);
+    let closure_7 = _sequence(&closure_1, &closure_6);
+    closure_7(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn parenthesised<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = _terminal(b'(');
+    let closure_2 = move |parent: Key, source: &Source, position: u32| {
+        wsn_nocomment(parent, context, source, position)
+    };
+    let closure_3 = _sequence(&closure_1, &closure_2);
+    let closure_4 = _var_name(Rules::expression, context, expression);
+    let closure_5 = _sequence(&closure_3, &closure_4);
+    let closure_6 = move |parent: Key, source: &Source, position: u32| {
+        wsn_nocomment(parent, context, source, position)
+    };
+    let closure_7 = _sequence(&closure_5, &closure_6);
+    let closure_8 = _terminal(b')');
+    let closure_9 = _sequence(&closure_7, &closure_8);
+    closure_9(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn negation<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = _terminal(b'-');
+    let closure_2 = move |parent: Key, source: &Source, position: u32| {
+        wsn_nocomment(parent, context, source, position)
+    };
+    let closure_3 = _sequence(&closure_1, &closure_2);
+    let closure_4 = _var_name(Rules::factor, context, factor);
+    let closure_5 = _sequence(&closure_3, &closure_4);
+    closure_5(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn factor<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = _var_name(Rules::number, context, number);
+    let closure_2 = _var_name(Rules::boolean, context, boolean);
+    let closure_3 = _ordered_choice(&closure_1, &closure_2);
+    let closure_4 = _var_name(Rules::string_literal, context, string_literal);
+    let closure_5 = _ordered_choice(&closure_3, &closure_4);
+    let closure_6 = _var_name(Rules::parenthesised, context, parenthesised);
+    let closure_7 = _ordered_choice(&closure_5, &closure_6);
+    let closure_8 = _var_name(Rules::negation, context, negation);
+    let closure_9 = _ordered_choice(&closure_7, &closure_8);
+    let closure_10 = _var_name(Rules::constant_ref, context, constant_ref);
+    let closure_11 = _ordered_choice(&closure_9, &closure_10);
+    closure_11(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn multiplicative_operator<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    //  A slash that starts a comment is not a division
+    let closure_1 = _terminal(b'*');
+    let closure_2 = _terminal(b'/');
+    let closure_3 = _terminal(b'/');
+    let closure_4 = _not_predicate(&closure_3);
+    let closure_5 = _sequence(&closure_2, &closure_4);
+    let closure_6 = _terminal(b'*');
+    let closure_7 = _not_predicate(&closure_6);
+    let closure_8 = _sequence(&closure_5, &closure_7);
+    let closure_9 = _subexpression(&closure_8);
+    let closure_10 = _ordered_choice(&closure_1, &closure_9);
+    let closure_11 = _terminal(b'%');
+    let closure_12 = _ordered_choice(&closure_10, &closure_11);
+    closure_12(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn term<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = _var_name(Rules::factor, context, factor);
+    let closure_2 = move |parent: Key, source: &Source, position: u32| {
+        wsn_nocomment(parent, context, source, position)
+    };
+    let closure_3 = _var_name(
+        Rules::multiplicative_operator,
+        context,
+        multiplicative_operator,
+    );
+    let closure_4 = _sequence(&closure_2, &closure_3);
+    let closure_5 = move |parent: Key, source: &Source, position: u32| {
+        wsn_nocomment(parent, context, source, position)
+    };
+    let closure_6 = _sequence(&closure_4, &closure_5);
+    let closure_7 = _var_name(Rules::factor, context, factor);
+    let closure_8 = _sequence(&closure_6, &closure_7);
+    let closure_9 = _subexpression(&closure_8);
+    let closure_10 = _zero_or_more(&closure_9);
+    let closure_11 = _sequence(&closure_1, &closure_10);
+    closure_11(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn additive_operator<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = _terminal(b'+');
+    let closure_2 = _terminal(b'-');
+    let closure_3 = _ordered_choice(&closure_1, &closure_2);
+    closure_3(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn expression<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    //  Comments are not allowed inside a constant initializer so the formatter can rebuild it
+    let closure_1 = _var_name(Rules::term, context, term);
+    let closure_2 = move |parent: Key, source: &Source, position: u32| {
+        wsn_nocomment(parent, context, source, position)
+    };
+    let closure_3 = _var_name(Rules::additive_operator, context, additive_operator);
+    let closure_4 = _sequence(&closure_2, &closure_3);
+    let closure_5 = move |parent: Key, source: &Source, position: u32| {
+        wsn_nocomment(parent, context, source, position)
+    };
+    let closure_6 = _sequence(&closure_4, &closure_5);
+    let closure_7 = _var_name(Rules::term, context, term);
+    let closure_8 = _sequence(&closure_6, &closure_7);
+    let closure_9 = _subexpression(&closure_8);
+    let closure_10 = _zero_or_more(&closure_9);
+    let closure_11 = _sequence(&closure_1, &closure_10);
+    closure_11(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn initializer<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = _var_name(Rules::array_initializer, context, array_initializer);
+    let closure_2 = _var_name(Rules::struct_initializer, context, struct_initializer);
+    let closure_3 = _ordered_choice(&closure_1, &closure_2);
+    let closure_4 = _var_name(Rules::expression, context, expression);
+    let closure_5 = _ordered_choice(&closure_3, &closure_4);
+    closure_5(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn array_initializer<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = _terminal(b'[');
+    let closure_2 = move |parent: Key, source: &Source, position: u32| {
+        wsn_nocomment(parent, context, source, position)
+    };
+    let closure_3 = _sequence(&closure_1, &closure_2);
+    let closure_4 = move |parent: Key, source: &Source, position: u32| {
+        initializer(parent, context, source, position)
+    };
+    let closure_5 = move |parent: Key, source: &Source, position: u32| {
+        wsn_nocomment(parent, context, source, position)
+    };
+    let closure_6 = _sequence(&closure_4, &closure_5);
+    let closure_7 = _terminal(b',');
+    let closure_8 = move |parent: Key, source: &Source, position: u32| {
+        wsn_nocomment(parent, context, source, position)
+    };
+    let closure_9 = _sequence(&closure_7, &closure_8);
+    let closure_10 = move |parent: Key, source: &Source, position: u32| {
+        initializer(parent, context, source, position)
+    };
+    let closure_11 = _sequence(&closure_9, &closure_10);
+    let closure_12 = move |parent: Key, source: &Source, position: u32| {
+        wsn_nocomment(parent, context, source, position)
+    };
+    let closure_13 = _sequence(&closure_11, &closure_12);
+    let closure_14 = _subexpression(&closure_13);
+    let closure_15 = _zero_or_more(&closure_14);
+    let closure_16 = _sequence(&closure_6, &closure_15);
+    let closure_17 = _terminal(b',');
+    let closure_18 = move |parent: Key, source: &Source, position: u32| {
+        wsn_nocomment(parent, context, source, position)
+    };
+    let closure_19 = _sequence(&closure_17, &closure_18);
+    let closure_20 = _subexpression(&closure_19);
+    let closure_21 = _optional(&closure_20);
+    let closure_22 = _sequence(&closure_16, &closure_21);
+    let closure_23 = _subexpression(&closure_22);
+    let closure_24 = _optional(&closure_23);
+    let closure_25 = _sequence(&closure_3, &closure_24);
+    let closure_26 = _terminal(b']');
+    let closure_27 = _sequence(&closure_25, &closure_26);
+    closure_27(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn field_initializer<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    let closure_1 = _var_name(Rules::variable_name, context, variable_name);
     let closure_2 = move |parent: Key, source: &Source, position: u32| {
-        ws_atlone(parent, context, source, position)
+        wsn_nocomment(parent, context, source, position)
     };
     let closure_3 = _sequence(&closure_1, &closure_2);
-    let closure_4 = _string_terminal_opt_ascii(&[b'm', b'o', b'd', b'e', b'l']);
+    let closure_4 = _terminal(b':');
     let closure_5 = _sequence(&closure_3, &closure_4);
     let closure_6 = move |parent: Key, source: &Source, position: u32| {
-        ws_atlone(parent, context, source, position)
+        wsn_nocomment(parent, context, source, position)
     };
     let closure_7 = _sequence(&closure_5, &closure_6);
-    let closure_8 = _var_name(Rules::file_path, context, file_path);
-    let closure_9 = _sequence(&closure_7, &closure_8);
-    let closure_10 = move |parent: Key, source: &Source, position: u32| {
-        ws_only_regular_comment(parent, context, source, position)
+    let closure_8 = move |parent: Key, source: &Source, position: u32| {
+        initializer(parent, context, source, position)
     };
-    let closure_11 = _sequence(&closure_9, &closure_10);
-    closure_11(parent, source, position)
+    let closure_9 = _sequence(&closure_7, &closure_8);
+    closure_9(parent, source, position)
 }
 #[allow(dead_code)]
-pub fn open_bracket<T: Context>(
+pub fn struct_initializer<T: Context>(
     parent: Key,
     context: &RefCell<T>,
     source: &Source,
     position: u32,
 ) -> (bool, u32) {
-    //  These two are relevant for formatting and can be inlined for anything else just like comments
     let closure_1 = _terminal(b'{');
-    closure_1(parent, source, position)
-}
-#[allow(dead_code)]
-pub fn close_bracket<T: Context>(
-    parent: Key,
-    context: &RefCell<T>,
-    source: &Source,
-    position: u32,
-) -> (bool, u32) {
-    let closure_1 = _terminal(b'}');
-    closure_1(parent, source, position)
+    let closure_2 = move |parent: Key, source: &Source, position: u32| {
+        wsn_nocomment(parent, context, source, position)
+    };
+    let closure_3 = _sequence(&closure_1, &closure_2);
+    let closure_4 = _var_name(Rules::field_initializer, context, field_initializer);
+    let closure_5 = move |parent: Key, source: &Source, position: u32| {
+        wsn_nocomment(parent, context, source, position)
+    };
+    let closure_6 = _sequence(&closure_4, &closure_5);
+    let closure_7 = _terminal(b',');
+    let closure_8 = move |parent: Key, source: &Source, position: u32| {
+        wsn_nocomment(parent, context, source, position)
+    };
+    let closure_9 = _sequence(&closure_7, &closure_8);
+    let closure_10 = _var_name(Rules::field_initializer, context, field_initializer);
+    let closure_11 = _sequence(&closure_9, &closure_10);
+    let closure_12 = move |parent: Key, source: &Source, position: u32| {
+        wsn_nocomment(parent, context, source, position)
+    };
+    let closure_13 = _sequence(&closure_11, &closure_12);
+    let closure_14 = _subexpression(&closure_13);
+    let closure_15 = _zero_or_more(&closure_14);
+    let closure_16 = _sequence(&closure_6, &closure_15);
+    let closure_17 = _terminal(b',');
+    let closure_18 = move |parent: Key, source: &Source, position: u32| {
+        wsn_nocomment(parent, context, source, position)
+    };
+    let closure_19 = _sequence(&closure_17, &closure_18);
+    let closure_20 = _subexpression(&closure_19);
+    let closure_21 = _optional(&closure_20);
+    let closure_22 = _sequence(&closure_16, &closure_21);
+    let closure_23 = _subexpression(&closure_22);
+    let closure_24 = _optional(&closure_23);
+    let closure_25 = _sequence(&closure_3, &closure_24);
+    let closure_26 = _terminal(b'}');
+    let closure_27 = _sequence(&closure_25, &closure_26);
+    closure_27(parent, source, position)
 }
 #[allow(dead_code)]
-pub fn attribute<T: Context>(
+pub fn constant<T: Context>(
     parent: Key,
     context: &RefCell<T>,
     source: &Source,
@@ -663,8 +1465,7 @@ pub fn attribute<T: Context>(
     let closure_3 =
         move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
     let closure_4 = _sequence(&closure_2, &closure_3);
-    let closure_5 =
-        _string_terminal_opt_ascii(&[b'a', b't', b't', b'r', b'i', b'b', b'u', b't', b'e']);
+    let closure_5 = _string_terminal_opt_ascii(&[b'c', b'o', b'n', b's', b't']);
     let closure_6 = _sequence(&closure_4, &closure_5);
     let closure_7 = move |parent: Key, source: &Source, position: u32| {
         ws_atlone(parent, context, source, position)
@@ -678,76 +1479,55 @@ pub fn attribute<T: Context>(
     let closure_12 = _sequence(&closure_10, &closure_11);
     let closure_13 = _var_name(Rules::variable_name, context, variable_name);
     let closure_14 = _sequence(&closure_12, &closure_13);
-    let closure_15 = move |parent: Key, source: &Source, position: u32| {
+    let closure_15 =
+        move |parent: Key, source: &Source, position: u32| ws(parent, context, source, position);
+    let closure_16 = _sequence(&closure_14, &closure_15);
+    let closure_17 = _terminal(b'=');
+    let closure_18 = _sequence(&closure_16, &closure_17);
+    let closure_19 = move |parent: Key, source: &Source, position: u32| {
+        wsn_nocomment(parent, context, source, position)
+    };
+    let closure_20 = _sequence(&closure_18, &closure_19);
+    let closure_21 = move |parent: Key, source: &Source, position: u32| {
+        initializer(parent, context, source, position)
+    };
+    let closure_22 = _sequence(&closure_20, &closure_21);
+    let closure_23 = move |parent: Key, source: &Source, position: u32| {
         ws_only_regular_comment(parent, context, source, position)
     };
-    let closure_16 = _sequence(&closure_14, &closure_15);
-    closure_16(parent, source, position)
+    let closure_24 = _sequence(&closure_22, &closure_23);
+    closure_24(parent, source, position)
 }
 #[allow(dead_code)]
-pub fn variable_declaration<T: Context>(
+pub fn extends<T: Context>(
     parent: Key,
     context: &RefCell<T>,
     source: &Source,
     position: u32,
 ) -> (bool, u32) {
-    let closure_1 = _var_name(Rules::annotation_block, context, annotation_block);
-    let closure_2 = _optional(&closure_1);
-    let closure_3 =
-        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
-    let closure_4 = _sequence(&closure_2, &closure_3);
-    let closure_5 = _var_name(Rules::type_ref, context, type_ref);
-    let closure_6 = _sequence(&closure_4, &closure_5);
-    let closure_7 =
-        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
-    let closure_8 = _sequence(&closure_6, &closure_7);
-    let closure_9 = _var_name(Rules::variable_name, context, variable_name);
-    let closure_10 = _sequence(&closure_8, &closure_9);
-    let closure_11 = move |parent: Key, source: &Source, position: u32| {
-        ws_only_regular_comment(parent, context, source, position)
+    let closure_1 = _string_terminal_opt_ascii(&[b'e', b'x', b't', b'e', b'n', b'd', b's']);
+    let closure_2 = move |parent: Key, source: &Source, position: u32| {
+        ws_atlone(parent, context, source, position)
     };
-    let closure_12 = _sequence(&closure_10, &closure_11);
-    closure_12(parent, source, position)
+    let closure_3 = _sequence(&closure_1, &closure_2);
+    let closure_4 = _var_name(Rules::type_ref, context, type_ref);
+    let closure_5 = _sequence(&closure_3, &closure_4);
+    closure_5(parent, source, position)
 }
 #[allow(dead_code)]
-pub fn input_params<T: Context>(
+pub fn polymorphic<T: Context>(
     parent: Key,
     context: &RefCell<T>,
     source: &Source,
     position: u32,
 ) -> (bool, u32) {
-    let closure_1 = _var_name(Rules::annotation_block, context, annotation_block);
-    let closure_2 = _optional(&closure_1);
-    let closure_3 =
-        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
-    let closure_4 = _sequence(&closure_2, &closure_3);
-    let closure_5 = _string_terminal_opt_ascii(&[b'i', b'n']);
-    let closure_6 = _sequence(&closure_4, &closure_5);
-    let closure_7 =
-        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
-    let closure_8 = _sequence(&closure_6, &closure_7);
-    let closure_9 = _var_name(Rules::open_bracket, context, open_bracket);
-    let closure_10 = _sequence(&closure_8, &closure_9);
-    let closure_11 =
-        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
-    let closure_12 = _sequence(&closure_10, &closure_11);
-    let closure_13 = _var_name(Rules::variable_declaration, context, variable_declaration);
-    let closure_14 =
-        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
-    let closure_15 = _sequence(&closure_13, &closure_14);
-    let closure_16 = _subexpression(&closure_15);
-    let closure_17 = _zero_or_more(&closure_16);
-    let closure_18 = _sequence(&closure_12, &closure_17);
-    let closure_19 = _var_name(Rules::close_bracket, context, close_bracket);
-    let closure_20 = _sequence(&closure_18, &closure_19);
-    let closure_21 = move |parent: Key, source: &Source, position: u32| {
-        ws_only_regular_comment(parent, context, source, position)
-    };
-    let closure_22 = _sequence(&closure_20, &closure_21);
-    closure_22(parent, source, position)
+    let closure_1 = _string_terminal_opt_ascii(&[
+        b'p', b'o', b'l', b'y', b'm', b'o', b'r', b'p', b'h', b'i', b'c',
+    ]);
+    closure_1(parent, source, position)
 }
 #[allow(dead_code)]
-pub fn output_params<T: Context>(
+pub fn structure<T: Context>(
     parent: Key,
     context: &RefCell<T>,
     source: &Source,
@@ -758,33 +1538,48 @@ pub fn output_params<T: Context>(
     let closure_3 =
         move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
     let closure_4 = _sequence(&closure_2, &closure_3);
-    let closure_5 = _string_terminal_opt_ascii(&[b'o', b'u', b't']);
+    let closure_5 = _string_terminal_opt_ascii(&[b's', b't', b'r', b'u', b'c', b't']);
     let closure_6 = _sequence(&closure_4, &closure_5);
     let closure_7 =
-        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+        move |parent: Key, source: &Source, position: u32| ws(parent, context, source, position);
     let closure_8 = _sequence(&closure_6, &closure_7);
-    let closure_9 = _var_name(Rules::open_bracket, context, open_bracket);
+    let closure_9 = _var_name(Rules::type_dec, context, type_dec);
     let closure_10 = _sequence(&closure_8, &closure_9);
     let closure_11 =
         move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
     let closure_12 = _sequence(&closure_10, &closure_11);
-    let closure_13 = _var_name(Rules::variable_declaration, context, variable_declaration);
-    let closure_14 =
-        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
-    let closure_15 = _sequence(&closure_13, &closure_14);
+    let closure_13 = _var_name(Rules::extends, context, extends);
+    let closure_14 = _var_name(Rules::polymorphic, context, polymorphic);
+    let closure_15 = _ordered_choice(&closure_13, &closure_14);
     let closure_16 = _subexpression(&closure_15);
-    let closure_17 = _zero_or_more(&closure_16);
-    let closure_18 = _sequence(&closure_12, &closure_17);
-    let closure_19 = _var_name(Rules::close_bracket, context, close_bracket);
-    let closure_20 = _sequence(&closure_18, &closure_19);
-    let closure_21 = move |parent: Key, source: &Source, position: u32| {
+    let closure_17 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_18 = _sequence(&closure_16, &closure_17);
+    let closure_19 = _subexpression(&closure_18);
+    let closure_20 = _optional(&closure_19);
+    let closure_21 = _sequence(&closure_12, &closure_20);
+    let closure_22 = _var_name(Rules::open_bracket, context, open_bracket);
+    let closure_23 = _sequence(&closure_21, &closure_22);
+    let closure_24 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_25 = _sequence(&closure_23, &closure_24);
+    let closure_26 = _var_name(Rules::variable_declaration, context, variable_declaration);
+    let closure_27 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_28 = _sequence(&closure_26, &closure_27);
+    let closure_29 = _subexpression(&closure_28);
+    let closure_30 = _zero_or_more(&closure_29);
+    let closure_31 = _sequence(&closure_25, &closure_30);
+    let closure_32 = _var_name(Rules::close_bracket, context, close_bracket);
+    let closure_33 = _sequence(&closure_31, &closure_32);
+    let closure_34 = move |parent: Key, source: &Source, position: u32| {
         ws_only_regular_comment(parent, context, source, position)
     };
-    let closure_22 = _sequence(&closure_20, &closure_21);
-    closure_22(parent, source, position)
+    let closure_35 = _sequence(&closure_33, &closure_34);
+    closure_35(parent, source, position)
 }
 #[allow(dead_code)]
-pub fn method<T: Context>(
+pub fn union<T: Context>(
     parent: Key,
     context: &RefCell<T>,
     source: &Source,
@@ -795,43 +1590,45 @@ pub fn method<T: Context>(
     let closure_3 =
         move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
     let closure_4 = _sequence(&closure_2, &closure_3);
-    let closure_5 = _string_terminal_opt_ascii(&[b'm', b'e', b't', b'h', b'o', b'd']);
+    let closure_5 = _string_terminal_opt_ascii(&[b'u', b'n', b'i', b'o', b'n']);
     let closure_6 = _sequence(&closure_4, &closure_5);
     let closure_7 =
-        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+        move |parent: Key, source: &Source, position: u32| ws(parent, context, source, position);
     let closure_8 = _sequence(&closure_6, &closure_7);
-    let closure_9 = _var_name(Rules::variable_name, context, variable_name);
+    let closure_9 = _var_name(Rules::type_dec, context, type_dec);
     let closure_10 = _sequence(&closure_8, &closure_9);
     let closure_11 =
         move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
     let closure_12 = _sequence(&closure_10, &closure_11);
-    let closure_13 = _var_name(Rules::open_bracket, context, open_bracket);
-    let closure_14 = _sequence(&closure_12, &closure_13);
-    let closure_15 =
+    let closure_13 = _var_name(Rules::extends, context, extends);
+    let closure_14 =
         move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
-    let closure_16 = _sequence(&closure_14, &closure_15);
-    let closure_17 = _var_name(Rules::input_params, context, input_params);
-    let closure_18 = _optional(&closure_17);
-    let closure_19 = _sequence(&closure_16, &closure_18);
-    let closure_20 =
+    let closure_15 = _sequence(&closure_13, &closure_14);
+    let closure_16 = _subexpression(&closure_15);
+    let closure_17 = _optional(&closure_16);
+    let closure_18 = _sequence(&closure_12, &closure_17);
+    let closure_19 = _var_name(Rules::open_bracket, context, open_bracket);
+    let closure_20 = _sequence(&closure_18, &closure_19);
+    let closure_21 =
         move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
-    let closure_21 = _sequence(&closure_19, &closure_20);
-    let closure_22 = _var_name(Rules::output_params, context, output_params);
-    let closure_23 = _optional(&closure_22);
-    let closure_24 = _sequence(&closure_21, &closure_23);
-    let closure_25 =
+    let closure_22 = _sequence(&closure_20, &closure_21);
+    let closure_23 = _var_name(Rules::variable_declaration, context, variable_declaration);
+    let closure_24 =
         move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
-    let closure_26 = _sequence(&closure_24, &closure_25);
-    let closure_27 = _var_name(Rules::close_bracket, context, close_bracket);
-    let closure_28 = _sequence(&closure_26, &closure_27);
-    let closure_29 = move |parent: Key, source: &Source, position: u32| {
+    let closure_25 = _sequence(&closure_23, &closure_24);
+    let closure_26 = _subexpression(&closure_25);
+    let closure_27 = _zero_or_more(&closure_26);
+    let closure_28 = _sequence(&closure_22, &closure_27);
+    let closure_29 = _var_name(Rules::close_bracket, context, close_bracket);
+    let closure_30 = _sequence(&closure_28, &closure_29);
+    let closure_31 = move |parent: Key, source: &Source, position: u32| {
         ws_only_regular_comment(parent, context, source, position)
     };
-    let closure_30 = _sequence(&closure_28, &closure_29);
-    closure_30(parent, source, position)
+    let closure_32 = _sequence(&closure_30, &closure_31);
+    closure_32(parent, source, position)
 }
 #[allow(dead_code)]
-pub fn typedef<T: Context>(
+pub fn array_type<T: Context>(
     parent: Key,
     context: &RefCell<T>,
     source: &Source,
@@ -839,11 +1636,10 @@ pub fn typedef<T: Context>(
 ) -> (bool, u32) {
     let closure_1 = _var_name(Rules::annotation_block, context, annotation_block);
     let closure_2 = _optional(&closure_1);
-    let closure_3 = move |parent: Key, source: &Source, position: u32| {
-        wsn_nocomment(parent, context, source, position)
-    };
+    let closure_3 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
     let closure_4 = _sequence(&closure_2, &closure_3);
-    let closure_5 = _string_terminal_opt_ascii(&[b't', b'y', b'p', b'e', b'd', b'e', b'f']);
+    let closure_5 = _string_terminal_opt_ascii(&[b'a', b'r', b'r', b'a', b'y']);
     let closure_6 = _sequence(&closure_4, &closure_5);
     let closure_7 = move |parent: Key, source: &Source, position: u32| {
         ws_atlone(parent, context, source, position)
@@ -855,7 +1651,7 @@ pub fn typedef<T: Context>(
         ws_atlone(parent, context, source, position)
     };
     let closure_12 = _sequence(&closure_10, &closure_11);
-    let closure_13 = _string_terminal_opt_ascii(&[b'i', b's']);
+    let closure_13 = _string_terminal_opt_ascii(&[b'o', b'f']);
     let closure_14 = _sequence(&closure_12, &closure_13);
     let closure_15 = move |parent: Key, source: &Source, position: u32| {
         ws_atlone(parent, context, source, position)
@@ -870,7 +1666,7 @@ pub fn typedef<T: Context>(
     closure_20(parent, source, position)
 }
 #[allow(dead_code)]
-pub fn structure<T: Context>(
+pub fn map<T: Context>(
     parent: Key,
     context: &RefCell<T>,
     source: &Source,
@@ -881,7 +1677,7 @@ pub fn structure<T: Context>(
     let closure_3 =
         move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
     let closure_4 = _sequence(&closure_2, &closure_3);
-    let closure_5 = _string_terminal_opt_ascii(&[b's', b't', b'r', b'u', b'c', b't']);
+    let closure_5 = _string_terminal_opt_ascii(&[b'm', b'a', b'p']);
     let closure_6 = _sequence(&closure_4, &closure_5);
     let closure_7 =
         move |parent: Key, source: &Source, position: u32| ws(parent, context, source, position);
@@ -896,20 +1692,30 @@ pub fn structure<T: Context>(
     let closure_15 =
         move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
     let closure_16 = _sequence(&closure_14, &closure_15);
-    let closure_17 = _var_name(Rules::variable_declaration, context, variable_declaration);
-    let closure_18 =
-        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
-    let closure_19 = _sequence(&closure_17, &closure_18);
-    let closure_20 = _subexpression(&closure_19);
-    let closure_21 = _zero_or_more(&closure_20);
-    let closure_22 = _sequence(&closure_16, &closure_21);
-    let closure_23 = _var_name(Rules::close_bracket, context, close_bracket);
+    let closure_17 = _var_name(Rules::type_ref, context, type_ref);
+    let closure_18 = _sequence(&closure_16, &closure_17);
+    let closure_19 = move |parent: Key, source: &Source, position: u32| {
+        ws_atlone(parent, context, source, position)
+    };
+    let closure_20 = _sequence(&closure_18, &closure_19);
+    let closure_21 = _string_terminal_opt_ascii(&[b't', b'o']);
+    let closure_22 = _sequence(&closure_20, &closure_21);
+    let closure_23 = move |parent: Key, source: &Source, position: u32| {
+        ws_atlone(parent, context, source, position)
+    };
     let closure_24 = _sequence(&closure_22, &closure_23);
-    let closure_25 = move |parent: Key, source: &Source, position: u32| {
+    let closure_25 = _var_name(Rules::type_ref, context, type_ref);
+    let closure_26 = _sequence(&closure_24, &closure_25);
+    let closure_27 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_28 = _sequence(&closure_26, &closure_27);
+    let closure_29 = _var_name(Rules::close_bracket, context, close_bracket);
+    let closure_30 = _sequence(&closure_28, &closure_29);
+    let closure_31 = move |parent: Key, source: &Source, position: u32| {
         ws_only_regular_comment(parent, context, source, position)
     };
-    let closure_26 = _sequence(&closure_24, &closure_25);
-    closure_26(parent, source, position)
+    let closure_32 = _sequence(&closure_30, &closure_31);
+    closure_32(parent, source, position)
 }
 #[allow(dead_code)]
 pub fn enumeration<T: Context>(
@@ -935,25 +1741,32 @@ pub fn enumeration<T: Context>(
     let closure_11 =
         move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
     let closure_12 = _sequence(&closure_10, &closure_11);
-    let closure_13 = _var_name(Rules::open_bracket, context, open_bracket);
-    let closure_14 = _sequence(&closure_12, &closure_13);
-    let closure_15 =
+    let closure_13 = _var_name(Rules::extends, context, extends);
+    let closure_14 =
         move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
-    let closure_16 = _sequence(&closure_14, &closure_15);
-    let closure_17 = _var_name(Rules::enum_value, context, enum_value);
-    let closure_18 =
+    let closure_15 = _sequence(&closure_13, &closure_14);
+    let closure_16 = _subexpression(&closure_15);
+    let closure_17 = _optional(&closure_16);
+    let closure_18 = _sequence(&closure_12, &closure_17);
+    let closure_19 = _var_name(Rules::open_bracket, context, open_bracket);
+    let closure_20 = _sequence(&closure_18, &closure_19);
+    let closure_21 =
         move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
-    let closure_19 = _sequence(&closure_17, &closure_18);
-    let closure_20 = _subexpression(&closure_19);
-    let closure_21 = _zero_or_more(&closure_20);
-    let closure_22 = _sequence(&closure_16, &closure_21);
-    let closure_23 = _var_name(Rules::close_bracket, context, close_bracket);
-    let closure_24 = _sequence(&closure_22, &closure_23);
-    let closure_25 = move |parent: Key, source: &Source, position: u32| {
+    let closure_22 = _sequence(&closure_20, &closure_21);
+    let closure_23 = _var_name(Rules::enum_value, context, enum_value);
+    let closure_24 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_25 = _sequence(&closure_23, &closure_24);
+    let closure_26 = _subexpression(&closure_25);
+    let closure_27 = _zero_or_more(&closure_26);
+    let closure_28 = _sequence(&closure_22, &closure_27);
+    let closure_29 = _var_name(Rules::close_bracket, context, close_bracket);
+    let closure_30 = _sequence(&closure_28, &closure_29);
+    let closure_31 = move |parent: Key, source: &Source, position: u32| {
         ws_only_regular_comment(parent, context, source, position)
     };
-    let closure_26 = _sequence(&closure_24, &closure_25);
-    closure_26(parent, source, position)
+    let closure_32 = _sequence(&closure_30, &closure_31);
+    closure_32(parent, source, position)
 }
 #[allow(dead_code)]
 pub fn enum_value<T: Context>(
@@ -1090,43 +1903,60 @@ pub fn interface<T: Context>(
     let closure_11 =
         move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
     let closure_12 = _sequence(&closure_10, &closure_11);
-    let closure_13 = _var_name(Rules::open_bracket, context, open_bracket);
-    let closure_14 = _sequence(&closure_12, &closure_13);
-    let closure_15 =
+    let closure_13 = _var_name(Rules::extends, context, extends);
+    let closure_14 =
         move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
-    let closure_16 = _sequence(&closure_14, &closure_15);
-    let closure_17 = _var_name(Rules::version, context, version);
-    let closure_18 = _optional(&closure_17);
-    let closure_19 = _sequence(&closure_16, &closure_18);
-    let closure_20 =
+    let closure_15 = _sequence(&closure_13, &closure_14);
+    let closure_16 = _subexpression(&closure_15);
+    let closure_17 = _optional(&closure_16);
+    let closure_18 = _sequence(&closure_12, &closure_17);
+    let closure_19 = _var_name(Rules::open_bracket, context, open_bracket);
+    let closure_20 = _sequence(&closure_18, &closure_19);
+    let closure_21 =
         move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
-    let closure_21 = _sequence(&closure_19, &closure_20);
-    let closure_22 = _var_name(Rules::method, context, method);
-    let closure_23 = _var_name(Rules::typedef, context, typedef);
-    let closure_24 = _ordered_choice(&closure_22, &closure_23);
-    let closure_25 = _var_name(Rules::structure, context, structure);
-    let closure_26 = _ordered_choice(&closure_24, &closure_25);
-    let closure_27 = _var_name(Rules::attribute, context, attribute);
-    let closure_28 = _ordered_choice(&closure_26, &closure_27);
-    let closure_29 = _var_name(Rules::enumeration, context, enumeration);
+    let closure_22 = _sequence(&closure_20, &closure_21);
+    let closure_23 = _var_name(Rules::version, context, version);
+    let closure_24 = _optional(&closure_23);
+    let closure_25 = _sequence(&closure_22, &closure_24);
+    let closure_26 =
+        move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
+    let closure_27 = _sequence(&closure_25, &closure_26);
+    let closure_28 = _var_name(Rules::method, context, method);
+    let closure_29 = _var_name(Rules::broadcast, context, broadcast);
     let closure_30 = _ordered_choice(&closure_28, &closure_29);
-    let closure_31 = _subexpression(&closure_30);
-    let closure_32 =
+    let closure_31 = _var_name(Rules::typedef, context, typedef);
+    let closure_32 = _ordered_choice(&closure_30, &closure_31);
+    let closure_33 = _var_name(Rules::constant, context, constant);
+    let closure_34 = _ordered_choice(&closure_32, &closure_33);
+    let closure_35 = _var_name(Rules::structure, context, structure);
+    let closure_36 = _ordered_choice(&closure_34, &closure_35);
+    let closure_37 = _var_name(Rules::union, context, union);
+    let closure_38 = _ordered_choice(&closure_36, &closure_37);
+    let closure_39 = _var_name(Rules::array_type, context, array_type);
+    let closure_40 = _ordered_choice(&closure_38, &closure_39);
+    let closure_41 = _var_name(Rules::map, context, map);
+    let closure_42 = _ordered_choice(&closure_40, &closure_41);
+    let closure_43 = _var_name(Rules::attribute, context, attribute);
+    let closure_44 = _ordered_choice(&closure_42, &closure_43);
+    let closure_45 = _var_name(Rules::enumeration, context, enumeration);
+    let closure_46 = _ordered_choice(&closure_44, &closure_45);
+    let closure_47 = _subexpression(&closure_46);
+    let closure_48 =
         move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
-    let closure_33 = _sequence(&closure_31, &closure_32);
-    let closure_34 = _subexpression(&closure_33);
-    let closure_35 = _zero_or_more(&closure_34);
-    let closure_36 = _sequence(&closure_21, &closure_35);
-    let closure_37 =
+    let closure_49 = _sequence(&closure_47, &closure_48);
+    let closure_50 = _subexpression(&closure_49);
+    let closure_51 = _zero_or_more(&closure_50);
+    let closure_52 = _sequence(&closure_27, &closure_51);
+    let closure_53 =
         move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
-    let closure_38 = _sequence(&closure_36, &closure_37);
-    let closure_39 = _var_name(Rules::close_bracket, context, close_bracket);
-    let closure_40 = _sequence(&closure_38, &closure_39);
-    let closure_41 = move |parent: Key, source: &Source, position: u32| {
+    let closure_54 = _sequence(&closure_52, &closure_53);
+    let closure_55 = _var_name(Rules::close_bracket, context, close_bracket);
+    let closure_56 = _sequence(&closure_54, &closure_55);
+    let closure_57 = move |parent: Key, source: &Source, position: u32| {
         ws_only_regular_comment(parent, context, source, position)
     };
-    let closure_42 = _sequence(&closure_40, &closure_41);
-    closure_42(parent, source, position)
+    let closure_58 = _sequence(&closure_56, &closure_57);
+    closure_58(parent, source, position)
 }
 #[allow(dead_code)]
 pub fn type_collection<T: Context>(
@@ -1165,24 +1995,45 @@ pub fn type_collection<T: Context>(
         move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
     let closure_22 = _sequence(&closure_20, &closure_21);
     let closure_23 = _var_name(Rules::typedef, context, typedef);
-    let closure_24 = _var_name(Rules::structure, context, structure);
+    let closure_24 = _var_name(Rules::constant, context, constant);
     let closure_25 = _ordered_choice(&closure_23, &closure_24);
-    let closure_26 = _var_name(Rules::enumeration, context, enumeration);
+    let closure_26 = _var_name(Rules::structure, context, structure);
     let closure_27 = _ordered_choice(&closure_25, &closure_26);
-    let closure_28 = _subexpression(&closure_27);
-    let closure_29 =
+    let closure_28 = _var_name(Rules::union, context, union);
+    let closure_29 = _ordered_choice(&closure_27, &closure_28);
+    let closure_30 = _var_name(Rules::array_type, context, array_type);
+    let closure_31 = _ordered_choice(&closure_29, &closure_30);
+    let closure_32 = _var_name(Rules::map, context, map);
+    let closure_33 = _ordered_choice(&closure_31, &closure_32);
+    let closure_34 = _var_name(Rules::enumeration, context, enumeration);
+    let closure_35 = _ordered_choice(&closure_33, &closure_34);
+    let closure_36 = _subexpression(&closure_35);
+    let closure_37 =
         move |parent: Key, source: &Source, position: u32| wsn(parent, context, source, position);
-    let closure_30 = _sequence(&closure_28, &closure_29);
-    let closure_31 = _subexpression(&closure_30);
-    let closure_32 = _zero_or_more(&closure_31);
-    let closure_33 = _sequence(&closure_22, &closure_32);
-    let closure_34 = _var_name(Rules::close_bracket, context, close_bracket);
-    let closure_35 = _sequence(&closure_33, &closure_34);
-    let closure_36 = move |parent: Key, source: &Source, position: u32| {
+    let closure_38 = _sequence(&closure_36, &closure_37);
+    let closure_39 = _subexpression(&closure_38);
+    let closure_40 = _zero_or_more(&closure_39);
+    let closure_41 = _sequence(&closure_22, &closure_40);
+    let closure_42 = _var_name(Rules::close_bracket, context, close_bracket);
+    let closure_43 = _sequence(&closure_41, &closure_42);
+    let closure_44 = move |parent: Key, source: &Source, position: u32| {
         ws_only_regular_comment(parent, context, source, position)
     };
-    let closure_37 = _sequence(&closure_35, &closure_36);
-    closure_37(parent, source, position)
+    let closure_45 = _sequence(&closure_43, &closure_44);
+    closure_45(parent, source, position)
+}
+#[allow(dead_code)]
+pub fn skipped<T: Context>(
+    parent: Key,
+    context: &RefCell<T>,
+    source: &Source,
+    position: u32,
+) -> (bool, u32) {
+    //  Never matched by the grammar itself, marks the source error recovery skipped over
+    let closure_1 =
+        move |parent: Key, source: &Source, position: u32| ascii(parent, context, source, position);
+    let closure_2 = _one_or_more(&closure_1);
+    closure_2(parent, source, position)
 }
 #[allow(dead_code)]
 pub fn grammar<T: Context>(
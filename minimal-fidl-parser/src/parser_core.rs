@@ -1,4 +1,5 @@
 use super::Source;
+use crate::failure::Expected;
 use crate::Key;
 
 pub fn _and_predicate_kernel(
@@ -8,7 +9,9 @@ pub fn _and_predicate_kernel(
     func: impl Fn(Key, &Source, u32) -> (bool, u32),
 ) -> (bool, u32) {
     let temp_position = position;
+    source.silence_failures();
     let (valid, _position) = func(parent, source, temp_position);
+    source.unsilence_failures();
     if !valid {
         return (false, temp_position);
     }
@@ -30,7 +33,9 @@ pub fn _not_predicate_kernel(
     func: impl Fn(Key, &Source, u32) -> (bool, u32),
 ) -> (bool, u32) {
     let temp_position = position;
+    source.silence_failures();
     let (valid, _position) = func(parent, source, temp_position);
+    source.unsilence_failures();
     if !valid {
         return (true, temp_position);
     }
@@ -179,12 +184,25 @@ pub fn _subexpression(
     }
 }
 
+/// Every byte as a slice of itself, a failed `_terminal` reports what it expected without allocating.
+static BYTES: [u8; 256] = {
+    let mut bytes = [0; 256];
+    let mut byte = 0;
+    while byte < 256 {
+        bytes[byte] = byte as u8;
+        byte += 1;
+    }
+    bytes
+};
+
 pub fn _terminal_kernel(source: &Source, position: u32, chr: u8) -> (bool, u32) {
     let char = source.get_char(position);
     //let char = char.expect("\nThis error only happens with invalid grammar. E.g **. This should be disallowed at generation time!!\n");
     if char == Some(chr) {
         (true, position + 1)
     } else {
+        let terminal = std::slice::from_ref(&BYTES[chr as usize]);
+        source.record_failure(position, Expected::Terminal(terminal));
         (false, position)
     }
 }
@@ -221,23 +239,24 @@ pub fn _zero_or_more(
 
 // Optimizations
 
-fn _string_terminal_kernel_ascii_opt(source: &Source, position: u32, data: &[u8]) -> (bool, u32) {
+fn _string_terminal_kernel_ascii_opt(
+    source: &Source,
+    position: u32,
+    data: &'static [u8],
+) -> (bool, u32) {
     let mut end_position = position;
     for char in data {
-        let s = match source.get_char(end_position) {
-            None => return (false, position),
-            Some(val) => val,
-        };
-        if *char == s {
+        if source.get_char(end_position) == Some(*char) {
             end_position += 1;
         } else {
+            source.record_failure(position, Expected::Terminal(data));
             return (false, position);
         }
     }
     (true, end_position)
 }
 
-pub fn _string_terminal_opt_ascii(data: &[u8]) -> impl Fn(Key, &Source, u32) -> (bool, u32) + '_ {
+pub fn _string_terminal_opt_ascii(data: &'static [u8]) -> impl Fn(Key, &Source, u32) -> (bool, u32) {
     move |_parent: Key, source: &Source, position: u32| {
         _string_terminal_kernel_ascii_opt(source, position, data)
     }
@@ -251,14 +270,11 @@ fn _ordered_choice_match_range_kernel(
 ) -> (bool, u32) {
     let chr = source.get_char(position);
     match chr {
-        Some(value) => {
-            if (value as u32 >= start) && (value as u32 <= end) {
-                (true, position + 1)
-            } else {
-                (false, position)
-            }
+        Some(value) if (value as u32 >= start) && (value as u32 <= end) => (true, position + 1),
+        _ => {
+            source.record_failure(position, Expected::Range(start, end));
+            (false, position)
         }
-        None => (false, position),
     }
 }
 
@@ -59,6 +59,13 @@ impl BasicPublisher {
         self.nodes.clear();
     }
 
+    /// Empties the tree to parse another source, the memory already allocated for nodes is kept.
+    pub fn reset(&mut self, size_of_source: usize, number_of_rules: usize) {
+        self.nodes.clear();
+        self.nodes.reserve((size_of_source * number_of_rules) / 64);
+        self.add_node(Rules::Grammar, 0, 0, true);
+    }
+
     pub fn add_node(
         &mut self,
         rule: Rules,
@@ -106,6 +113,18 @@ impl BasicPublisher {
         parent_node.children.push(child_index);
         parent_node.children.append(&mut copy_of_children);
     }
+    /// Connects a child so the children of the parent stay ordered by their start position.
+    pub(crate) fn connect_in_order(&mut self, parent_index: Key, child_index: Key) {
+        let start_position = self.get_node(child_index).start_position;
+        let children = &self.get_node(parent_index).children;
+        let index = children
+            .iter()
+            .position(|child| self.get_node(*child).start_position > start_position)
+            .unwrap_or(children.len());
+        self.get_mut_node(parent_index)
+            .children
+            .insert(index, child_index);
+    }
     pub fn connect_if_not_connected(&mut self, parent_index: Key, child_index: Key) {
         //println!("Connecting: {:?} <- {:?}", parent_index, child_index);
         let parent_node: &mut Node = self.get_mut_node(parent_index);
@@ -132,6 +151,11 @@ impl BasicPublisher {
         }
     }
 
+    /// Drops the node at `index` and every node added after it, nothing before it may point at them.
+    pub(crate) fn truncate(&mut self, index: Key) {
+        self.nodes.truncate(usize::from(index));
+    }
+
     pub fn set_node_start_position(&mut self, index: Key, start_position: u32) {
         self.nodes[usize::from(index)].start_position = start_position
     }
@@ -0,0 +1,349 @@
+use crate::incremental::{copy_subtree, LOOKAHEAD};
+use crate::parse::parse_with_context;
+use crate::parser::{import_model, import_namespace, interface, type_collection, wsn};
+use crate::parser_core::_ordered_choice;
+use crate::var_name::_var_name;
+use crate::{BasicContext, BasicPublisher, Context, Key, ParseFailure, Rules, Source, RULES_SIZE};
+use std::cell::RefCell;
+
+/// Keywords that start a declaration, parsing resumes at a line that starts with one of these.
+const DECLARATION_KEYWORDS: [&str; 15] = [
+    "package",
+    "import",
+    "interface",
+    "typeCollection",
+    "version",
+    "method",
+    "broadcast",
+    "attribute",
+    "typedef",
+    "const",
+    "struct",
+    "union",
+    "enumeration",
+    "array",
+    "map",
+];
+
+/// The result of `parse_with_recovery`.
+#[derive(Debug)]
+pub struct RecoveredParse {
+    /// Everything that parsed, each region that did not is a `Rules::skipped` node inside the
+    /// grammar, interface or type collection it was found in.
+    pub publisher: BasicPublisher,
+    /// Why each region was skipped, ordered by position.
+    pub failures: Vec<ParseFailure>,
+}
+
+/// Parses a whole file, skipping over declarations that do not parse instead of stopping at the first one.
+///
+/// When parsing fails the declaration the failure is in, up to its closing bracket or the next line
+/// starting with a keyword like `method` or `struct`, is blanked out. Parsing resumes after the last
+/// package, import, interface or type collection that ended before it, everything before is kept.
+/// Byte offsets never change so the tree is used with the original source like any other.
+/// Fails if no amount of skipping lets the file parse, i.e a broken package or a missing closing bracket
+/// at the end of the file.
+pub fn parse_with_recovery(source: &str) -> Result<RecoveredParse, ParseFailure> {
+    let mut blanked = source.to_string();
+    let mut failures: Vec<ParseFailure> = Vec::new();
+    let mut skipped: Vec<(usize, usize)> = Vec::new();
+    // Blanking keeps the length the same so every attempt fits in the same context.
+    let context = RefCell::new(BasicContext::new(source.len(), RULES_SIZE as usize));
+    let (result, mut position, publisher, mut furthest) = parse_with_context(&blanked, &context);
+    if result && position == blanked.len() as u32 {
+        return Ok(RecoveredParse {
+            publisher,
+            failures,
+        });
+    }
+    // The top level items that parsed so far, each as the attempt it was parsed in and its key there.
+    let mut attempts: Vec<BasicPublisher> = Vec::new();
+    let mut items: Vec<(usize, Key)> = Vec::new();
+    if let Some(grammar) = publisher.get_node(Key(0)).get_children().first() {
+        items.extend(
+            publisher
+                .get_node(*grammar)
+                .get_children()
+                .iter()
+                .map(|key| (0, *key)),
+        );
+    }
+    attempts.push(publisher);
+    while position != blanked.len() as u32 {
+        let failure = with_original_column(source, furthest);
+        let failed_at = (failure.position as usize).min(blanked.len());
+        if blanked[failed_at..].trim().is_empty() {
+            return Err(failure);
+        }
+        let code = mask(&blanked);
+        let start = declaration_start(&code, failed_at);
+        let end = declaration_end(&code, start);
+        let end = start + blanked[start..end].trim_end().len();
+        if blanked[start..end].trim().is_empty() {
+            // Nothing left to skip so parsing would fail in the same place forever.
+            return Err(failure);
+        }
+        // Items whose trailing comment check could have looked into the blanked region are parsed again.
+        let end_of = |(attempt, key): &(usize, Key)| attempts[*attempt].get_node(*key).end_position;
+        let kept = items
+            .iter()
+            .take_while(|item| end_of(item) + LOOKAHEAD <= start as u32)
+            .count();
+        items.truncate(kept);
+        let rule_of = |(attempt, key): &(usize, Key)| attempts[*attempt].get_node(*key).rule;
+        if !items.iter().any(|item| rule_of(item) == Rules::package) {
+            // Without a package nothing after it can parse.
+            return Err(failure);
+        }
+        blank(&mut blanked, start, end);
+        failures.push(failure);
+        skipped.push((start, end));
+        let imports = !items
+            .iter()
+            .any(|item| matches!(rule_of(item), Rules::interface | Rules::type_collection));
+        let resume = items.last().map_or(0, end_of);
+
+        let attempt = Source::new(&blanked);
+        context
+            .borrow_mut()
+            .reset(blanked.len(), RULES_SIZE as usize);
+        position = parse_top_level(&context, &attempt, resume, imports);
+        furthest = attempt.furthest_failure();
+        let publisher = context.borrow().publisher().clear_false();
+        let index = attempts.len();
+        items.extend(
+            publisher
+                .get_node(Key(0))
+                .get_children()
+                .iter()
+                .map(|key| (index, *key)),
+        );
+        attempts.push(publisher);
+    }
+
+    let mut publisher = BasicPublisher::new(attempts[0].len(), 1);
+    let grammar = publisher.add_node(Rules::Grammar, 0, position, true);
+    publisher.connect(Key(0), grammar);
+    for (attempt, key) in items {
+        copy_subtree(&mut publisher, grammar, &attempts[attempt], key, &|p| p);
+    }
+    for (start, end) in merge(skipped) {
+        connect_skipped(&mut publisher, start as u32, end as u32);
+    }
+    failures.sort_by_key(|failure| failure.position);
+    Ok(RecoveredParse {
+        publisher,
+        failures,
+    })
+}
+
+/// The same as the top level of `grammar` after the package, starting at `position`.
+/// Imports are only allowed when no interface or type collection came before `position`.
+fn parse_top_level(
+    context: &RefCell<BasicContext>,
+    source: &Source,
+    position: u32,
+    imports: bool,
+) -> u32 {
+    let import_model = _var_name(Rules::import_model, context, import_model);
+    let import_namespace = _var_name(Rules::import_namespace, context, import_namespace);
+    let import = _ordered_choice(&import_model, &import_namespace);
+    let interface = _var_name(Rules::interface, context, interface);
+    let type_collection = _var_name(Rules::type_collection, context, type_collection);
+    let declaration = _ordered_choice(&interface, &type_collection);
+    let mut position = wsn(Key(0), context, source, position).1;
+    if imports {
+        position = repeat(context, source, position, &import);
+    }
+    repeat(context, source, position, &declaration)
+}
+
+/// Matches `item` followed by whitespace as many times as possible.
+fn repeat(
+    context: &RefCell<BasicContext>,
+    source: &Source,
+    mut position: u32,
+    item: &impl Fn(Key, &Source, u32) -> (bool, u32),
+) -> u32 {
+    loop {
+        let (result, end) = item(Key(0), source, position);
+        if !result {
+            return position;
+        }
+        position = wsn(Key(0), context, source, end).1;
+    }
+}
+
+/// Replaces comments, strings and annotation contents with spaces so brackets and keywords inside
+/// them are ignored. Only ascii is ever replaced from one ascii delimiter to another so offsets and
+/// character boundaries do not change.
+fn mask(source: &str) -> Vec<u8> {
+    let bytes = source.as_bytes();
+    let find = |from: usize, pattern: &[u8]| {
+        bytes[from..]
+            .windows(pattern.len())
+            .position(|window| window == pattern)
+            .map_or(bytes.len(), |index| from + index + pattern.len())
+    };
+    let mut code = bytes.to_vec();
+    let mut index = 0;
+    while index < bytes.len() {
+        let rest = &bytes[index..];
+        let (mask_from, mask_to) = if rest.starts_with(b"//") {
+            (index, find(index, b"\n"))
+        } else if rest.starts_with(b"/*") {
+            (index, find(index + 2, b"*/"))
+        } else if rest.starts_with(b"<**") {
+            // The opening stays so an annotation block still starts a declaration.
+            (index + 3, find(index + 3, b"**>"))
+        } else if rest[0] == b'"' {
+            (index, find(index + 1, b"\""))
+        } else {
+            index += 1;
+            continue;
+        };
+        for byte in &mut code[mask_from..mask_to] {
+            if *byte != b'\n' {
+                *byte = b' ';
+            }
+        }
+        index = mask_to;
+    }
+    code
+}
+
+/// Whether a declaration keyword or an annotation block starts at the beginning of `code`.
+fn is_declaration(code: &[u8]) -> bool {
+    if code.starts_with(b"<**") {
+        return true;
+    }
+    DECLARATION_KEYWORDS.iter().any(|keyword| {
+        code.strip_prefix(keyword.as_bytes()).is_some_and(|rest| {
+            !rest
+                .first()
+                .is_some_and(|byte| byte.is_ascii_alphanumeric() || *byte == b'_')
+        })
+    })
+}
+
+/// Where the declaration containing `position` starts.
+/// Only declarations directly inside the grammar, an interface or a type collection are skipped, so
+/// a failure nested deeper, i.e inside a struct, skips the whole struct.
+/// A line that fails at its start, i.e a misspelt keyword, is skipped on its own.
+fn declaration_start(code: &[u8], position: usize) -> usize {
+    let mut depth: usize = 0;
+    let mut line_first = true;
+    let mut start: Option<usize> = None;
+    for (offset, byte) in code.iter().enumerate().take(position) {
+        if line_first
+            && depth <= 1
+            && !byte.is_ascii_whitespace()
+            && is_declaration(&code[offset..])
+        {
+            start = Some(offset);
+        }
+        match byte {
+            b'{' => depth += 1,
+            b'}' => depth = depth.saturating_sub(1),
+            b'\n' => line_first = true,
+            _ => {}
+        }
+        if !byte.is_ascii_whitespace() {
+            line_first = false;
+        }
+    }
+    // A closing bracket ends whatever it is in, so it can only be part of a declaration before it.
+    if line_first && depth <= 1 && code.get(position).is_some_and(|byte| *byte != b'}') {
+        return position;
+    }
+    start.unwrap_or(position)
+}
+
+/// Where the declaration starting at `start` ends, after its closing bracket, before the closing
+/// bracket of whatever it is in or before the next line that starts a declaration.
+fn declaration_end(code: &[u8], start: usize) -> usize {
+    let mut depth: usize = 0;
+    let mut line_first = false;
+    for (offset, byte) in code.iter().enumerate().skip(start) {
+        if line_first
+            && depth == 0
+            && !byte.is_ascii_whitespace()
+            && is_declaration(&code[offset..])
+        {
+            return offset;
+        }
+        match byte {
+            b'{' => depth += 1,
+            b'}' if depth == 0 => return offset,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return offset + 1;
+                }
+            }
+            b'\n' => line_first = true,
+            _ => {}
+        }
+        if !byte.is_ascii_whitespace() {
+            line_first = false;
+        }
+    }
+    code.len()
+}
+
+/// Replaces everything between `start` and `end` with spaces, keeping line endings and byte offsets.
+fn blank(source: &mut String, start: usize, end: usize) {
+    let blanked: String = source[start..end]
+        .chars()
+        .map(|c| match c {
+            '\n' | '\r' => c.to_string(),
+            c => " ".repeat(c.len_utf8()),
+        })
+        .collect();
+    source.replace_range(start..end, &blanked);
+}
+
+/// Columns count characters and blanking may have replaced a wide character before the failure.
+fn with_original_column(source: &str, mut failure: ParseFailure) -> ParseFailure {
+    let mut position = (failure.position as usize).min(source.len());
+    while !source.is_char_boundary(position) {
+        position -= 1;
+    }
+    let line_start = source[..position].rfind('\n').map_or(0, |index| index + 1);
+    failure.column = source[line_start..position].chars().count() + 1;
+    failure
+}
+
+/// Later skips can grow into earlier ones, those are reported as one region.
+fn merge(mut skipped: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+    skipped.sort();
+    let mut merged: Vec<(usize, usize)> = Vec::new();
+    for (start, end) in skipped {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Adds a skipped node to the innermost interface or type collection it is in, else to the grammar.
+fn connect_skipped(publisher: &mut BasicPublisher, start: u32, end: u32) {
+    let mut parent = publisher.get_node(Key(0)).get_children()[0];
+    while let Some(child) = publisher
+        .get_node(parent)
+        .get_children()
+        .iter()
+        .find(|child| {
+            let node = publisher.get_node(**child);
+            matches!(node.rule, Rules::interface | Rules::type_collection)
+                && node.start_position <= start
+                && end <= node.end_position
+        })
+        .copied()
+    {
+        parent = child;
+    }
+    let skipped = publisher.add_node(Rules::skipped, start, end, true);
+    publisher.connect_in_order(parent, skipped);
+}
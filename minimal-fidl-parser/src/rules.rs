@@ -10,26 +10,38 @@ impl From<u32> for Rules {
     }
 }
 #[allow(dead_code)]
-pub static RULES_SIZE: u32 = 41;
+pub static RULES_SIZE: u32 = 70;
 #[allow(clippy::upper_case_acronyms)] // Again due to generation -> Might solve eventually
 #[derive(PartialEq, Eq, Hash, FromPrimitive, Clone, Copy, Debug, Ord, PartialOrd)]
 
 pub enum Rules {
     Grammar,
+    additive_operator,
     annotation,
     annotation_block,
     annotation_content,
     annotation_name,
     array,
+    array_initializer,
+    array_type,
     attribute,
     binary,
+    boolean,
+    broadcast,
     close_bracket,
     comment,
+    constant,
+    constant_ref,
     digits,
     enum_value,
     enumeration,
     exponent,
+    expression,
+    extends,
+    factor,
+    field_initializer,
     file_path,
+    fire_and_forget,
     float,
     fraction,
     hex,
@@ -37,21 +49,38 @@ pub enum Rules {
     import_namespace,
     input_params,
     integer,
+    integer_range,
     interface,
     major,
+    map,
+    max_int,
     method,
+    method_error,
+    min_int,
     minor,
     multiline_comment,
+    multiplicative_operator,
+    negation,
+    no_subscriptions,
     number,
     open_bracket,
     output_params,
     package,
+    parenthesised,
+    polymorphic,
+    readonly,
+    selective,
     sign,
+    skipped,
+    string_literal,
+    struct_initializer,
     structure,
+    term,
     type_collection,
     type_dec,
     type_ref,
     typedef,
+    union,
     variable_declaration,
     variable_name,
     version,
@@ -1,7 +1,13 @@
+use crate::failure::{Expected, FailureTracker, ParseFailure};
+use crate::Rules;
+use std::cell::RefCell;
+
 pub struct Source<'a> {
     // Acts as immutable string since Source should never be modified.
     source: &'a str,
     source_len: u32,
+    // Only bookkeeping about the parse, the source itself is never modified.
+    failures: RefCell<FailureTracker>,
 }
 impl<'a> From<Source<'a>> for String {
     fn from(i: Source) -> String {
@@ -17,7 +23,40 @@ impl<'a> From<&Source<'a>> for String {
 impl<'a> Source<'a> {
     pub fn new(source: &'a str) -> Source<'a> {
         let source_len = source.len() as u32;
-        Source { source, source_len }
+        Source {
+            source,
+            source_len,
+            failures: RefCell::new(FailureTracker::default()),
+        }
+    }
+
+    /// The furthest position the parser could not get past and what it expected there.
+    /// Only meaningful once a parse has failed or stopped before the end of the source.
+    pub fn furthest_failure(&self) -> ParseFailure {
+        self.failures.borrow().to_failure(self.source)
+    }
+
+    pub(crate) fn record_failure(&self, position: u32, expected: Expected) {
+        self.failures.borrow_mut().record(position, expected);
+    }
+
+    pub(crate) fn enter_rule(&self, rule: Rules) {
+        self.failures.borrow_mut().enter_rule(rule);
+    }
+
+    pub(crate) fn exit_rule(&self, rule: Rules, is_true: bool, start_position: u32) {
+        self.failures
+            .borrow_mut()
+            .exit_rule(rule, is_true, start_position);
+    }
+
+    /// Stops recording failures until `unsilence_failures`, used by lookaheads which are expected to fail.
+    pub(crate) fn silence_failures(&self) {
+        self.failures.borrow_mut().silence();
+    }
+
+    pub(crate) fn unsilence_failures(&self) {
+        self.failures.borrow_mut().unsilence();
     }
 
     pub fn get_char(&self, position: u32) -> Option<u8> {
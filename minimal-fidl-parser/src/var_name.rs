@@ -12,7 +12,10 @@ fn memoized_behaviour<T: Context>(
     end_position: u32,
     memoized_key: Key,
 ) -> (bool, u32) {
-    context.borrow_mut().connect(parent, memoized_key);
+    // A failed result may have had its node discarded, failed nodes are never part of the tree anyway.
+    if is_true {
+        context.borrow_mut().connect(parent, memoized_key);
+    }
     (is_true, end_position)
 }
 
@@ -25,8 +28,15 @@ fn default_behaviour<T: Context>(
     start_position: u32,
 ) -> (bool, u32) {
     let current_key = context.borrow_mut().reserve_publisher_entry(rule);
+    source.enter_rule(rule);
     let f = func(current_key, context, source, start_position);
+    source.exit_rule(rule, f.0, start_position);
     let mut c = context.borrow_mut();
+    if !f.0 && c.discard_failed(current_key) {
+        // Only the result is kept so the rule is not parsed again here.
+        c.create_cache_entry(rule, f.0, start_position, f.1, current_key);
+        return f;
+    }
     c.create_cache_entry(rule, f.0, start_position, f.1, current_key);
     c.update_publisher_entry(current_key, f.0, start_position, f.1);
     // Change to only connect on success to makes things a little faster
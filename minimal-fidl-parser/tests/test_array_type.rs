@@ -0,0 +1,32 @@
+use minimal_fidl_parser::{array_type, BasicContext, Rules};
+mod shared;
+use shared::shared;
+
+#[test]
+fn test_array_type_1() {
+    let src = r#"array Names of String"#;
+    let result = shared(src, array_type::<BasicContext>, Rules::array_type);
+    assert_eq!(result, (true, src.len() as u32));
+}
+
+#[test]
+fn test_array_type_2() {
+    let src = r#"<** @length: 4 **>
+    array Matrix of MyTypes.Row[] // Comment"#;
+    let result = shared(src, array_type::<BasicContext>, Rules::array_type);
+    assert_eq!(result, (true, src.len() as u32));
+}
+
+#[test]
+fn test_array_type_3() {
+    let src = r#"array Percentages of Integer(0, 100)"#;
+    let result = shared(src, array_type::<BasicContext>, Rules::array_type);
+    assert_eq!(result, (true, src.len() as u32));
+}
+
+#[test]
+fn test_array_type_4() {
+    let src = r#"array Names String"#;
+    let result = shared(src, array_type::<BasicContext>, Rules::array_type);
+    assert_eq!(result, (false, 0));
+}
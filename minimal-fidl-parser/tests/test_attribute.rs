@@ -42,3 +42,17 @@ fn test_attribute_2() {
         assert_eq!(result, (true, str.len() as u32));
     }
 }
+
+#[test]
+fn test_attribute_3() {
+    let strs = vec![
+        "attribute UInt8 volume readonly",
+        "attribute UInt8 volume noSubscriptions",
+        "attribute UInt8 volume readonly noSubscriptions // Comment",
+        "<** @description: Current volume. **>\n\tattribute UInt8[] volume \treadonly",
+    ];
+    for str in strs {
+        let result = shared(str, attribute::<BasicContext>, Rules::attribute);
+        assert_eq!(result, (true, str.len() as u32));
+    }
+}
@@ -0,0 +1,34 @@
+use minimal_fidl_parser::{broadcast, BasicContext, Rules};
+mod shared;
+use shared::shared;
+
+#[test]
+fn test_broadcast_1() {
+    let src = r#"<** @description: Emitted whenever the playlist ends. **>
+	broadcast endOfPlaylist {
+		out {
+			UInt32 playedTracks
+			Boolean repeat
+		}
+	}"#;
+    let result = shared(src, broadcast::<BasicContext>, Rules::broadcast);
+    assert_eq!(result, (true, src.len() as u32));
+}
+
+#[test]
+fn test_broadcast_2() {
+    let src = r#"broadcast statusChanged selective {
+		out {
+			String status // Comment
+		}
+	}"#;
+    let result = shared(src, broadcast::<BasicContext>, Rules::broadcast);
+    assert_eq!(result, (true, src.len() as u32));
+}
+
+#[test]
+fn test_broadcast_3() {
+    let src = r#"broadcast ping { }"#;
+    let result = shared(src, broadcast::<BasicContext>, Rules::broadcast);
+    assert_eq!(result, (true, src.len() as u32));
+}
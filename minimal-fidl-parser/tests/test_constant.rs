@@ -0,0 +1,67 @@
+mod shared;
+use minimal_fidl_parser::{constant, expression, BasicContext, Rules};
+use shared::shared;
+
+#[test]
+fn test_constant_1() {
+    let src = "const UInt32 MAX = 10";
+    let result = shared(src, constant::<BasicContext>, Rules::constant);
+    assert_eq!(result, (true, src.len() as u32));
+}
+
+#[test]
+fn test_constant_2() {
+    let src = r#"<** @description: Greeting used by the server. **>
+    const String GREETING = "Hello, World" // Comment"#;
+    let result = shared(src, constant::<BasicContext>, Rules::constant);
+    assert_eq!(result, (true, src.len() as u32));
+}
+
+#[test]
+fn test_constant_3() {
+    let src = "const Boolean ENABLED = true";
+    let result = shared(src, constant::<BasicContext>, Rules::constant);
+    assert_eq!(result, (true, src.len() as u32));
+}
+
+#[test]
+fn test_constant_4() {
+    let src = "const UInt8[] DEFAULTS = [1, 0x02, 0b11,
+        4,
+    ]";
+    let result = shared(src, constant::<BasicContext>, Rules::constant);
+    assert_eq!(result, (true, src.len() as u32));
+}
+
+#[test]
+fn test_constant_5() {
+    let src = r#"const MyTypes.Point ORIGIN = { x: 0.0, y: -1.5e3, label: "origin", tags: [] }"#;
+    let result = shared(src, constant::<BasicContext>, Rules::constant);
+    assert_eq!(result, (true, src.len() as u32));
+}
+
+#[test]
+fn test_constant_6() {
+    let src = "const Int32 MIN = -(MAX * 2 + 1) % 7 - MyTypes.OFFSET";
+    let result = shared(src, constant::<BasicContext>, Rules::constant);
+    assert_eq!(result, (true, src.len() as u32));
+}
+
+#[test]
+fn test_expression_1() {
+    // A trailing comment is not part of the expression.
+    let src = "1 + 2 // Comment";
+    let result = shared(src, expression::<BasicContext>, Rules::expression);
+    assert_eq!(result, (true, 5));
+    let src = "1 + 2 /* Comment */";
+    let result = shared(src, expression::<BasicContext>, Rules::expression);
+    assert_eq!(result, (true, 5));
+}
+
+#[test]
+fn test_expression_2() {
+    // `trueValue` is a reference, not the boolean `true`.
+    let src = "trueValue";
+    let result = shared(src, expression::<BasicContext>, Rules::expression);
+    assert_eq!(result, (true, src.len() as u32));
+}
@@ -0,0 +1,73 @@
+use minimal_fidl_parser::{
+    _var_name, grammar, parse, parse_with_context, BasicContext, Context, Key, Rules, Source,
+    RULES_SIZE,
+};
+use std::cell::RefCell;
+use std::fs;
+
+fn read(name: &str) -> String {
+    fs::read_to_string(format!("tests/grammar_test_files/{}", name)).unwrap()
+}
+
+#[test]
+fn test_context_reuse_1() {
+    let a = read("test1.fidl");
+    let b = read("05-CoverageInterface.fidl");
+    let context = RefCell::new(BasicContext::new(a.len(), RULES_SIZE as usize));
+    let (result, position, _, _) = parse_with_context(&a, &context);
+    assert_eq!((result, position), (true, a.len() as u32));
+
+    let reused = parse_with_context(&b, &context);
+    let fresh = parse(&b);
+    assert_eq!((reused.0, reused.1), (true, b.len() as u32));
+    assert_eq!((reused.0, reused.1), (fresh.0, fresh.1));
+    assert!(reused.2 == fresh.2);
+}
+
+#[test]
+fn test_context_reuse_2() {
+    // A failed parse leaves plenty behind in the cache.
+    let a = "package org.example\ninterface A {\n    metod broken {\n    }\n}\n";
+    let b = read("10-TypesInTypeCollection.fidl");
+    let context = RefCell::new(BasicContext::new(a.len(), RULES_SIZE as usize));
+    let (_, position, _, _) = parse_with_context(a, &context);
+    assert!(position < a.len() as u32);
+
+    let reused = parse_with_context(&b, &context);
+    let fresh = parse(&b);
+    assert_eq!((reused.0, reused.1), (true, b.len() as u32));
+    assert!(reused.2 == fresh.2);
+
+    // And the other way round, the failure is reported the same.
+    let reused = parse_with_context(a, &context);
+    let fresh = parse(a);
+    assert!(reused.1 < a.len() as u32);
+    assert_eq!((reused.0, reused.1), (fresh.0, fresh.1));
+    assert!(reused.2 == fresh.2);
+    assert_eq!(reused.3, fresh.3);
+}
+
+#[test]
+fn test_context_reuse_3() {
+    let a = read("test1.fidl");
+    let b = read("01-Minimal.fidl");
+    let context = RefCell::new(BasicContext::new(a.len(), RULES_SIZE as usize));
+    {
+        let source = Source::new(&a);
+        let executor = _var_name(Rules::Grammar, &context, grammar);
+        assert_eq!(executor(Key(0), &source, 0), (true, a.len() as u32));
+    }
+    let capacity = context.borrow().publisher().capacity();
+
+    context.borrow_mut().reset(b.len(), RULES_SIZE as usize);
+    assert_eq!(context.borrow().publisher().len(), 1);
+    {
+        let source = Source::new(&b);
+        let executor = _var_name(Rules::Grammar, &context, grammar);
+        assert_eq!(executor(Key(0), &source, 0), (true, b.len() as u32));
+    }
+    // The smaller source fits in what was allocated for the first.
+    assert_eq!(context.borrow().publisher().capacity(), capacity);
+    let reused = context.into_inner().get_publisher().clear_false();
+    assert!(reused == parse(&b).2);
+}
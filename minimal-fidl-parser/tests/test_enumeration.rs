@@ -96,3 +96,13 @@ fn test_enumeration_8() {
     let result = shared(src, enumeration::<BasicContext>, Rules::enumeration);
     assert_eq!(result, (true, src.len() as u32));
 }
+
+#[test]
+fn test_enumeration_9() {
+    let src = r#"enumeration MyDerivedEnum extends MyBaseEnum {
+		F = 11
+		G
+	}"#;
+    let result = shared(src, enumeration::<BasicContext>, Rules::enumeration);
+    assert_eq!(result, (true, src.len() as u32));
+}
@@ -0,0 +1,105 @@
+use minimal_fidl_parser::{parse, reparse};
+use std::fs;
+use std::ops::Range;
+
+/// Reparsing `src` with `edit` replaced by `text` gives the same as parsing the edited source.
+fn assert_reparse(src: &str, edit: Range<usize>, text: &str) {
+    let (_, _, previous, _) = parse(src);
+    let mut edited = src.to_string();
+    edited.replace_range(edit.clone(), text);
+    let reparsed = reparse(&previous, src, edit, text);
+    let full = parse(&edited);
+    assert_eq!((reparsed.0, reparsed.1), (full.0, full.1));
+    assert!(reparsed.2 == full.2);
+    if full.1 != edited.len() as u32 {
+        assert_eq!(reparsed.3, full.3);
+    }
+}
+
+const SRC: &str = "package org.example
+import org.example.* from \"other.fidl\"
+
+interface First {
+    method one {
+        in { UInt8 a }
+    }
+}
+
+// Between the declarations.
+typeCollection Types {
+    struct Point {
+        Int32 x
+        Int32 y
+    }
+}
+
+interface Last { // Trailing comment.
+    broadcast changed {
+        out { Types.Point p }
+    }
+}
+";
+
+#[test]
+fn test_incremental_1() {
+    // Inside the type collection, in the middle of the file.
+    let at = SRC.find("Int32 y").unwrap();
+    assert_reparse(SRC, at..at + 5, "Double");
+    assert_reparse(SRC, at..at + 7, "");
+    // Inside the last interface, nothing after it to reuse.
+    let at = SRC.find("changed").unwrap();
+    assert_reparse(SRC, at..at + 7, "renamed");
+    // A new declaration between two others.
+    let at = SRC.find("// Between").unwrap();
+    assert_reparse(SRC, at..at, "interface Added {\n}\n\n");
+    // Removing one.
+    let end = SRC.find("interface Last").unwrap();
+    assert_reparse(SRC, at..end, "");
+}
+
+#[test]
+fn test_incremental_2() {
+    // Edits in the package and imports parse everything again.
+    let at = SRC.find("example").unwrap();
+    assert_reparse(SRC, at..at + 7, "other");
+    let at = SRC.find("\"other.fidl\"").unwrap();
+    assert_reparse(SRC, at..at + 12, "\"renamed.fidl\"");
+    // Next to the end of a declaration its trailing comment can change.
+    let at = SRC.find(" // Trailing").unwrap();
+    assert_reparse(SRC, at..at + 1, "");
+    let at = SRC.find("}\n\n// Between").unwrap();
+    assert_reparse(SRC, at + 1..at + 1, " // Now trailing.");
+    assert_reparse(SRC, at + 1..at + 2, "/");
+}
+
+#[test]
+fn test_incremental_3() {
+    // Edits that break the source, or that change how everything after them parses.
+    let at = SRC.find("struct Point").unwrap();
+    assert_reparse(SRC, at..at + 6, "strct");
+    assert_reparse(SRC, at..at, "/*");
+    let at = SRC.find("interface Last").unwrap();
+    assert_reparse(SRC, at..at, "/* Commented out */\n");
+    assert_reparse(SRC, at..at, "}");
+    assert_reparse(SRC, SRC.len()..SRC.len(), "interface Unfinished {");
+}
+
+#[test]
+fn test_incremental_4() {
+    // Spread over every file, edits that do and do not keep the file parsing.
+    let mut sources = vec![SRC.to_string()];
+    for entry in fs::read_dir("tests/grammar_test_files").unwrap() {
+        sources.push(fs::read_to_string(entry.unwrap().path()).unwrap());
+    }
+    for src in sources {
+        for at in (0..src.len()).step_by(src.len() / 16 + 1) {
+            if !src.is_char_boundary(at) || !src.is_char_boundary(at + 1) {
+                continue;
+            }
+            for text in ["", " ", "\n", "x", "}", "//", "/*"] {
+                assert_reparse(&src, at..at + 1, text);
+            }
+            assert_reparse(&src, at..at, "\ninterface Inserted {\n}\n");
+        }
+    }
+}
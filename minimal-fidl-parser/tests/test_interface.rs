@@ -261,3 +261,44 @@ fn test_interface_5() {
     let result = shared(src, interface::<BasicContext>, Rules::interface);
     assert_eq!(result, (true, src.len() as u32));
 }
+
+#[test]
+fn test_interface_6() {
+    let src = "interface name {
+        union aUnion {
+            UInt8 a
+            String b
+        }
+        attribute aUnion u
+    }";
+    let result = shared(src, interface::<BasicContext>, Rules::interface);
+    assert_eq!(result, (true, src.len() as u32));
+}
+
+#[test]
+fn test_interface_7() {
+    let src = "interface name {
+        method getStatus {
+            out {
+                String status
+            }
+        }
+        broadcast statusChanged selective {
+            out {
+                String status
+            }
+        }
+    }";
+    let result = shared(src, interface::<BasicContext>, Rules::interface);
+    assert_eq!(result, (true, src.len() as u32));
+}
+
+#[test]
+fn test_interface_8() {
+    let src = "interface Derived extends Base {
+        version { major 1 minor 0 }
+        attribute UInt8 value
+    }";
+    let result = shared(src, interface::<BasicContext>, Rules::interface);
+    assert_eq!(result, (true, src.len() as u32));
+}
@@ -0,0 +1,36 @@
+use minimal_fidl_parser::{map, BasicContext, Rules};
+mod shared;
+use shared::shared;
+
+#[test]
+fn test_map_1() {
+    let src = r#"<** @description: Configuration table. **>
+	map ConfigTable {
+		String to UInt32
+	}"#;
+    let result = shared(src, map::<BasicContext>, Rules::map);
+    assert_eq!(result, (true, src.len() as u32));
+}
+
+#[test]
+fn test_map_2() {
+    let src = r#"map MyMap08 { MyTypes.MyEnum01 to MyStruct02[] } // Comment"#;
+    let result = shared(src, map::<BasicContext>, Rules::map);
+    assert_eq!(result, (true, src.len() as u32));
+}
+
+#[test]
+fn test_map_3() {
+    let src = r#"map MyMap05 {
+		UInt16 	to	String
+	}"#;
+    let result = shared(src, map::<BasicContext>, Rules::map);
+    assert_eq!(result, (true, src.len() as u32));
+}
+
+#[test]
+fn test_map_4() {
+    let src = r#"map MyMap05 { UInt16 String }"#;
+    let result = shared(src, map::<BasicContext>, Rules::map);
+    assert_eq!(result.0, false);
+}
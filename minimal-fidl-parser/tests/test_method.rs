@@ -77,3 +77,54 @@ fn test_method_6() {
     let result = shared(src, method::<BasicContext>, Rules::method);
     assert_eq!(result, (true, src.len() as u32));
 }
+
+#[test]
+fn test_method_7() {
+    let src = r#"method connect {
+		in {
+			String device
+		}
+		out {
+			UInt8 handle
+		}
+		error {
+			TIMEOUT
+			REFUSED = 2 // Comment
+		}
+	}"#;
+    let result = shared(src, method::<BasicContext>, Rules::method);
+    assert_eq!(result, (true, src.len() as u32));
+}
+
+#[test]
+fn test_method_8() {
+    let src = r#"method connect {
+		in {
+			String device
+		}
+		error MyTypes.ConnectError
+	}"#;
+    let result = shared(src, method::<BasicContext>, Rules::method);
+    assert_eq!(result, (true, src.len() as u32));
+}
+
+#[test]
+fn test_method_9() {
+    let src = r#"method disconnect {
+		<** @description: Nothing to disconnect from **>
+		error{ NOT_CONNECTED }
+	}"#;
+    let result = shared(src, method::<BasicContext>, Rules::method);
+    assert_eq!(result, (true, src.len() as u32));
+}
+
+#[test]
+fn test_method_10() {
+    let src = r#"method log fireAndForget {
+		in {
+			String message
+		}
+	}"#;
+    let result = shared(src, method::<BasicContext>, Rules::method);
+    assert_eq!(result, (true, src.len() as u32));
+}
@@ -0,0 +1,51 @@
+use core::cell::RefCell;
+use minimal_fidl_parser::{
+    grammar, BasicContext, Context, Expected, Key, ParseFailure, Rules, Source, _var_name, RULES_SIZE,
+};
+
+fn furthest_failure(src: &str) -> ParseFailure {
+    let source = Source::new(src);
+    let context = RefCell::new(BasicContext::new(src.len(), RULES_SIZE as usize));
+    let result = {
+        let executor = _var_name(Rules::Grammar, &context, grammar);
+        executor(Key(0), &source, 0)
+    };
+    assert_ne!(result, (true, src.len() as u32), "{} should not parse", src);
+    source.furthest_failure()
+}
+
+#[test]
+fn test_parse_failure_1() {
+    let src = "package org.example\ninterface MyInterface {\n    version { major 1 minor 0 }\n    metod doThing {\n    }\n}";
+    let failure = furthest_failure(src);
+    assert_eq!((failure.line, failure.column), (4, 5));
+    assert!(failure.expected.contains(&Expected::Terminal(b"method")));
+    assert!(failure.expected.contains(&Expected::Terminal(b"}")));
+    assert_eq!(failure.found.as_deref(), Some("metod"));
+    assert_eq!(
+        failure.to_string(),
+        "line 4 col 5: expected `<**`, `array`, `attribute`, `broadcast`, `const`, `enumeration`, `map`, `method`, `struct`, `typedef`, `union` or `}`, found `metod`"
+    );
+}
+#[test]
+fn test_parse_failure_2() {
+    let src = "package org.example\ntypeCollection MyTypes {\n    struct MyStruct {\n        UInt8\n    }\n}";
+    let failure = furthest_failure(src);
+    assert_eq!(failure.to_string(), "line 5 col 5: expected a name, found `}`");
+}
+#[test]
+fn test_parse_failure_3() {
+    let src = "package org.example\ntypeCollection MyTypes {\n";
+    let failure = furthest_failure(src);
+    assert_eq!(failure.position, src.len() as u32);
+    assert_eq!(failure.found, None);
+}
+#[test]
+fn test_parse_failure_4() {
+    let src = "package org.example\ntypeCollection MyTypes {\n  const UInt8 A = \n}";
+    let failure = furthest_failure(src);
+    assert_eq!(
+        failure.to_string(),
+        "line 4 col 1: expected `(`, `-`, `[`, `false`, `true`, `{`, a constant, a number or a string, found `}`"
+    );
+}
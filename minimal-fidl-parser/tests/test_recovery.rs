@@ -0,0 +1,194 @@
+use minimal_fidl_parser::{parse_with_recovery, BasicPublisher, Key, Node, Rules};
+
+fn children<'a>(publisher: &'a BasicPublisher, node: &Node) -> Vec<&'a Node> {
+    node.get_children()
+        .iter()
+        .map(|child| publisher.get_node(*child))
+        .collect()
+}
+
+fn grammar_node(publisher: &BasicPublisher) -> &Node {
+    let root = publisher.get_node(Key(0));
+    publisher.get_node(root.get_children()[0])
+}
+
+/// Every node under `key` except skipped ones as its rule and position, in order.
+fn flatten(publisher: &BasicPublisher, key: Key) -> Vec<(Rules, u32, u32)> {
+    let node = publisher.get_node(key);
+    let mut nodes = Vec::new();
+    if node.rule != Rules::skipped {
+        nodes.push((node.rule, node.start_position, node.end_position));
+    }
+    for child in node.get_children() {
+        nodes.extend(flatten(publisher, *child));
+    }
+    nodes
+}
+
+/// Replaces the source of every skipped node under `key` with spaces, keeping line endings.
+fn blank_skipped(publisher: &BasicPublisher, key: Key, source: &mut String) {
+    let node = publisher.get_node(key);
+    if node.rule == Rules::skipped {
+        let range = node.start_position as usize..node.end_position as usize;
+        let spaces: String = source[range.clone()]
+            .chars()
+            .map(|c| if c == '\n' { c } else { ' ' })
+            .collect();
+        source.replace_range(range, &spaces);
+    }
+    for child in node.get_children() {
+        blank_skipped(publisher, *child, source);
+    }
+}
+
+#[test]
+fn test_recovery_1() {
+    let src = "package org.example\ninterface MyInterface {\n    version { major 1 minor 0 }\n    method first {\n    }\n    metod doThing {\n        in { UInt8 a }\n    }\n    method second {\n    }\n}\n";
+    let recovered = parse_with_recovery(src).unwrap();
+    let publisher = &recovered.publisher;
+    assert_eq!(recovered.failures.len(), 1);
+    assert_eq!(
+        (recovered.failures[0].line, recovered.failures[0].column),
+        (6, 5)
+    );
+    let interface = children(publisher, grammar_node(publisher))
+        .into_iter()
+        .find(|node| node.rule == Rules::interface)
+        .unwrap();
+    let rules: Vec<Rules> = children(publisher, interface)
+        .iter()
+        .map(|node| node.rule)
+        .filter(|rule| matches!(rule, Rules::method | Rules::skipped))
+        .collect();
+    assert_eq!(rules, vec![Rules::method, Rules::skipped, Rules::method]);
+    let skipped = children(publisher, interface)
+        .into_iter()
+        .find(|node| node.rule == Rules::skipped)
+        .unwrap();
+    assert_eq!(
+        skipped.get_string(src),
+        "metod doThing {\n        in { UInt8 a }\n    }"
+    );
+}
+#[test]
+fn test_recovery_2() {
+    let src = r#"package org.example
+typeCollection MyTypes {
+    struct Broken {
+        UInt8
+    }
+    struct Fine {
+        UInt8 a
+    }
+    enumeration AlsoBroken {
+        A = ,
+    }
+    // A comment with a { that is ignored
+    union AlsoFine {
+        UInt8 b
+    }
+}
+interface MyInterface {
+    method doThing {
+        in { UInt8 a }
+    }
+}
+"#;
+    let recovered = parse_with_recovery(src).unwrap();
+    let publisher = &recovered.publisher;
+    let lines: Vec<usize> = recovered.failures.iter().map(|f| f.line).collect();
+    assert_eq!(lines, vec![5, 10]);
+    let top_level: Vec<Rules> = children(publisher, grammar_node(publisher))
+        .iter()
+        .map(|node| node.rule)
+        .filter(|rule| !matches!(rule, Rules::package))
+        .collect();
+    assert_eq!(top_level, vec![Rules::type_collection, Rules::interface]);
+    let type_collection = children(publisher, grammar_node(publisher))[1];
+    let skipped: Vec<String> = children(publisher, type_collection)
+        .iter()
+        .filter(|node| node.rule == Rules::skipped)
+        .map(|node| node.get_string(src))
+        .collect();
+    assert_eq!(
+        skipped,
+        vec![
+            "struct Broken {\n        UInt8\n    }",
+            "enumeration AlsoBroken {\n        A = ,\n    }"
+        ]
+    );
+    let declarations = children(publisher, type_collection)
+        .iter()
+        .filter(|node| matches!(node.rule, Rules::structure | Rules::union))
+        .count();
+    assert_eq!(declarations, 2);
+}
+#[test]
+fn test_recovery_3() {
+    // A file without errors parses the same as without recovery.
+    let src = "package org.example\ntypeCollection MyTypes {\n    struct Fine {\n        UInt8 a\n    }\n}\n";
+    let recovered = parse_with_recovery(src).unwrap();
+    assert!(recovered.failures.is_empty());
+    let (result, position, publisher, _) = minimal_fidl_parser::parse(src);
+    assert_eq!((result, position), (true, src.len() as u32));
+    assert_eq!(recovered.publisher, publisher);
+}
+#[test]
+fn test_recovery_4() {
+    // Nothing can be skipped to make up for a missing closing bracket at the end.
+    let src = "package org.example\ntypeCollection MyTypes {\n    struct Fine {\n        UInt8 a\n    }\n";
+    let failure = parse_with_recovery(src).unwrap_err();
+    assert_eq!(failure.found, None);
+}
+#[test]
+fn test_recovery_5() {
+    // Garbage between declarations is skipped on its own.
+    let src = "package org.example\ninterface MyInterface {\n    method first {\n    }\n    +++ ???\n    method second {\n    }\n}\n";
+    let recovered = parse_with_recovery(src).unwrap();
+    assert_eq!(recovered.failures.len(), 1);
+    let interface = children(&recovered.publisher, grammar_node(&recovered.publisher))[1];
+    let skipped: Vec<String> = children(&recovered.publisher, interface)
+        .iter()
+        .filter(|node| node.rule == Rules::skipped)
+        .map(|node| node.get_string(src))
+        .collect();
+    assert_eq!(skipped, vec!["+++ ???"]);
+}
+#[test]
+fn test_recovery_6() {
+    // Parsing resumes after the last item before each skip, the tree is the same as parsing the
+    // file with every skipped region blanked out from the start.
+    let src = r#"package org.example
+import model "common.fidl"
+import broken from
+// Between the imports and the declarations
+typeCollection MyTypes {
+    struct Broken {
+        UInt8
+    }
+}
+interface First {
+    metod doThing {
+    }
+}
+// Between declarations
+interface Second {
+    method fine {
+    }
+    +++
+}
+"#;
+    let recovered = parse_with_recovery(src).unwrap();
+    let lines: Vec<usize> = recovered.failures.iter().map(|f| f.line).collect();
+    assert_eq!(lines, vec![3, 8, 11, 18]);
+
+    let mut blanked = src.to_string();
+    blank_skipped(&recovered.publisher, Key(0), &mut blanked);
+    assert_eq!(blanked.matches("metod").count(), 0);
+    let (result, position, publisher, _) = minimal_fidl_parser::parse(&blanked);
+    assert_eq!((result, position), (true, src.len() as u32));
+    assert_eq!(
+        flatten(&recovered.publisher, Key(0)),
+        flatten(&publisher, Key(0))
+    );
+}
@@ -0,0 +1,50 @@
+use minimal_fidl_parser::{
+    parse, parse_with_context, BasicContext, Context, Key, SparseContext, RULES_SIZE,
+};
+use std::cell::RefCell;
+use std::fs;
+
+#[test]
+fn test_sparse_context_1() {
+    // A tiny window evicts results the parse still backtracks to, the tree must not change.
+    for entry in fs::read_dir("tests/grammar_test_files").unwrap() {
+        let src = fs::read_to_string(entry.unwrap().path()).unwrap();
+        let context = RefCell::new(SparseContext::with_window(16, RULES_SIZE as usize));
+        let sparse = parse_with_context(&src, &context);
+        let dense = parse(&src);
+        assert_eq!((sparse.0, sparse.1), (dense.0, dense.1));
+        assert!(sparse.2 == dense.2);
+        assert_eq!(sparse.3, dense.3);
+    }
+}
+
+#[test]
+fn test_sparse_context_2() {
+    let declaration = fs::read_to_string("tests/grammar_test_files/05-CoverageInterface.fidl")
+        .unwrap()
+        .replacen("package", "//", 1);
+    let src = format!("package org.example\n{}", declaration.repeat(20));
+    let sparse = RefCell::new(SparseContext::with_window(256, RULES_SIZE as usize));
+    let (result, position, _, _) = parse_with_context(&src, &sparse);
+    assert_eq!((result, position), (true, src.len() as u32));
+    let everything = RefCell::new(SparseContext::with_window(u32::MAX, RULES_SIZE as usize));
+    parse_with_context(&src, &everything);
+    // Only the results near the end are kept rather than ones for the whole source.
+    assert!(sparse.borrow().cache_len() * 20 < everything.borrow().cache_len());
+}
+
+#[test]
+fn test_sparse_context_3() {
+    // Failed rules are dropped as they fail, so the tree only holds what matched.
+    let src = fs::read_to_string("tests/grammar_test_files/05-CoverageInterface.fidl").unwrap();
+    let sparse = RefCell::new(SparseContext::with_window(256, RULES_SIZE as usize));
+    let (result, _, cleared, _) = parse_with_context(&src, &sparse);
+    assert!(result);
+    let sparse = sparse.borrow();
+    let publisher = sparse.publisher();
+    assert!((0..publisher.len()).all(|index| publisher.get_node(Key(index as u32)).result));
+    let dense = RefCell::new(BasicContext::new(src.len(), RULES_SIZE as usize));
+    let (_, _, dense_cleared, _) = parse_with_context(&src, &dense);
+    assert!(cleared == dense_cleared);
+    assert!(dense.borrow().publisher().len() > publisher.len() * 4);
+}
@@ -73,3 +73,21 @@ fn test_structure_6() {
     let result = shared(src, structure::<BasicContext>, Rules::structure);
     assert_eq!(result, (true, src.len() as u32));
 }
+
+#[test]
+fn test_structure_7() {
+    let src = r#"struct MyDerivedStruct extends MyTypes.MyBaseStruct {
+			UInt16 se01
+		}"#;
+    let result = shared(src, structure::<BasicContext>, Rules::structure);
+    assert_eq!(result, (true, src.len() as u32));
+}
+
+#[test]
+fn test_structure_8() {
+    let src = r#"struct MyPolymorphicStruct polymorphic {
+			UInt16 se01
+		}"#;
+    let result = shared(src, structure::<BasicContext>, Rules::structure);
+    assert_eq!(result, (true, src.len() as u32));
+}
@@ -20,3 +20,21 @@ fn test_number_3() {
     let result = shared(src, type_ref::<BasicContext>, Rules::type_ref);
     assert_eq!(result, (true, src.len() as u32));
 }
+#[test]
+fn test_number_4() {
+    let src = r#"Integer(-5, 100)"#;
+    let result = shared(src, type_ref::<BasicContext>, Rules::type_ref);
+    assert_eq!(result, (true, src.len() as u32));
+}
+#[test]
+fn test_number_5() {
+    let src = r#"Integer( minInt , 0xFF )[]"#;
+    let result = shared(src, type_ref::<BasicContext>, Rules::type_ref);
+    assert_eq!(result, (true, src.len() as u32));
+}
+#[test]
+fn test_number_6() {
+    let src = r#"Integer(0, maxInt"#;
+    let result = shared(src, type_ref::<BasicContext>, Rules::type_ref);
+    assert_eq!(result, (true, 7));
+}
@@ -24,3 +24,27 @@ fn test_type_collection_2() {
     let result = shared(src, type_collection::<BasicContext>, Rules::type_collection);
     assert_eq!(result, (true, src.len() as u32));
 }
+
+#[test]
+fn test_type_collection_3() {
+    let src = r#"typeCollection MyTypes {
+	union MyUnion extends BaseUnion {
+		UInt16 e1
+		Float e2
+	}
+}"#;
+    let result = shared(src, type_collection::<BasicContext>, Rules::type_collection);
+    assert_eq!(result, (true, src.len() as u32));
+}
+
+#[test]
+fn test_type_collection_4() {
+    let src = r#"typeCollection MyTypes {
+	map MyMap05 { UInt16 to String }
+	struct MyStruct08 {
+		MyMap05 se01
+	}
+}"#;
+    let result = shared(src, type_collection::<BasicContext>, Rules::type_collection);
+    assert_eq!(result, (true, src.len() as u32));
+}
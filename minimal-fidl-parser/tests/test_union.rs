@@ -0,0 +1,32 @@
+use minimal_fidl_parser::{union, BasicContext, Rules};
+mod shared;
+use shared::shared;
+
+#[test]
+fn test_union_1() {
+    let src = r#"<** @description: Either a name or an id. **>
+	union NameOrId {
+		String name
+		UInt32 id
+	}"#;
+    let result = shared(src, union::<BasicContext>, Rules::union);
+    assert_eq!(result, (true, src.len() as u32));
+}
+
+#[test]
+fn test_union_2() {
+    let src = r#"union MyUnion02 extends MyUnion01 {
+		UInt8[] se01 // Comment
+		Boolean se02
+	}"#;
+    let result = shared(src, union::<BasicContext>, Rules::union);
+    assert_eq!(result, (true, src.len() as u32));
+}
+
+#[test]
+fn test_union_3() {
+    let src = r#"	// empty union
+	union MyUnion03 { }"#;
+    let result = shared(src, union::<BasicContext>, Rules::union);
+    assert_eq!(result, (true, src.len() as u32));
+}
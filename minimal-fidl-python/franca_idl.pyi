@@ -1,5 +1,5 @@
 # Should provide typestub for franca_idl_rs
-from typing import Optional
+from typing import Any, Optional
 from pathlib import Path
 
 def _respond_42() -> int:
@@ -18,12 +18,37 @@ def load_fidl_project(dir: Path) -> Optional[list[FidlFile]]:
     Throws a ValueError if it cannot read or parse a fidl file for some reason.
     '''
 
+class FidlProject:
+    files: list[FidlFile]
+
+    def __init__(self, dir: Path) -> None:
+        '''
+        Parses every fidl file in dir along with the files they import.
+        Throws a ValueError if it cannot read or parse a fidl file for some reason.
+        '''
+    def package_files(self, package: str) -> list[FidlFile]:
+        '''The files declaring package, i.e "org.example".'''
+    def find_interface(self, name: str) -> Optional[FidlInterface]:
+        '''Finds an interface by its fully qualified name, i.e "org.example.MyInterface".'''
+    def types_in_package(self, package: str) -> list[str]:
+        '''The fully qualified names of every type declared in package.'''
+    def dependencies(self, file_path: Path) -> list[str]:
+        '''The files that file_path imports.'''
+    def reverse_dependencies(self, file_path: Path) -> list[str]:
+        '''The files that import file_path.'''
+    def unresolved_types(self) -> list[str]:
+        '''An error message for every type reference that is unresolved or ambiguous.'''
+
 class FidlTypeCollection:
     annotations: list[FidlAnnotation]
     name: str
     version: Optional[FidlVersion]
     typedefs: list[FidlTypeDef]
+    constants: list[FidlConstant]
     structures: list[FidlStructure]
+    unions: list[FidlUnion]
+    maps: list[FidlMap]
+    arrays: list[FidlArrayType]
     enumerations: list[FidlEnumeration]
 
 class FidlEnumValue:
@@ -34,6 +59,7 @@ class FidlEnumValue:
 class FidlEnumeration:
     annotations: list[FidlAnnotation]
     name: str
+    extends: Optional[str]
     values: list[FidlEnumValue]
 
 class FidlMethod:
@@ -41,29 +67,77 @@ class FidlMethod:
     name: str
     input_parameters: list[FidlVariableDeclaration]
     output_parameters: list[FidlVariableDeclaration]
+    error_type: Optional[str]
+    error_enumeration: Optional[FidlEnumeration]
+    fire_and_forget: bool
+
+class FidlBroadcast:
+    annotations: list[FidlAnnotation]
+    name: str
+    selective: bool
+    output_parameters: list[FidlVariableDeclaration]
 
 class FidlTypeDef:
     annotations: list[FidlAnnotation]
     name: str
     type_name: str
     is_array: bool
+    range: Optional[tuple[int, int]]
 
+class FidlConstant:
+    annotations: list[FidlAnnotation]
+    name: str
+    type_name: str
+    is_array: bool
+    range: Optional[tuple[int, int]]
+    value: Any
+    '''int, float, bool, str, a list for array initializers or a dict for struct initializers'''
 
 class FidlVariableDeclaration:
     annotations: list[FidlAnnotation]
     name: str
     type_name: str
     is_array: bool
+    range: Optional[tuple[int, int]]
+    length: Optional[int]
 
 class FidlStructure:
     annotations: list[FidlAnnotation]
     name: str
+    extends: Optional[str]
+    polymorphic: bool
     ontents: list[FidlVariableDeclaration]
 
+class FidlUnion:
+    annotations: list[FidlAnnotation]
+    name: str
+    extends: Optional[str]
+    contents: list[FidlVariableDeclaration]
+
+class FidlArrayType:
+    annotations: list[FidlAnnotation]
+    name: str
+    type_name: str
+    is_array: bool
+    range: Optional[tuple[int, int]]
+    length: Optional[int]
+
+class FidlMap:
+    annotations: list[FidlAnnotation]
+    name: str
+    key_type: str
+    key_range: Optional[tuple[int, int]]
+    value_type: str
+    value_is_array: bool
+    value_range: Optional[tuple[int, int]]
+
 class FidlAttribute:
     annotations: list[FidlAnnotation]
     name: str
     type_name: str
+    readonly: bool
+    no_subscriptions: bool
+    range: Optional[tuple[int, int]]
 
 class FidlPackage:
     path: list[str]
@@ -86,12 +160,18 @@ class FidlVersion:
 
 class FidlInterface:
     name: str
+    extends: Optional[str]
     version: Optional[FidlVersion]
     annotations: list[FidlAnnotation]
     attributes: list[FidlAttribute]
     structures: list[FidlStructure]
+    unions: list[FidlUnion]
+    maps: list[FidlMap]
+    arrays: list[FidlArrayType]
     typedefs: list[FidlTypeDef]
+    constants: list[FidlConstant]
     methods: list[FidlMethod]
+    broadcasts: list[FidlBroadcast]
     enumerations: list[FidlEnumeration]
 
 class FidlFile:
@@ -8,28 +8,84 @@ mod franca_idl {
     use std::path::PathBuf;
 
     use minimal_fidl_collect::{
-        Annotation, Attribute, EnumValue, Enumeration, FidlFileRs, FidlProject, FileError,
-        ImportModel, ImportNamespace, Interface, Method, Package, Structure, TypeCollection,
-        TypeDef, VariableDeclaration, Version,
+        Annotation, ArrayType, Attribute, Broadcast, Constant, ConstantValue, EnumValue, Enumeration, FidlFileRs, FidlProject as FidlProjectRs, FileError,
+        ImportModel, ImportNamespace, Interface, Map, Method, MethodError, Package, Structure, TypeCollection,
+        TypeDef, Union, VariableDeclaration, Version,
     };
     use pyo3::exceptions::PyValueError;
     use pyo3::prelude::*;
+    use pyo3::types::{PyDict, PyList};
     #[pyfunction]
     fn _respond_42() -> u8 {
         42
     }
     #[pyfunction]
-    fn load_fidl_project(dir: PathBuf) -> Result<Vec<FidlFile>, PyErr> {
-        match FidlProject::new(dir) {
-            Err(e) => Err(PyValueError::new_err(e.to_string())),
-            Ok(file_paths) => {
-                let mut fidl_files: Vec<FidlFile> = Vec::new();
-                for path in file_paths {
-                    let fidl_file = FidlFile::new(path.as_os_str().to_string_lossy().to_string())?;
-                    fidl_files.push(fidl_file);
-                }
-                Ok(fidl_files)
-            }
+    fn load_fidl_project(dir: PathBuf) -> Result<Vec<FidlFile>, FidlFileError> {
+        Ok(FidlProject::new(dir)?.files)
+    }
+
+    #[pyclass(name = "FidlProject", frozen)]
+    struct FidlProject {
+        #[pyo3(get)]
+        pub files: Vec<FidlFile>,
+        project: FidlProjectRs,
+    }
+    #[pymethods]
+    impl FidlProject {
+        #[new]
+        fn new(dir: PathBuf) -> Result<Self, FidlFileError> {
+            let project = FidlProjectRs::new(dir)?;
+            let files = project
+                .files()
+                .map(|(path, file)| FidlFile::from_path(path, file))
+                .collect();
+            Ok(FidlProject { files, project })
+        }
+
+        fn package_files(&self, package: &str) -> Vec<FidlFile> {
+            self.project
+                .package_files(package)
+                .into_iter()
+                .map(|(path, file)| FidlFile::from_path(path, file))
+                .collect()
+        }
+
+        fn find_interface(&self, name: &str) -> Option<FidlInterface> {
+            self.project
+                .find_interface(name)
+                .map(|(_, interface)| FidlInterface::from(interface))
+        }
+
+        fn types_in_package(&self, package: &str) -> Vec<String> {
+            self.project
+                .types_in_package(package)
+                .iter()
+                .map(|symbol| symbol.qualified_name())
+                .collect()
+        }
+
+        fn dependencies(&self, file_path: PathBuf) -> Vec<String> {
+            self.project
+                .dependencies(&file_path)
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect()
+        }
+
+        fn reverse_dependencies(&self, file_path: PathBuf) -> Vec<String> {
+            self.project
+                .reverse_dependencies(&file_path)
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect()
+        }
+
+        fn unresolved_types(&self) -> Vec<String> {
+            self.project
+                .check()
+                .iter()
+                .map(|error| error.to_string())
+                .collect()
         }
     }
 
@@ -65,8 +121,8 @@ mod franca_idl {
         #[pyo3(get)]
         pub type_collections: Vec<FidlTypeCollection>,
     }
-    impl From<FidlFileRs> for FidlFile {
-        fn from(item: FidlFileRs) -> Self {
+    impl From<&FidlFileRs> for FidlFile {
+        fn from(item: &FidlFileRs) -> Self {
             FidlFile {
                 file_path: None,
                 interfaces: item
@@ -92,24 +148,32 @@ mod franca_idl {
                     .collect(),
                 package: item
                     .package
-                    .and_then(|package| Some(FidlPackage::from(&package))),
+                    .as_ref()
+                    .and_then(|package| Some(FidlPackage::from(package))),
             }
         }
     }
+    impl FidlFile {
+        fn from_path(path: &std::path::Path, file: &FidlFileRs) -> Self {
+            let mut fidl_file = FidlFile::from(file);
+            fidl_file.file_path = Some(path.to_string_lossy().to_string());
+            fidl_file
+        }
+    }
     #[pymethods]
     impl FidlFile {
         #[new]
         fn new(file_path: String) -> Result<Self, FidlFileError> {
-            let result = FidlProject::generate_file(file_path.clone())?;
-            let mut fidl_file = FidlFile::from(result);
+            let result = FidlProjectRs::generate_file(file_path.clone())?;
+            let mut fidl_file = FidlFile::from(&result);
             fidl_file.file_path = Some(file_path);
             Ok(fidl_file)
         }
 
         #[staticmethod]
         fn new_from_string(file_string: String) -> Result<Self, FidlFileError> {
-            let result = FidlProject::generate_file_from_string(file_string)?;
-            Ok(FidlFile::from(result))
+            let result = FidlProjectRs::generate_file_from_string(file_string)?;
+            Ok(FidlFile::from(&result))
         }
 
         fn __str__(&self) -> String {
@@ -128,8 +192,16 @@ mod franca_idl {
         #[pyo3(get)]
         pub typedefs: Vec<FidlTypeDef>,
         #[pyo3(get)]
+        pub constants: Vec<FidlConstant>,
+        #[pyo3(get)]
         pub structures: Vec<FidlStructure>,
         #[pyo3(get)]
+        pub unions: Vec<FidlUnion>,
+        #[pyo3(get)]
+        pub maps: Vec<FidlMap>,
+        #[pyo3(get)]
+        pub arrays: Vec<FidlArrayType>,
+        #[pyo3(get)]
         pub enumerations: Vec<FidlEnumeration>,
     }
     #[pymethods]
@@ -158,11 +230,23 @@ mod franca_idl {
                     .iter()
                     .map(|a| FidlStructure::from(a))
                     .collect(),
+                unions: iface.unions.iter().map(|a| FidlUnion::from(a)).collect(),
+                maps: iface.maps.iter().map(|a| FidlMap::from(a)).collect(),
+                arrays: iface
+                    .arrays
+                    .iter()
+                    .map(|a| FidlArrayType::from(a))
+                    .collect(),
                 typedefs: iface
                     .typedefs
                     .iter()
                     .map(|a| FidlTypeDef::from(a))
                     .collect(),
+                constants: iface
+                    .constants
+                    .iter()
+                    .map(|a| FidlConstant::from(a))
+                    .collect(),
                 enumerations: iface
                     .enumerations
                     .iter()
@@ -180,16 +264,28 @@ mod franca_idl {
         #[pyo3(get)]
         pub name: String,
         #[pyo3(get)]
+        pub extends: Option<String>,
+        #[pyo3(get)]
         pub version: Option<FidlVersion>,
         #[pyo3(get)]
         pub attributes: Vec<FidlAttribute>,
         #[pyo3(get)]
         pub structures: Vec<FidlStructure>,
         #[pyo3(get)]
+        pub unions: Vec<FidlUnion>,
+        #[pyo3(get)]
+        pub maps: Vec<FidlMap>,
+        #[pyo3(get)]
+        pub arrays: Vec<FidlArrayType>,
+        #[pyo3(get)]
         pub typedefs: Vec<FidlTypeDef>,
         #[pyo3(get)]
+        pub constants: Vec<FidlConstant>,
+        #[pyo3(get)]
         pub methods: Vec<FidlMethod>,
         #[pyo3(get)]
+        pub broadcasts: Vec<FidlBroadcast>,
+        #[pyo3(get)]
         pub enumerations: Vec<FidlEnumeration>,
     }
     #[pymethods]
@@ -211,6 +307,7 @@ mod franca_idl {
                 .collect();
             FidlInterface {
                 name: iface.name.clone(),
+                extends: iface.extends.as_ref().map(|e| e.name.clone()),
                 version,
                 annotations,
                 attributes: iface
@@ -223,12 +320,29 @@ mod franca_idl {
                     .iter()
                     .map(|a| FidlStructure::from(a))
                     .collect(),
+                unions: iface.unions.iter().map(|a| FidlUnion::from(a)).collect(),
+                maps: iface.maps.iter().map(|a| FidlMap::from(a)).collect(),
+                arrays: iface
+                    .arrays
+                    .iter()
+                    .map(|a| FidlArrayType::from(a))
+                    .collect(),
                 typedefs: iface
                     .typedefs
                     .iter()
                     .map(|a| FidlTypeDef::from(a))
                     .collect(),
+                constants: iface
+                    .constants
+                    .iter()
+                    .map(|a| FidlConstant::from(a))
+                    .collect(),
                 methods: iface.methods.iter().map(|a| FidlMethod::from(a)).collect(),
+                broadcasts: iface
+                    .broadcasts
+                    .iter()
+                    .map(|a| FidlBroadcast::from(a))
+                    .collect(),
                 enumerations: iface
                     .enumerations
                     .iter()
@@ -293,6 +407,12 @@ mod franca_idl {
         pub name: String,
         #[pyo3(get)]
         pub type_name: String,
+        #[pyo3(get)]
+        pub readonly: bool,
+        #[pyo3(get)]
+        pub no_subscriptions: bool,
+        #[pyo3(get)]
+        pub range: Option<(i64, i64)>,
     }
     #[pymethods]
     impl FidlAttribute {
@@ -310,6 +430,9 @@ mod franca_idl {
                     .collect(),
                 name: item.name.clone(),
                 type_name: item.type_n.clone(),
+                readonly: item.readonly,
+                no_subscriptions: item.no_subscriptions,
+                range: item.range.map(|r| (r.min, r.max)),
             }
         }
     }
@@ -321,6 +444,10 @@ mod franca_idl {
         #[pyo3(get)]
         pub name: String,
         #[pyo3(get)]
+        pub extends: Option<String>,
+        #[pyo3(get)]
+        pub polymorphic: bool,
+        #[pyo3(get)]
         pub contents: Vec<FidlVariableDeclaration>,
     }
     #[pymethods]
@@ -338,6 +465,8 @@ mod franca_idl {
                     .map(|a| FidlAnnotation::from(a))
                     .collect(),
                 name: item.name.clone(),
+                extends: item.extends.as_ref().map(|e| e.name.clone()),
+                polymorphic: item.polymorphic,
                 contents: item
                     .contents
                     .iter()
@@ -346,6 +475,83 @@ mod franca_idl {
             }
         }
     }
+    #[pyclass(name = "FidlUnion", frozen)]
+    #[derive(Clone, Debug)]
+    struct FidlUnion {
+        #[pyo3(get)]
+        pub annotations: Vec<FidlAnnotation>,
+        #[pyo3(get)]
+        pub name: String,
+        #[pyo3(get)]
+        pub extends: Option<String>,
+        #[pyo3(get)]
+        pub contents: Vec<FidlVariableDeclaration>,
+    }
+    #[pymethods]
+    impl FidlUnion {
+        fn __str__(&self) -> String {
+            format!("{:#?}", self)
+        }
+    }
+    impl From<&Union> for FidlUnion {
+        fn from(item: &Union) -> Self {
+            FidlUnion {
+                annotations: item
+                    .annotations
+                    .iter()
+                    .map(|a| FidlAnnotation::from(a))
+                    .collect(),
+                name: item.name.clone(),
+                extends: item.extends.as_ref().map(|e| e.name.clone()),
+                contents: item
+                    .contents
+                    .iter()
+                    .map(|a| FidlVariableDeclaration::from(a))
+                    .collect(),
+            }
+        }
+    }
+    #[pyclass(name = "FidlMap", frozen)]
+    #[derive(Clone, Debug)]
+    struct FidlMap {
+        #[pyo3(get)]
+        pub annotations: Vec<FidlAnnotation>,
+        #[pyo3(get)]
+        pub name: String,
+        #[pyo3(get)]
+        pub key_type: String,
+        #[pyo3(get)]
+        pub key_range: Option<(i64, i64)>,
+        #[pyo3(get)]
+        pub value_type: String,
+        #[pyo3(get)]
+        pub value_is_array: bool,
+        #[pyo3(get)]
+        pub value_range: Option<(i64, i64)>,
+    }
+    #[pymethods]
+    impl FidlMap {
+        fn __str__(&self) -> String {
+            format!("{:#?}", self)
+        }
+    }
+    impl From<&Map> for FidlMap {
+        fn from(item: &Map) -> Self {
+            FidlMap {
+                annotations: item
+                    .annotations
+                    .iter()
+                    .map(|a| FidlAnnotation::from(a))
+                    .collect(),
+                name: item.name.clone(),
+                key_type: item.key_type.clone(),
+                key_range: item.key_range.map(|r| (r.min, r.max)),
+                value_type: item.value_type.clone(),
+                value_is_array: item.value_is_array,
+                value_range: item.value_range.map(|r| (r.min, r.max)),
+            }
+        }
+    }
     #[pyclass(name = "FidlVariableDeclaration", frozen)]
     #[derive(Clone, Debug)]
     struct FidlVariableDeclaration {
@@ -357,6 +563,10 @@ mod franca_idl {
         pub type_name: String,
         #[pyo3(get)]
         pub is_array: bool,
+        #[pyo3(get)]
+        pub range: Option<(i64, i64)>,
+        #[pyo3(get)]
+        pub length: Option<u64>,
     }
     #[pymethods]
     impl FidlVariableDeclaration {
@@ -375,6 +585,8 @@ mod franca_idl {
                 name: item.name.clone(),
                 type_name: item.type_n.clone(),
                 is_array: item.is_array,
+                range: item.range.map(|r| (r.min, r.max)),
+                length: item.length,
             }
         }
     }
@@ -390,6 +602,8 @@ mod franca_idl {
         pub type_name: String,
         #[pyo3(get)]
         pub is_array: bool,
+        #[pyo3(get)]
+        pub range: Option<(i64, i64)>,
     }
     #[pymethods]
     impl FidlTypeDef {
@@ -408,6 +622,111 @@ mod franca_idl {
                 name: item.name.clone(),
                 type_name: item.type_n.clone(),
                 is_array: item.is_array,
+                range: item.range.map(|r| (r.min, r.max)),
+            }
+        }
+    }
+
+    #[pyclass(name = "FidlArrayType", frozen)]
+    #[derive(Clone, Debug)]
+    struct FidlArrayType {
+        #[pyo3(get)]
+        pub annotations: Vec<FidlAnnotation>,
+        #[pyo3(get)]
+        pub name: String,
+        #[pyo3(get)]
+        pub type_name: String,
+        #[pyo3(get)]
+        pub is_array: bool,
+        #[pyo3(get)]
+        pub range: Option<(i64, i64)>,
+        #[pyo3(get)]
+        pub length: Option<u64>,
+    }
+    #[pymethods]
+    impl FidlArrayType {
+        fn __str__(&self) -> String {
+            format!("{:#?}", self)
+        }
+    }
+    impl From<&ArrayType> for FidlArrayType {
+        fn from(item: &ArrayType) -> Self {
+            FidlArrayType {
+                annotations: item
+                    .annotations
+                    .iter()
+                    .map(|a| FidlAnnotation::from(a))
+                    .collect(),
+                name: item.name.clone(),
+                type_name: item.type_n.clone(),
+                is_array: item.is_array,
+                range: item.range.map(|r| (r.min, r.max)),
+                length: item.length,
+            }
+        }
+    }
+
+    #[pyclass(name = "FidlConstant", frozen)]
+    #[derive(Clone, Debug)]
+    struct FidlConstant {
+        #[pyo3(get)]
+        pub annotations: Vec<FidlAnnotation>,
+        #[pyo3(get)]
+        pub name: String,
+        #[pyo3(get)]
+        pub type_name: String,
+        #[pyo3(get)]
+        pub is_array: bool,
+        #[pyo3(get)]
+        pub range: Option<(i64, i64)>,
+        pub value: ConstantValue,
+    }
+    #[pymethods]
+    impl FidlConstant {
+        fn __str__(&self) -> String {
+            format!("{:#?}", self)
+        }
+        #[getter]
+        fn value<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+            constant_value_to_py(py, &self.value)
+        }
+    }
+    // Struct initializers become a dict of field name to value.
+    fn constant_value_to_py<'py>(py: Python<'py>, value: &ConstantValue) -> PyResult<Bound<'py, PyAny>> {
+        Ok(match value {
+            ConstantValue::Integer(i) => i.into_pyobject(py)?.into_any(),
+            ConstantValue::Float(f) => f.into_pyobject(py)?.into_any(),
+            ConstantValue::Boolean(b) => b.into_pyobject(py)?.to_owned().into_any(),
+            ConstantValue::String(s) => s.into_pyobject(py)?.into_any(),
+            ConstantValue::Array(values) => {
+                let list = PyList::empty(py);
+                for value in values {
+                    list.append(constant_value_to_py(py, value)?)?;
+                }
+                list.into_any()
+            }
+            ConstantValue::Struct(fields) => {
+                let dict = PyDict::new(py);
+                for (name, value) in fields {
+                    dict.set_item(name, constant_value_to_py(py, value)?)?;
+                }
+                dict.into_any()
+            }
+        })
+    }
+    impl From<&Constant> for FidlConstant {
+        fn from(item: &Constant) -> Self {
+            FidlConstant {
+                annotations: item
+                    .annotations
+                    .iter()
+                    .map(|a| FidlAnnotation::from(a))
+                    .collect(),
+                name: item.name.clone(),
+                type_name: item.type_n.clone(),
+                is_array: item.is_array,
+                range: item.range.map(|r| (r.min, r.max)),
+                value: item.value.clone(),
             }
         }
     }
@@ -423,6 +742,14 @@ mod franca_idl {
         pub input_parameters: Vec<FidlVariableDeclaration>,
         #[pyo3(get)]
         pub output_parameters: Vec<FidlVariableDeclaration>,
+        /// Name of the error type, set for both inline and referenced errors.
+        #[pyo3(get)]
+        pub error_type: Option<String>,
+        /// Only set for inline `error { ... }` blocks.
+        #[pyo3(get)]
+        pub error_enumeration: Option<FidlEnumeration>,
+        #[pyo3(get)]
+        pub fire_and_forget: bool,
     }
     #[pymethods]
     impl FidlMethod {
@@ -432,7 +759,18 @@ mod franca_idl {
     }
     impl From<&Method> for FidlMethod {
         fn from(item: &Method) -> Self {
+            let (error_type, error_enumeration) = match &item.errors {
+                None => (None, None),
+                Some(MethodError::Inline(enumeration)) => (
+                    Some(enumeration.name.clone()),
+                    Some(FidlEnumeration::from(enumeration)),
+                ),
+                Some(MethodError::Reference(type_n)) => (Some(type_n.clone()), None),
+            };
             FidlMethod {
+                error_type,
+                error_enumeration,
+                fire_and_forget: item.fire_and_forget,
                 annotations: item
                     .annotations
                     .iter()
@@ -452,6 +790,42 @@ mod franca_idl {
             }
         }
     }
+    #[pyclass(name = "FidlBroadcast", frozen)]
+    #[derive(Clone, Debug)]
+    struct FidlBroadcast {
+        #[pyo3(get)]
+        pub annotations: Vec<FidlAnnotation>,
+        #[pyo3(get)]
+        pub name: String,
+        #[pyo3(get)]
+        pub selective: bool,
+        #[pyo3(get)]
+        pub output_parameters: Vec<FidlVariableDeclaration>,
+    }
+    #[pymethods]
+    impl FidlBroadcast {
+        fn __str__(&self) -> String {
+            format!("{:#?}", self)
+        }
+    }
+    impl From<&Broadcast> for FidlBroadcast {
+        fn from(item: &Broadcast) -> Self {
+            FidlBroadcast {
+                annotations: item
+                    .annotations
+                    .iter()
+                    .map(|a| FidlAnnotation::from(a))
+                    .collect(),
+                name: item.name.clone(),
+                selective: item.selective,
+                output_parameters: item
+                    .output_parameters
+                    .iter()
+                    .map(|a| FidlVariableDeclaration::from(a))
+                    .collect(),
+            }
+        }
+    }
     #[pyclass(name = "FidlEnumeration", frozen)]
     #[derive(Clone, Debug)]
     struct FidlEnumeration {
@@ -460,6 +834,8 @@ mod franca_idl {
         #[pyo3(get)]
         pub name: String,
         #[pyo3(get)]
+        pub extends: Option<String>,
+        #[pyo3(get)]
         pub values: Vec<FidlEnumValue>,
     }
     #[pymethods]
@@ -477,6 +853,7 @@ mod franca_idl {
                     .map(|a| FidlAnnotation::from(a))
                     .collect(),
                 name: item.name.clone(),
+                extends: item.extends.as_ref().map(|e| e.name.clone()),
                 values: item.values.iter().map(|a| FidlEnumValue::from(a)).collect(),
             }
         }